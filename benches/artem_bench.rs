@@ -12,4 +12,6 @@ criterion_main!(
     benchmarks::outline::benches,
     //using the outline algorithm with hysteresis and double threshold
     benchmarks::hysteresis::benches,
+    //wide outputs with and without the parallel feature
+    benchmarks::wide::benches,
 );
@@ -6,5 +6,7 @@ pub mod size;
 pub mod outline;
 //outline version with hysteresis
 pub mod hysteresis;
+//wide outputs, to compare the sequential and parallel conversion
 ///Utils for loading different images.
 mod util;
+pub mod wide;
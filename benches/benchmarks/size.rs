@@ -24,7 +24,7 @@ fn size_10_benchmark(c: &mut Criterion) {
     group.bench_function("low resolution", |b| {
         b.iter_batched(
             util::load_low_res_image,
-            |data| artem::convert(data, &options.build()),
+            |data| artem::convert(data, &options.build().unwrap()),
             criterion::BatchSize::LargeInput,
         );
     });
@@ -32,7 +32,7 @@ fn size_10_benchmark(c: &mut Criterion) {
     group.bench_function("normal resolution", |b| {
         b.iter_batched(
             util::load_normal_res_image,
-            |data| artem::convert(data, &options.build()),
+            |data| artem::convert(data, &options.build().unwrap()),
             criterion::BatchSize::LargeInput,
         );
     });
@@ -40,7 +40,7 @@ fn size_10_benchmark(c: &mut Criterion) {
     group.bench_function("high resolution", |b| {
         b.iter_batched(
             util::load_high_res_image,
-            |data| artem::convert(data, &options.build()),
+            |data| artem::convert(data, &options.build().unwrap()),
             criterion::BatchSize::LargeInput,
         );
     });
@@ -69,7 +69,7 @@ fn size_100_benchmark(c: &mut Criterion) {
     group.bench_function("low resolution", |b| {
         b.iter_batched(
             util::load_low_res_image,
-            |data| artem::convert(data, &options.build()),
+            |data| artem::convert(data, &options.build().unwrap()),
             criterion::BatchSize::LargeInput,
         );
     });
@@ -77,7 +77,7 @@ fn size_100_benchmark(c: &mut Criterion) {
     group.bench_function("normal resolution", |b| {
         b.iter_batched(
             util::load_normal_res_image,
-            |data| artem::convert(data, &options.build()),
+            |data| artem::convert(data, &options.build().unwrap()),
             criterion::BatchSize::LargeInput,
         );
     });
@@ -85,7 +85,7 @@ fn size_100_benchmark(c: &mut Criterion) {
     group.bench_function("high resolution", |b| {
         b.iter_batched(
             util::load_high_res_image,
-            |data| artem::convert(data, &options.build()),
+            |data| artem::convert(data, &options.build().unwrap()),
             criterion::BatchSize::LargeInput,
         );
     });
@@ -114,7 +114,7 @@ fn size_500_benchmark(c: &mut Criterion) {
     group.bench_function("low resolution", |b| {
         b.iter_batched(
             util::load_low_res_image,
-            |data| artem::convert(data, &options.build()),
+            |data| artem::convert(data, &options.build().unwrap()),
             criterion::BatchSize::LargeInput,
         );
     });
@@ -122,7 +122,7 @@ fn size_500_benchmark(c: &mut Criterion) {
     group.bench_function("normal resolution", |b| {
         b.iter_batched(
             util::load_normal_res_image,
-            |data| artem::convert(data, &options.build()),
+            |data| artem::convert(data, &options.build().unwrap()),
             criterion::BatchSize::LargeInput,
         );
     });
@@ -130,7 +130,7 @@ fn size_500_benchmark(c: &mut Criterion) {
     group.bench_function("high resolution", |b| {
         b.iter_batched(
             util::load_high_res_image,
-            |data| artem::convert(data, &options.build()),
+            |data| artem::convert(data, &options.build().unwrap()),
             criterion::BatchSize::LargeInput,
         );
     });
@@ -0,0 +1,72 @@
+use std::num::NonZeroU32;
+
+use crate::benchmarks::util;
+use artem::config::{ConfigBuilder, Dithering, RenderMode};
+use criterion::{criterion_group, Criterion};
+
+/// Benchmarks for wide outputs with 400 columns.
+///
+/// Wide outputs convert a lot of tiles, so they show the difference between the sequential
+/// and parallel conversion the most. Run them with and without the `parallel` feature to compare both:
+/// `cargo bench --bench artem_bench -- wide` and `cargo bench --bench artem_bench --features parallel -- wide`.
+fn wide_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wide");
+
+    //use lower sample size for faster benchmarking
+    //it should still take long enough to see relevant changes in performance
+    group.sample_size(10);
+
+    let options = wide_options();
+    group.bench_function("default", |b| {
+        b.iter_batched(
+            util::load_high_res_image,
+            |data| artem::convert(data, &options.build().unwrap()),
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    //the dithering is always applied in order, after the tiles are converted
+    let mut dither_options = wide_options();
+    dither_options.dither(Dithering::FloydSteinberg);
+    group.bench_function("dithering", |b| {
+        b.iter_batched(
+            util::load_high_res_image,
+            |data| artem::convert(data, &dither_options.build().unwrap()),
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    let mut shape_options = wide_options();
+    shape_options.render_mode(RenderMode::Shape);
+    group.bench_function("shape", |b| {
+        b.iter_batched(
+            util::load_high_res_image,
+            |data| artem::convert(data, &shape_options.build().unwrap()),
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    //the blur, sobel and hysteresis passes of the outline are parallelized as well
+    let mut outline_options = wide_options();
+    outline_options.outline(true);
+    outline_options.hysteresis(true);
+    outline_options.edge_characters(true);
+    group.bench_function("outline", |b| {
+        b.iter_batched(
+            util::load_high_res_image,
+            |data| artem::convert(data, &outline_options.build().unwrap()),
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+/// Returns the default options with a target size of 400 columns.
+fn wide_options() -> ConfigBuilder {
+    let mut options = ConfigBuilder::new();
+    options.target_size(NonZeroU32::new(400).unwrap());
+    options
+}
+
+criterion_group!(benches, wide_benchmark);
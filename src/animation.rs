@@ -0,0 +1,295 @@
+//! Decoding and playback of animated images in the terminal.
+
+use std::{
+    io::{self, Cursor, Write},
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use image::{
+    codecs::{gif::GifDecoder, png::PngDecoder, webp::WebPDecoder},
+    AnimationDecoder, DynamicImage, Frames, ImageFormat, ImageResult,
+};
+
+/// Delay of frames, which do not specify one, browsers use the same delay for them.
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+/// Interval in which a stopped playback is checked while waiting for the next frame.
+const STOP_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Returns all frames of an animated GIF, APNG or WebP image, together with their delays.
+///
+/// Other images, including PNG and WebP images without an animation, are returned as a single frame.
+///
+/// # Errors
+/// Returns an error if the image can not be decoded.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let frames = decode_frames(&std::fs::read("assets/images/animation.gif")?)?;
+/// assert_eq!(3, frames.len());
+/// ```
+pub(crate) fn decode_frames(bytes: &[u8]) -> ImageResult<Vec<(DynamicImage, Duration)>> {
+    let frames = match image::guess_format(bytes)? {
+        ImageFormat::Gif => Some(GifDecoder::new(Cursor::new(bytes))?.into_frames()),
+        ImageFormat::Png => {
+            let decoder = PngDecoder::new(Cursor::new(bytes))?;
+            decoder.is_apng().then(|| decoder.apng().into_frames())
+        }
+        ImageFormat::WebP => {
+            let decoder = WebPDecoder::new(Cursor::new(bytes))?;
+            decoder.has_animation().then(|| decoder.into_frames())
+        }
+        _ => None,
+    };
+    match frames {
+        Some(frames) => collect_frames(frames),
+        None => Ok(vec![(image::load_from_memory(bytes)?, DEFAULT_DELAY)]),
+    }
+}
+
+/// Returns the decoded frames as images with their delays.
+fn collect_frames(frames: Frames) -> ImageResult<Vec<(DynamicImage, Duration)>> {
+    frames
+        .map(|frame| {
+            let frame = frame?;
+            let delay = Duration::from(frame.delay());
+            let delay = if delay.is_zero() {
+                DEFAULT_DELAY
+            } else {
+                delay
+            };
+            Ok((DynamicImage::ImageRgba8(frame.into_buffer()), delay))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_decode_frames {
+    use super::*;
+
+    #[test]
+    fn gif_has_all_frames() {
+        let bytes = std::fs::read("assets/images/animation.gif").unwrap();
+        let frames = decode_frames(&bytes).unwrap();
+        assert_eq!(3, frames.len());
+        for (frame, delay) in frames {
+            assert_eq!((30, 10), (frame.width(), frame.height()));
+            assert_eq!(Duration::from_millis(100), delay);
+        }
+    }
+
+    #[test]
+    fn still_image_is_single_frame() {
+        let bytes = std::fs::read("assets/images/standard_test_img.png").unwrap();
+        assert_eq!(1, decode_frames(&bytes).unwrap().len());
+    }
+
+    #[test]
+    fn invalid_image_fails() {
+        assert!(decode_frames(b"not an image").is_err());
+    }
+}
+
+/// Play the converted frames in place, by moving the cursor back to the start of the frame after each one.
+///
+/// Each frame is shown for its delay, the playback is repeated until it is stopped if `repeat` is set.
+/// The cursor is hidden during the playback and shown again afterwards, even if the playback is stopped early.
+///
+/// # Errors
+/// Returns an error if the frames can not be written.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let stopped = AtomicBool::new(false);
+/// play(&frames, false, &stopped, &mut std::io::stdout())?;
+/// ```
+pub(crate) fn play(
+    frames: &[(String, Duration)],
+    repeat: bool,
+    stopped: &AtomicBool,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    //hide the cursor, so it does not flicker at the end of each frame
+    writer.write_all(b"\x1b[?25l")?;
+    let result = play_frames(frames, repeat, stopped, writer);
+    writer.write_all(b"\x1b[?25h")?;
+    writer.flush()?;
+    result
+}
+
+/// Write the frames one after another, until all frames are played or the playback is stopped.
+fn play_frames(
+    frames: &[(String, Duration)],
+    repeat: bool,
+    stopped: &AtomicBool,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    //number of lines of the previous frame, which the cursor has to move up
+    let mut lines = 0;
+    loop {
+        for (frame, delay) in frames {
+            let start = Instant::now();
+            if lines > 0 {
+                write!(writer, "\x1b[{lines}A")?;
+            }
+            writer.write_all(frame.as_bytes())?;
+            writer.flush()?;
+            lines = frame.matches('\n').count();
+
+            //wait in small steps, so a stopped playback does not have to wait for long delays
+            while start.elapsed() < *delay {
+                if stopped.load(Ordering::Relaxed) {
+                    return Ok(());
+                }
+                thread::sleep(STOP_INTERVAL.min(delay.saturating_sub(start.elapsed())));
+            }
+            if stopped.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+        }
+        if !repeat {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_play {
+    use super::*;
+
+    fn frames() -> Vec<(String, Duration)> {
+        vec![
+            (String::from("ab\ncd\n"), Duration::ZERO),
+            (String::from("ef\ngh\n"), Duration::ZERO),
+        ]
+    }
+
+    #[test]
+    fn frames_are_played_in_place() {
+        let mut output = Vec::new();
+        play(&frames(), false, &AtomicBool::new(false), &mut output).unwrap();
+        assert_eq!(
+            "\x1b[?25lab\ncd\n\x1b[2Aef\ngh\n\x1b[?25h",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn stopped_playback_shows_cursor() {
+        let mut output = Vec::new();
+        play(&frames(), true, &AtomicBool::new(true), &mut output).unwrap();
+        assert_eq!(
+            "\x1b[?25lab\ncd\n\x1b[?25h",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
+
+/// Write the converted frames as an asciinema v2 `.cast` recording.
+///
+/// The header contains the size of the terminal in columns and rows, followed by one output event
+/// for each frame, which moves the cursor to the top left corner and writes the frame.
+/// The timestamp of each event is the sum of the delays of the previous frames.
+/// An empty event after the last frame keeps it visible for its delay.
+///
+/// # Errors
+/// Returns an error if the recording can not be written.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// write_cast(&frames, (80, 24), &mut file)?;
+/// ```
+pub(crate) fn write_cast(
+    frames: &[(String, Duration)],
+    (width, height): (u32, u32),
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    writeln!(
+        writer,
+        r#"{{"version": 2, "width": {width}, "height": {height}}}"#
+    )?;
+
+    let mut timestamp = Duration::ZERO;
+    for (frame, delay) in frames {
+        //the terminal has exactly the height of a frame, so the last line break would scroll it
+        let frame = frame.strip_suffix('\n').unwrap_or(frame);
+        //the recording is replayed as raw terminal output, where a line break does not return the cursor
+        let data = format!("\x1b[H{}", frame.replace('\n', "\r\n"));
+        writeln!(
+            writer,
+            r#"[{:.6}, "o", {}]"#,
+            timestamp.as_secs_f64(),
+            json_string(&data)
+        )?;
+        timestamp += *delay;
+    }
+    //an empty event at the end keeps the last frame visible for its delay
+    if !frames.is_empty() {
+        writeln!(writer, r#"[{:.6}, "o", ""]"#, timestamp.as_secs_f64())?;
+    }
+    Ok(())
+}
+
+/// Returns the text as a quoted JSON string, with all special and control characters escaped.
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for char in text.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            char if char.is_control() => json.push_str(&format!("\\u{:04x}", char as u32)),
+            char => json.push(char),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod test_write_cast {
+    use super::*;
+
+    #[test]
+    fn header_has_size() {
+        let mut output = Vec::new();
+        write_cast(&[], (80, 24), &mut output).unwrap();
+        assert_eq!(
+            "{\"version\": 2, \"width\": 80, \"height\": 24}\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn frames_have_timestamps() {
+        let frames = vec![
+            (String::from("ab\ncd\n"), Duration::from_millis(100)),
+            (String::from("ef\ngh\n"), Duration::from_millis(250)),
+        ];
+        let mut output = Vec::new();
+        write_cast(&frames, (2, 2), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let events: Vec<&str> = output.lines().skip(1).collect();
+        assert_eq!(
+            vec![
+                r#"[0.000000, "o", "\u001b[Hab\r\ncd"]"#,
+                r#"[0.100000, "o", "\u001b[Hef\r\ngh"]"#,
+                r#"[0.350000, "o", ""]"#,
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn json_escapes_special_chars() {
+        assert_eq!(
+            r#""\"quoted\" \\ \t \u001b[0m""#,
+            json_string("\"quoted\" \\ \t \x1b[0m")
+        );
+    }
+}
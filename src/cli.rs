@@ -27,10 +27,10 @@ pub fn build_cli() -> Command {
                     if cfg!(feature = "web_image")
                     {
                         //special help message with url help 
-                        "Paths or URLs to the target image. If the input is an URL, the image is downloaded and then converted. The original image is NOT altered."
+                        "Paths or URLs to the target image. If the input is an URL, the image is downloaded and then converted. Use - to read the image from stdin. Glob patterns like images/*.png are expanded, directories can be used with --output-dir. The original image is NOT altered."
                     } else {
                         //normal help text with only paths
-                        "Paths to the target image. The original image is NOT altered."
+                        "Paths to the target image. Use - to read the image from stdin. Glob patterns like images/*.png are expanded, directories can be used with --output-dir. The original image is NOT altered."
                     }
 
                 )
@@ -51,7 +51,18 @@ pub fn build_cli() -> Command {
                 //use "\" to keep this readable but still as a single line string
                 .help("Change the characters that are used to display the image.\
                 The first character should have the highest 'darkness' and the last should have the least (recommended to be a space ' '). \
-                A lower detail map is recommend for smaller images. Included characters can be used with the argument 0 | 1 | 2. If no characters are passed in, the default set will be used."),
+                A lower detail map is recommend for smaller images. Included characters can be used with the argument 0 | 1 | 2. If no characters are passed in, the default set will be used. \
+                Use --calibrate to order custom characters automatically."),
+        )
+        .arg(
+            Arg::new("calibrate")
+                .long("calibrate")
+                .value_parser(["none", "sort", "linear"])
+                .default_value("none")
+                .help("Calibrate the characters by measuring how much of each one is covered by ink, using an embedded bitmap font. \
+                Sort orders the characters from dark to light, linear also spaces them, so the brightness decreases evenly. \
+                This drops characters with nearly the same brightness and repeats characters, where the gap to the next one is large. \
+                Characters, which are not part of the font, are removed."),
         )
         .arg(
             Arg::new("size")
@@ -103,7 +114,7 @@ pub fn build_cli() -> Command {
             Arg::new("centerX")
                 .long("centerX")
                 .action(ArgAction::SetTrue)
-                .help("Center the image along the X-Axis/horizontally in the terminal."),
+                .help("Center the image along the X-Axis/horizontally in the terminal. Svg and png output files are never centered."),
         ).arg(
             Arg::new("centerY")
                 .long("centerY")
@@ -117,10 +128,121 @@ pub fn build_cli() -> Command {
                 .value_parser(value_parser!(PathBuf))
                 .value_hint(ValueHint::FilePath)
                 .help("Output file for non-colored ascii. If the output file is a plaintext file, no color will be used. The use color, either use a file with an \
-                .ansi extension, or an .html file, to convert the output to html. \
+                .ansi extension, an .html file, to convert the output to html, an .svg file, to convert the output to a scalable vector graphic, or a .png file, to render the output as an image. \
+                A .cast file exports all frames of animated images as an asciinema recording, which is never centered. \
                 .ansi files will consider environment variables when creating colored output, for example when COLORTERM is not set to truecolor,\
                 the resulting file will fallback to 8-bit colors."),
         )
+        .arg(
+            Arg::new("font-family")
+                .long("font-family")
+                .value_parser(value_parser!(String))
+                .default_value("monospace")
+                .value_hint(ValueHint::Other)
+                .help("Font family used for .svg output files. It can be any css font family list, \
+                it is recommended to only use monospace fonts."),
+        )
+        .arg(
+            Arg::new("cell-size")
+                .long("cell-size")
+                .value_name("WIDTHxHEIGHT")
+                .value_parser(parse_cell_size)
+                .default_value("8x16")
+                .value_hint(ValueHint::Other)
+                .help("Size of a single character in pixels for .png output files, for example 8x16."),
+        )
+        .arg(
+            Arg::new("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::DirPath)
+                .conflicts_with("output-file")
+                .help("Convert each input into its own file in the output directory, instead of writing all of them into a single output. \
+                The output files keep the name of their input, with the extension of the --format. \
+                Directory inputs are converted recursively, images which fail to convert are listed in a summary at the end."),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_parser(["txt", "ansi", "html", "svg", "png", "cast"])
+                .default_value("txt")
+                .requires("output-dir")
+                .help("Format of the files written to the --output-dir, it works the same as the extension of an --output file."),
+        )
+        .arg(
+            Arg::new("include")
+                .long("include")
+                .value_name("GLOB")
+                .action(ArgAction::Append)
+                .requires("output-dir")
+                .value_hint(ValueHint::Other)
+                .help("Only convert images in directory inputs, whose path inside the directory matches the glob pattern, for example '*.png'. \
+                It can be used multiple times, an image is converted if it matches any of the patterns."),
+        )
+        .arg(
+            Arg::new("exclude")
+                .long("exclude")
+                .value_name("GLOB")
+                .action(ArgAction::Append)
+                .requires("output-dir")
+                .value_hint(ValueHint::Other)
+                .help("Skip images in directory inputs, whose path inside the directory matches the glob pattern, for example 'thumbnails/*'. \
+                It can be used multiple times and takes precedence over --include."),
+        )
+        .arg(
+            Arg::new("animate")
+                .long("animate")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("output-file")
+                .conflicts_with("output-dir")
+                .help("Play animated GIF, APNG and WebP images in the terminal, instead of only converting their first frame. \
+                Each frame is converted with the same options and shown in place for its delay."),
+        )
+        .arg(
+            Arg::new("loop")
+                .long("loop")
+                .action(ArgAction::SetTrue)
+                .requires("animate")
+                .help("Repeat the animation until it is stopped with Ctrl-C."),
+        )
+        .arg(
+            Arg::new("fps")
+                .long("fps")
+                .value_parser(parse_fps)
+                .value_hint(ValueHint::Other)
+                .help("Play or export the animation with the given frames per second, instead of the delays stored in the image. \
+                It is used with --animate or .cast output files."),
+        )
+        .arg(
+            Arg::new("mode")
+                .long("mode")
+                .value_parser(["ascii", "half-block", "braille", "quadrant", "sextant", "shape"])
+                .default_value("ascii")
+                .help("Mode used to render the image. Ascii uses the characters of the density, \
+                half-block uses the half block characters (▀▄) with two pixels per character, which doubles the vertical resolution. \
+                With colors, the top pixel is used as the foreground and the bottom pixel as the background color. \
+                Braille uses the braille patterns (⣿) with 2x4 dots per character, which works well for line art and outlines. \
+                Quadrant (▚) and sextant use 2x2 and 2x3 pixels per character, which are split into the two best matching colors. \
+                Sextants are not supported by every font, in this case use --sextant-fallback to draw them as quadrants. \
+                Shape uses the characters of the density as well, but chooses them by comparing the shape of their glyphs with the image, \
+                so lines are drawn using characters like / | _, which works well with --outline. Dithering is ignored by it."),
+        )
+        .arg(
+            Arg::new("sextant-fallback")
+                .long("sextant-fallback")
+                .action(ArgAction::SetTrue)
+                .help("Draw the sextants as the quadrants of their top and bottom row, for fonts which do not support sextants. \
+                The output keeps the size of the sextants, but their middle row is not shown. It is only used with --mode sextant."),
+        )
+        .arg(
+            Arg::new("dither")
+                .long("dither")
+                .value_parser(["none", "floyd-steinberg", "atkinson", "bayer"])
+                .default_value("none")
+                .help("Dithering method used to reduce banding in gradients. It is used when choosing the characters, \
+                as well as when reducing the colors to 16 or 256 colors."),
+        )
         .arg(
             Arg::new("invert-density")
                 .long("invert")
@@ -132,9 +254,47 @@ pub fn build_cli() -> Command {
                 .long("background")
                 .conflicts_with("no-color")
                 .action(ArgAction::SetTrue)
-                .help("Sets the background of the ascii as the color. This will be ignored if the terminal does not support truecolor. \
+                .help("Sets the background of the ascii as the color. This will be ignored if only 16 colors are used. \
                 This argument is mutually exclusive with the no-color argument."),
         )
+        .arg(
+            Arg::new("color-depth")
+                .long("color-depth")
+                .value_parser(["16", "256", "truecolor"])
+                .conflicts_with("no-color")
+                .help("Number of colors used for the terminal and .ansi files. By default it is detected from the COLORTERM and TERM \
+                environment variables, using truecolor if COLORTERM is set to truecolor or 24bit, 256 colors if TERM contains 256color, \
+                and 16 colors otherwise."),
+        )
+        .arg(
+            Arg::new("color-metric")
+                .long("color-metric")
+                .value_parser(["euclidean", "redmean", "cie76", "ciede2000"])
+                .default_value("cie76")
+                .conflicts_with("no-color")
+                .help("Metric used to find the most similar color when only 16 or 256 colors are used. \
+                The perceptual metrics (cie76, ciede2000) match hues better than the rgb based ones (euclidean, redmean). \
+                ciede2000 is the most accurate, but considerably slower."),
+        )
+        .arg(
+            Arg::new("compact")
+                .long("compact")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("no-color")
+                .help("Only change the color of the terminal, .ansi and .html output when it differs from the previous character \
+                and reset it once at the end of each row. This results in a considerably smaller output."),
+        )
+        .arg(
+            Arg::new("color-tolerance")
+                .long("color-tolerance")
+                .value_name("TOLERANCE")
+                .value_parser(value_parser!(u8))
+                .requires("compact")
+                .value_hint(ValueHint::Other)
+                .help("How much each color channel (0-255) may differ from the previous color, before the compacted output \
+                changes its color. Higher values result in a smaller output, but less accurate colors. \
+                This will require the --compact argument to be present as well."),
+        )
         .arg(
             Arg::new("border")
                 .long("border")
@@ -148,6 +308,108 @@ pub fn build_cli() -> Command {
                 .action(ArgAction::SetTrue)
                 .help("Do not use color when printing the image to the terminal."),
         )
+        .arg(
+            Arg::new("crop")
+                .long("crop")
+                .value_name("X,Y,WIDTH,HEIGHT")
+                .value_parser(parse_crop)
+                .value_hint(ValueHint::Other)
+                .help("Only convert a region of the image, given in pixels (10,20,300,200) or in percent of the image size \
+                (10%,0%,50%,100%). The region is cropped after correcting the EXIF orientation and before rotating the image."),
+        )
+        .arg(
+            Arg::new("rotate")
+                .long("rotate")
+                .value_name("DEGREES")
+                .value_parser(value_parser!(f32))
+                .allow_negative_numbers(true)
+                .value_hint(ValueHint::Other)
+                .help("Rotate the image clockwise by the given degrees. Multiples of 90 are rotated exactly, \
+                other angles enlarge the image, so the corners are not cut off."),
+        )
+        .arg(
+            Arg::new("background-fill")
+                .long("background-fill")
+                .value_name("COLOR")
+                .value_parser(parse_color)
+                .value_hint(ValueHint::Other)
+                .help("Composite transparent images onto a hex color, for example #ffffff, before the conversion. \
+                Otherwise only the visible pixels of a tile are used for its color."),
+        )
+        .arg(
+            Arg::new("blank-transparent")
+                .long("blank-transparent")
+                .action(ArgAction::SetTrue)
+                .help("Write fully transparent parts of the image as blank space without any colors."),
+        )
+        .arg(
+            Arg::new("brightness")
+                .long("brightness")
+                .value_parser(value_parser!(f32))
+                .allow_negative_numbers(true)
+                .action(ArgAction::Append)
+                .value_hint(ValueHint::Other)
+                .help("Change the brightness of the image before converting it, from -1 (black) to 1 (white). \
+                Like the other adjustments, it can be used multiple times and is applied in the given order."),
+        )
+        .arg(
+            Arg::new("contrast")
+                .long("contrast")
+                .value_parser(value_parser!(f32))
+                .action(ArgAction::Append)
+                .value_hint(ValueHint::Other)
+                .help("Scale the contrast of the image before converting it. 1 keeps the image unchanged, \
+                larger values increase the contrast and 0 results in a gray image."),
+        )
+        .arg(
+            Arg::new("gamma")
+                .long("gamma")
+                .value_parser(value_parser!(f32))
+                .action(ArgAction::Append)
+                .value_hint(ValueHint::Other)
+                .help("Apply a gamma correction to the image before converting it. \
+                Values above 1 brighten the dark parts of the image, values below 1 darken them."),
+        )
+        .arg(
+            Arg::new("saturation")
+                .long("saturation")
+                .value_parser(value_parser!(f32))
+                .action(ArgAction::Append)
+                .value_hint(ValueHint::Other)
+                .help("Scale the saturation of the image before converting it. 1 keeps the image unchanged \
+                and 0 results in a grayscale image."),
+        )
+        .arg(
+            Arg::new("hue")
+                .long("hue")
+                .value_parser(value_parser!(f32))
+                .allow_negative_numbers(true)
+                .action(ArgAction::Append)
+                .value_hint(ValueHint::Other)
+                .help("Rotate the hue of the image by the given degrees before converting it."),
+        )
+        .arg(
+            Arg::new("sharpen")
+                .long("sharpen")
+                .value_parser(value_parser!(f32))
+                .action(ArgAction::Append)
+                .value_hint(ValueHint::Other)
+                .help("Sharpen the image using an unsharp mask with a sigma of 1 before converting it. \
+                The value is the amount of sharpening, 1 is a good starting point."),
+        )
+        .arg(
+            Arg::new("auto-levels")
+                .long("auto-levels")
+                .action(ArgAction::SetTrue)
+                .help("Stretch the brightness of the image, so its darkest part is black and its brightest part white."),
+        )
+        .arg(
+            Arg::new("equalize")
+                .long("equalize")
+                .action(ArgAction::SetTrue)
+                .help("Equalize the histogram of the image, so all brightness levels are used equally often. \
+                This increases the contrast of images, which mostly use a few similar levels."),
+        )
         .arg(
             Arg::new("outline")
                 .long("outline")
@@ -164,6 +426,54 @@ pub fn build_cli() -> Command {
                 .help("When creating the outline use the hysteresis method, which will remove imperfection, but might not be as good looking in ascii form.\
                  This will require the --outline argument to be present as well."),
         )
+        .arg(
+            Arg::new("outline-low")
+                .long("outline-low")
+                .requires("hysteresis")
+                .value_parser(value_parser!(f32))
+                .default_value("0.3")
+                .value_hint(ValueHint::Other)
+                .help("The lower threshold of the hysteresis, between 0 and 1. Weaker edges are removed, \
+                stronger ones are only kept next to an edge above the upper threshold. \
+                This will require the --hysteresis argument to be present as well."),
+        )
+        .arg(
+            Arg::new("outline-high")
+                .long("outline-high")
+                .requires("hysteresis")
+                .value_parser(value_parser!(f32))
+                .default_value("0.5")
+                .value_hint(ValueHint::Other)
+                .help("The upper threshold of the hysteresis, between 0 and 1. Stronger edges are always kept. \
+                It can not be below the lower threshold. This will require the --hysteresis argument to be present as well."),
+        )
+        .arg(
+            Arg::new("outline-sigma")
+                .long("outline-sigma")
+                .requires("outline")
+                .value_parser(value_parser!(f32))
+                .value_hint(ValueHint::Other)
+                .help("The sigma of the gaussian blur applied before creating the outline, for example 1.4. Larger values remove \
+                more noise and details, but take longer. Without it, a small 3x3 blur is used. \
+                This will require the --outline argument to be present as well."),
+        )
+        .arg(
+            Arg::new("non-max-suppression")
+                .long("non-max-suppression")
+                .requires("outline")
+                .action(ArgAction::SetTrue)
+                .help("When creating the outline, thin the edges to a width of one pixel, like the canny edge detection does. \
+                This will require the --outline argument to be present as well."),
+        )
+        .arg(
+            Arg::new("edge-characters")
+                .long("edge-characters")
+                .requires("outline")
+                .action(ArgAction::SetTrue)
+                .help("When creating the outline, draw the edges with characters matching their direction (- _ | / \\), \
+                based on the dominant direction of the edge in each character. This only works with the ascii mode. \
+                This will require the --outline argument to be present as well."),
+        )
         .arg(
             Arg::new("verbosity")
                 .long("verbose")
@@ -255,6 +565,110 @@ impl From<Verbosity> for log::LevelFilter {
     }
 }
 
+/// Parse a cell size in the format `WIDTHxHEIGHT`, both values must be at least 1.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(Ok((8, 16)), parse_cell_size("8x16"));
+/// ```
+fn parse_cell_size(value: &str) -> Result<(u32, u32), String> {
+    let Some((width, height)) = value.split_once('x') else {
+        return Err(String::from("expected a size in the format WIDTHxHEIGHT"));
+    };
+    let width = width.trim().parse::<u32>().map_err(|err| err.to_string())?;
+    let height = height
+        .trim()
+        .parse::<u32>()
+        .map_err(|err| err.to_string())?;
+    if width == 0 || height == 0 {
+        return Err(String::from("width and height must be at least 1"));
+    }
+    Ok((width, height))
+}
+
+/// Parse the frames per second of an animation, which must be a positive number.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(Ok(12.5), parse_fps("12.5"));
+/// ```
+fn parse_fps(value: &str) -> Result<f32, String> {
+    let fps = value.trim().parse::<f32>().map_err(|err| err.to_string())?;
+    if !fps.is_finite() || fps <= 0f32 {
+        return Err(String::from("frames per second must be a positive number"));
+    }
+    Ok(fps)
+}
+
+/// Parse a hex color in the format `#RRGGBB` or the short format `#RGB`, the `#` is optional.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(Ok([255, 136, 0]), parse_color("#ff8800"));
+/// assert_eq!(Ok([255, 136, 0]), parse_color("f80"));
+/// ```
+fn parse_color(value: &str) -> Result<[u8; 3], String> {
+    let hex = value.trim().trim_start_matches('#');
+    if !hex.chars().all(|digit| digit.is_ascii_hexdigit()) {
+        return Err(format!("{value} is not a valid hex color"));
+    }
+    //each digit of the short format is repeated, so f80 is the same as ff8800
+    let hex = match hex.len() {
+        3 => hex.chars().flat_map(|digit| [digit, digit]).collect(),
+        6 => hex.to_string(),
+        _ => {
+            return Err(String::from(
+                "expected a hex color in the format #RRGGBB or #RGB",
+            ))
+        }
+    };
+    let mut color = [0u8; 3];
+    for (index, channel) in color.iter_mut().enumerate() {
+        *channel = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16)
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(color)
+}
+
+/// Parse a crop in the format `X,Y,WIDTH,HEIGHT`, either all in pixels or all in percent, for example `10%,0%,50%,100%`.
+///
+/// Returns if the values are percentages and the values.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(Ok((true, [10f32, 0f32, 50f32, 100f32])), parse_crop("10%,0%,50%,100%"));
+/// ```
+fn parse_crop(value: &str) -> Result<(bool, [f32; 4]), String> {
+    let parts: Vec<&str> = value.split(',').map(str::trim).collect();
+    let [x, y, width, height] = parts[..] else {
+        return Err(String::from(
+            "expected a region in the format X,Y,WIDTH,HEIGHT",
+        ));
+    };
+    let parts = [x, y, width, height];
+    let percentage = parts.iter().all(|part| part.ends_with('%'));
+    if !percentage && parts.iter().any(|part| part.ends_with('%')) {
+        return Err(String::from(
+            "either all or none of the values must be percentages",
+        ));
+    }
+
+    let mut values = [0f32; 4];
+    for (value, part) in values.iter_mut().zip(parts) {
+        *value = if percentage {
+            part.trim_end_matches('%')
+                .parse::<f32>()
+                .map_err(|err| err.to_string())?
+        } else {
+            part.parse::<u32>().map_err(|err| err.to_string())? as f32
+        };
+    }
+    if values[2] <= 0f32 || values[3] <= 0f32 {
+        return Err(String::from("width and height must be larger than 0"));
+    }
+    Ok((percentage, values))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -318,4 +732,72 @@ mod test {
         ]);
         assert!(matches.is_err());
     }
+
+    #[test]
+    fn parse_cell_size_valid() {
+        assert_eq!(Ok((6, 12)), parse_cell_size("6x12"));
+    }
+
+    #[test]
+    fn parse_cell_size_missing_height() {
+        assert!(parse_cell_size("8").is_err());
+    }
+
+    #[test]
+    fn parse_cell_size_zero() {
+        assert!(parse_cell_size("0x16").is_err());
+    }
+
+    #[test]
+    fn parse_crop_pixels() {
+        assert_eq!(
+            Ok((false, [10f32, 20f32, 300f32, 200f32])),
+            parse_crop("10,20,300,200")
+        );
+    }
+
+    #[test]
+    fn parse_crop_percentage() {
+        assert_eq!(
+            Ok((true, [10f32, 0f32, 50.5f32, 100f32])),
+            parse_crop("10%, 0%, 50.5%, 100%")
+        );
+    }
+
+    #[test]
+    fn parse_crop_mixed_fails() {
+        assert!(parse_crop("10%,0,50%,100%").is_err());
+        assert!(parse_crop("10,0,50").is_err());
+        assert!(parse_crop("0,0,0,10").is_err());
+    }
+
+    #[test]
+    fn parse_fps_valid() {
+        assert_eq!(Ok(12.5f32), parse_fps("12.5"));
+    }
+
+    #[test]
+    fn parse_fps_invalid() {
+        assert!(parse_fps("0").is_err());
+        assert!(parse_fps("-10").is_err());
+        assert!(parse_fps("inf").is_err());
+    }
+
+    #[test]
+    fn parse_color_long() {
+        assert_eq!(Ok([255, 136, 0]), parse_color("#FF8800"));
+        assert_eq!(Ok([1, 2, 3]), parse_color("010203"));
+    }
+
+    #[test]
+    fn parse_color_short() {
+        assert_eq!(Ok([255, 136, 0]), parse_color("#f80"));
+    }
+
+    #[test]
+    fn parse_color_invalid() {
+        assert!(parse_color("#ff88").is_err());
+        assert!(parse_color("#gg8800").is_err());
+        assert!(parse_color("#ff+800").is_err());
+    }
 }
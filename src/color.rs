@@ -0,0 +1,306 @@
+use crate::config::ColorMetric;
+
+/// A fixed set of colors, to which other colors can be reduced.
+///
+/// Besides the rgb values, the colors are also stored in the CIELAB color space,
+/// so they do not have to be converted again for every comparison.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let palette = Palette::new(&[[0, 0, 0], [255, 255, 255]]);
+/// assert_eq!(1, palette.nearest([200, 200, 200], ColorMetric::Ciede2000));
+/// ```
+#[derive(Debug, Clone)]
+pub(crate) struct Palette {
+    rgb: Vec<[u8; 3]>,
+    lab: Vec<[f32; 3]>,
+}
+
+impl Palette {
+    /// Create a new [`Palette`] from the given rgb colors.
+    pub(crate) fn new(colors: &[[u8; 3]]) -> Self {
+        Self {
+            rgb: colors.to_vec(),
+            lab: colors.iter().map(|color| srgb_to_lab(*color)).collect(),
+        }
+    }
+
+    /// Returns the rgb value of the color at the given index.
+    ///
+    /// # Panics
+    /// Panics if the index is outside of the palette.
+    pub(crate) fn color(&self, index: usize) -> [u8; 3] {
+        self.rgb[index]
+    }
+
+    /// Returns the index of the palette color, which is the most similar to the given color.
+    ///
+    /// The similarity is determined by the given [`ColorMetric`]. If multiple colors have
+    /// the same distance, the first one is returned.
+    pub(crate) fn nearest(&self, color: [u8; 3], metric: ColorMetric) -> usize {
+        //the color only needs to be converted once for all comparisons
+        let lab = match metric {
+            ColorMetric::Cie76 | ColorMetric::Ciede2000 => srgb_to_lab(color),
+            _ => [0f32; 3],
+        };
+
+        let mut smallest_distance = f32::MAX;
+        let mut smallest_distance_index = 0;
+        for index in 0..self.rgb.len() {
+            let distance = match metric {
+                ColorMetric::Euclidean => euclidean(color, self.rgb[index]),
+                ColorMetric::Redmean => redmean(color, self.rgb[index]),
+                //the squared distance has the same order and saves a square root
+                ColorMetric::Cie76 => squared_distance(lab, self.lab[index]),
+                ColorMetric::Ciede2000 => ciede2000(lab, self.lab[index]),
+            };
+
+            if distance < smallest_distance {
+                smallest_distance = distance;
+                smallest_distance_index = index;
+            }
+        }
+        smallest_distance_index
+    }
+}
+
+/// Returns the squared euclidean distance between two vectors.
+fn squared_distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+/// Returns the squared euclidean distance between two rgb colors.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(3f32, euclidean([0, 0, 0], [1, 1, 1]));
+/// ```
+fn euclidean(a: [u8; 3], b: [u8; 3]) -> f32 {
+    squared_distance(a.map(f32::from), b.map(f32::from))
+}
+
+/// Returns the squared "redmean" distance between two rgb colors.
+///
+/// It is a weighted euclidean distance, which weights the channels depending on the
+/// mean of the red values, to roughly approximate how the human eye perceives colors.
+/// See <https://www.compuphase.com/cmetric.htm>.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// //green differences are weighted more than red or blue ones
+/// assert!(redmean([0, 0, 0], [0, 10, 0]) > redmean([0, 0, 0], [10, 0, 0]));
+/// ```
+fn redmean(a: [u8; 3], b: [u8; 3]) -> f32 {
+    let red_mean = (a[0] as f32 + b[0] as f32) / 2f32;
+    let red = a[0] as f32 - b[0] as f32;
+    let green = a[1] as f32 - b[1] as f32;
+    let blue = a[2] as f32 - b[2] as f32;
+    (2f32 + red_mean / 256f32) * red * red
+        + 4f32 * green * green
+        + (2f32 + (255f32 - red_mean) / 256f32) * blue * blue
+}
+
+/// Converts a sRGB color to the CIELAB color space, using the D65 white point.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let [lightness, _, _] = srgb_to_lab([255, 255, 255]);
+/// assert!((lightness - 100f32).abs() < 0.01);
+/// ```
+fn srgb_to_lab(color: [u8; 3]) -> [f32; 3] {
+    //remove the gamma correction of sRGB
+    let [red, green, blue] = color.map(|channel| {
+        let channel = channel as f32 / 255f32;
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    });
+
+    //convert to XYZ, relative to the D65 white point
+    let x = (0.4124564 * red + 0.3575761 * green + 0.1804375 * blue) / 0.95047;
+    let y = 0.2126729 * red + 0.7151522 * green + 0.0721750 * blue;
+    let z = (0.0193339 * red + 0.119192 * green + 0.9503041 * blue) / 1.08883;
+
+    let f = |t: f32| {
+        if t > 216f32 / 24389f32 {
+            t.cbrt()
+        } else {
+            (24389f32 / 27f32 * t + 16f32) / 116f32
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116f32 * fy - 16f32, 500f32 * (fx - fy), 200f32 * (fy - fz)]
+}
+
+/// Returns the CIEDE2000 color difference between two CIELAB colors.
+///
+/// It is the most accurate of the CIE color differences, by correcting the lightness, chroma and hue
+/// differences of CIELAB, especially for blue and gray tones.
+/// The implementation follows <https://hajim.rochester.edu/ece/sites/gsharma/ciede2000/ciede2000noteCRNA.pdf>.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(0f32, ciede2000([50f32, 2.5, 0f32], [50f32, 2.5, 0f32]));
+/// ```
+fn ciede2000(lab1: [f32; 3], lab2: [f32; 3]) -> f32 {
+    let [l1, a1, b1] = lab1;
+    let [l2, a2, b2] = lab2;
+    //25^7, used for the chroma corrections
+    const POW25_7: f32 = 6_103_515_625f32;
+
+    let chroma_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2f32;
+    let g = 0.5 * (1f32 - (chroma_mean.powi(7) / (chroma_mean.powi(7) + POW25_7)).sqrt());
+    let a1 = (1f32 + g) * a1;
+    let a2 = (1f32 + g) * a2;
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+
+    let hue = |a: f32, b: f32| {
+        if a == 0f32 && b == 0f32 {
+            0f32
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360f32)
+        }
+    };
+    let h1 = hue(a1, b1);
+    let h2 = hue(a2, b2);
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0f32 {
+        0f32
+    } else if (h2 - h1).abs() <= 180f32 {
+        h2 - h1
+    } else if h2 - h1 > 180f32 {
+        h2 - h1 - 360f32
+    } else {
+        h2 - h1 + 360f32
+    };
+    let delta_h = 2f32 * (c1 * c2).sqrt() * (delta_h / 2f32).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2f32;
+    let c_mean = (c1 + c2) / 2f32;
+    let h_mean = if c1 * c2 == 0f32 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180f32 {
+        (h1 + h2) / 2f32
+    } else if h1 + h2 < 360f32 {
+        (h1 + h2 + 360f32) / 2f32
+    } else {
+        (h1 + h2 - 360f32) / 2f32
+    };
+
+    let t = 1f32 - 0.17 * (h_mean - 30f32).to_radians().cos()
+        + 0.24 * (2f32 * h_mean).to_radians().cos()
+        + 0.32 * (3f32 * h_mean + 6f32).to_radians().cos()
+        - 0.20 * (4f32 * h_mean - 63f32).to_radians().cos();
+    let delta_theta = 30f32 * (-((h_mean - 275f32) / 25f32).powi(2)).exp();
+    let r_c = 2f32 * (c_mean.powi(7) / (c_mean.powi(7) + POW25_7)).sqrt();
+    let s_l = 1f32 + 0.015 * (l_mean - 50f32).powi(2) / (20f32 + (l_mean - 50f32).powi(2)).sqrt();
+    let s_c = 1f32 + 0.045 * c_mean;
+    let s_h = 1f32 + 0.015 * c_mean * t;
+    let r_t = -(2f32 * delta_theta).to_radians().sin() * r_c;
+
+    let l = delta_l / s_l;
+    let c = delta_c / s_c;
+    let h = delta_h / s_h;
+    (l * l + c * c + h * h + r_t * c * h).sqrt()
+}
+
+#[cfg(test)]
+mod test_lab {
+    use super::*;
+
+    #[test]
+    fn white_and_black() {
+        let white = srgb_to_lab([255, 255, 255]);
+        assert!((white[0] - 100f32).abs() < 0.01);
+        assert!(white[1].abs() < 0.01 && white[2].abs() < 0.01);
+        assert_eq!([0f32; 3], srgb_to_lab([0, 0, 0]));
+    }
+
+    #[test]
+    fn rust_color() {
+        let [l, a, b] = srgb_to_lab([154, 85, 54]);
+        assert!((l - 43.71).abs() < 0.05, "{l}");
+        assert!((a - 25.69).abs() < 0.05, "{a}");
+        assert!((b - 30.32).abs() < 0.05, "{b}");
+    }
+}
+
+#[cfg(test)]
+mod test_ciede2000 {
+    use super::*;
+
+    /// Test data from the paper by Sharma, Wu and Dalal.
+    #[test]
+    fn reference_values() {
+        let pairs = [
+            ([50f32, 2.6772, -79.7751], [50f32, 0f32, -82.7485], 2.0425),
+            ([50f32, 3.1571, -77.2803], [50f32, 0f32, -82.7485], 2.8615),
+            ([50f32, 0f32, 0f32], [50f32, -1f32, 2f32], 2.3669),
+            ([50f32, 2.5, 0f32], [73f32, 25f32, -18f32], 27.1492),
+            ([50f32, 2.5, 0f32], [50f32, 3.1736, 0.5854], 1.0),
+            (
+                [60.2574, -34.0099, 36.2677],
+                [60.4626, -34.1751, 39.4387],
+                1.2644,
+            ),
+            (
+                [22.7233, 20.0904, -46.694],
+                [23.0331, 14.973, -42.5619],
+                2.0373,
+            ),
+        ];
+        for (lab1, lab2, expected) in pairs {
+            let difference = ciede2000(lab1, lab2);
+            assert!(
+                (difference - expected).abs() < 0.001,
+                "{lab1:?} {lab2:?}: {difference} != {expected}"
+            );
+            //the difference is symmetric
+            assert!((ciede2000(lab2, lab1) - expected).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn same_color() {
+        assert_eq!(0f32, ciede2000([50f32, 2.5, 0f32], [50f32, 2.5, 0f32]));
+    }
+}
+
+#[cfg(test)]
+mod test_palette {
+    use super::*;
+
+    #[test]
+    fn exact_color_is_found() {
+        let palette = Palette::new(&[[0, 0, 0], [154, 85, 54], [255, 255, 255]]);
+        for metric in [
+            ColorMetric::Euclidean,
+            ColorMetric::Redmean,
+            ColorMetric::Cie76,
+            ColorMetric::Ciede2000,
+        ] {
+            assert_eq!(1, palette.nearest([154, 85, 54], metric));
+        }
+    }
+
+    #[test]
+    fn redmean_weights_green() {
+        //a green difference is more noticeable than a blue one
+        assert!(redmean([0, 0, 0], [0, 10, 0]) > redmean([0, 0, 0], [0, 0, 10]));
+    }
+
+    #[test]
+    fn perceptual_keeps_hue() {
+        //a greenish gray is reduced to a neutral gray by the euclidean distance,
+        //but keeps its hue with perceptual metrics
+        let palette = Palette::new(&[[118, 118, 118], [135, 135, 95]]);
+        assert_eq!(0, palette.nearest([120, 130, 110], ColorMetric::Euclidean));
+        assert_eq!(1, palette.nearest([120, 130, 110], ColorMetric::Cie76));
+        assert_eq!(1, palette.nearest([120, 130, 110], ColorMetric::Ciede2000));
+    }
+}
@@ -1,5 +1,9 @@
 use std::num::NonZeroU32;
 
+use image::Rgb;
+
+use crate::Error;
+
 ///Preferred image resize direction
 ///
 ///This changes which dimensions should be used when resizing the image.
@@ -28,14 +32,18 @@ impl ResizingDimension {
     /// The dimension property can be used to change what dimension will be scaled. Since terminal character are a bit higher the wide,
     /// Width and Height of the output needs to be based on either one, so the other can be calculated.
     ///
+    /// The [`RenderMode`] determines how many pixels are sampled for each cell, the tile dimensions are always
+    /// a multiple of these, so each tile can be split evenly. For example, a half-block cell uses two pixels
+    /// stacked on top of each other.
+    ///
     /// # Examples
     /// ```
-    /// use artem::config::{ResizingDimension};
+    /// use artem::config::{RenderMode, ResizingDimension};
     ///
     /// assert_eq!(
     /// (100, 46, 5, 11),
     /// //image with a size of 512x512, split into 100 columns with no border
-    /// ResizingDimension::calculate_dimensions(100, 512, 512, 0.42, false, ResizingDimension::Width));
+    /// ResizingDimension::calculate_dimensions(100, 512, 512, 0.42, false, ResizingDimension::Width, RenderMode::Ascii));
     /// ```
     pub fn calculate_dimensions(
         target_size: u32,
@@ -44,14 +52,18 @@ impl ResizingDimension {
         scale: f32,
         border: bool,
         dimension: ResizingDimension,
+        mode: RenderMode,
     ) -> (u32, u32, u32, u32) {
+        let (pixels_x, pixels_y) = mode.pixels_per_cell();
         match dimension {
             ResizingDimension::Width => {
                 //calculate dimensions based on columns
-                let mut columns = if width > target_size {
+                //a column spans multiple pixels, rounded up so a small image still has a column
+                let pixel_columns = (width + pixels_x - 1) / pixels_x;
+                let mut columns = if pixel_columns > target_size {
                     target_size
                 } else {
-                    width
+                    pixel_columns
                 };
 
                 if border {
@@ -60,8 +72,11 @@ impl ResizingDimension {
                 }
 
                 //calculate tiles
-                let tile_width = width / columns;
-                let tile_height = (tile_width as f32 / scale).floor() as u32;
+                let tile_width = (width / columns / pixels_x).max(1) * pixels_x;
+                //a scale larger than the tile width would result in a tile height of 0
+                let tile_height = ((tile_width as f32 / scale / pixels_y as f32).floor() as u32)
+                    .max(1)
+                    .saturating_mul(pixels_y);
 
                 let rows = height / tile_height;
 
@@ -73,14 +88,19 @@ impl ResizingDimension {
             ResizingDimension::Height => {
                 let mut rows = if height > target_size {
                     // minus 1, since the user input line is included
-                    target_size - 1
+                    (target_size - 1).max(1)
                 } else {
                     height
                 };
 
                 //calculate tiles
-                let tile_height = height / rows;
-                let tile_width = (tile_height as f32 * scale).ceil() as u32;
+                let tile_height = (height / rows / pixels_y).max(1) * pixels_y;
+                if rows * tile_height > height {
+                    //the image is too small to have a full tile for each row
+                    rows = height / tile_height;
+                }
+                let tile_width =
+                    (tile_height as f32 * scale / pixels_x as f32).ceil() as u32 * pixels_x;
 
                 let mut columns = width / tile_width;
 
@@ -104,6 +124,8 @@ impl ResizingDimension {
 /// The first boolean determines if the output should be colored, the second if the color is the background color.
 ///
 /// An target might support neither, one or both colors.
+/// Each target selects one of the built-in renderers, to use a custom output format implement
+/// [`crate::target::Renderer`] and use [`crate::convert_with_renderer`] instead.
 ///
 /// # Examples
 ///```
@@ -119,6 +141,10 @@ pub enum TargetType {
     AnsiFile(bool),
     /// HTML target, Supports color and background colors.
     HtmlFile(bool, bool),
+    /// SVG target, Supports color and background colors.
+    SvgFile(bool, bool),
+    /// PNG image target, Supports color and background colors.
+    PngFile(bool, bool),
     /// Every other file, does not support either colored outputs.
     File,
 }
@@ -140,6 +166,28 @@ impl Default for TargetType {
     }
 }
 
+impl TargetType {
+    /// Returns if the target has colored output.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::TargetType;
+    ///
+    /// assert!(TargetType::AnsiFile(false).is_colored());
+    /// assert!(!TargetType::Shell(false, false).is_colored());
+    /// ```
+    pub fn is_colored(&self) -> bool {
+        match self {
+            TargetType::Shell(color, _)
+            | TargetType::HtmlFile(color, _)
+            | TargetType::SvgFile(color, _)
+            | TargetType::PngFile(color, _) => *color,
+            TargetType::AnsiFile(_) => true,
+            TargetType::File => false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,8 +198,413 @@ mod tests {
     }
 }
 
+/// Number of colors used for Ansi formatted output.
+///
+/// This is used for targets, who support Ansi colors, like the shell/terminal or `.ans`/`.ansi` files.
+/// By default it is detected from the `COLORTERM` and `TERM` environment variables, see [`crate::COLOR_DEPTH`].
+///
+/// # Examples
+/// ```
+/// use artem::config::ColorDepth;
+///
+/// assert_eq!(*artem::COLOR_DEPTH, ColorDepth::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// The 16 basic Ansi colors, which are supported by almost every terminal.
+    /// Background colors are not supported.
+    Ansi16,
+    /// The 240 colors of the xterm palette starting at index 16, consisting of a 6x6x6 color cube and a gray ramp.
+    /// The 16 basic colors are not used, since they are defined by the terminal and differ between them.
+    Ansi256,
+    /// 24-bit truecolor.
+    TrueColor,
+}
+
+impl Default for ColorDepth {
+    /// Default [`ColorDepth`]
+    ///
+    /// The default [`ColorDepth`] is the one supported by the current terminal.
+    fn default() -> ColorDepth {
+        *crate::COLOR_DEPTH
+    }
+}
+
+/// Metric used to find the most similar color, when the colors are reduced to a palette.
+///
+/// This is used when only 16 or 256 colors are available, see [`ColorDepth`].
+/// The perceptual metrics result in better matching hues, for example for skin tones and browns.
+/// By default [`ColorMetric::Cie76`] is used, since it is about as fast as the rgb based metrics.
+///
+/// # Examples
+/// ```
+/// use artem::config::ColorMetric;
+///
+/// assert_eq!(ColorMetric::Cie76, ColorMetric::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMetric {
+    /// Euclidean distance of the rgb values, which does not match how colors are perceived.
+    Euclidean,
+    /// Weighted euclidean distance of the rgb values, depending on the amount of red.
+    Redmean,
+    /// Euclidean distance in the CIELAB color space (CIE76).
+    #[default]
+    Cie76,
+    /// CIEDE2000 color difference in the CIELAB color space, which is the most accurate,
+    /// but also considerably slower than the other metrics.
+    Ciede2000,
+}
+
+/// Dithering method used when reducing the image to the characters and colors of the output.
+///
+/// Without dithering, each tile is mapped to the nearest character independently, which results
+/// in visible bands in gradients, especially with short character sets. Dithering distributes
+/// this error over the neighbouring tiles. It is also used when reducing colors to 16 or 256 colors.
+///
+/// # Examples
+/// ```
+/// use artem::config::Dithering;
+///
+/// assert_eq!(Dithering::None, Dithering::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dithering {
+    /// No dithering, each tile is mapped to the nearest character and color.
+    #[default]
+    None,
+    /// Floyd–Steinberg error diffusion, which distributes the whole error to the neighbouring tiles.
+    FloydSteinberg,
+    /// Atkinson error diffusion, which only distributes parts of the error, resulting in more contrast.
+    Atkinson,
+    /// Ordered dithering using a 4x4 Bayer matrix, which results in a regular pattern.
+    Bayer,
+}
+
+/// Mode that determines how the pixels of a tile are rendered as a cell.
+///
+/// By default each tile is converted to a single character of the density string. Other modes sample
+/// multiple pixels per cell and use block characters instead, which results in a higher resolution.
+///
+/// # Examples
+/// ```
+/// use artem::config::RenderMode;
+///
+/// assert_eq!(RenderMode::Ascii, RenderMode::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Each tile is mapped to a character of the density string, based on its luminosity.
+    #[default]
+    Ascii,
+    /// Each cell consists of two pixels on top of each other, rendered using the half block characters (▀▄).
+    /// With colors, the foreground is the color of the top and the background the color of the bottom pixel.
+    HalfBlock,
+    /// Each cell consists of 2x4 dots, rendered using the braille patterns (U+2800 - U+28FF).
+    /// Each dot is either set or not, the color of the cell is the average color of all dots.
+    Braille,
+    /// Each cell consists of 2x2 pixels, rendered using the quadrant block elements (▖▗▘▝).
+    /// With colors, the pixels are split into a foreground and background color, so the colors match the tile best.
+    Quadrant,
+    /// Each cell consists of 2x3 pixels, rendered using the sextants of the Symbols for Legacy Computing
+    /// (U+1FB00 - U+1FB3B). Like [`RenderMode::Quadrant`], it uses two colors, but not every font supports these chars.
+    Sextant,
+    /// Each tile is mapped to the character of the density string, whose glyph has the most similar shape.
+    /// The glyphs are rasterized from an embedded bitmap font, so lines are matched by characters like `/`, `|` or `_`.
+    Shape,
+}
+
+impl RenderMode {
+    /// Returns the number of pixels (horizontal, vertical) that are sampled for each cell.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::RenderMode;
+    ///
+    /// assert_eq!((1, 2), RenderMode::HalfBlock.pixels_per_cell());
+    /// ```
+    pub fn pixels_per_cell(&self) -> (u32, u32) {
+        match self {
+            RenderMode::Ascii => (1, 1),
+            RenderMode::HalfBlock => (1, 2),
+            RenderMode::Braille => (2, 4),
+            RenderMode::Quadrant => (2, 2),
+            RenderMode::Sextant => (2, 3),
+            RenderMode::Shape => (1, 1),
+        }
+    }
+}
+
+/// Calibration of the characters, which measures how much of each character is covered by ink.
+///
+/// The coverage is measured using the embedded bitmap font, so it can differ slightly from the font used to show
+/// the result. Characters that are not part of it are removed, unless none of the characters are part of it.
+///
+/// # Examples
+/// ```
+/// use artem::config::Calibration;
+///
+/// assert_eq!(Calibration::None, Calibration::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Calibration {
+    /// The characters are used in the given order.
+    #[default]
+    None,
+    /// The characters are sorted from the densest to the lightest one.
+    Sort,
+    /// The characters are sorted and spaced, so their coverage decreases linearly. Characters with nearly the
+    /// same coverage are dropped and characters are repeated, where the gap to the next one is large.
+    Linear,
+}
+
+/// Adjustment of the input image, which is applied before outlining and resizing it.
+///
+/// Multiple adjustments are applied in the given order, so for example sharpening before or after
+/// increasing the contrast gives different results. The alpha channel is never changed.
+///
+/// # Examples
+/// ```
+/// use artem::config::{Adjustment, ConfigBuilder};
+///
+/// let mut builder = ConfigBuilder::new();
+/// builder.adjustments(vec![Adjustment::AutoLevels, Adjustment::Gamma(1.2f32)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Adjustment {
+    /// Add the value to each channel, as a fraction of the maximum brightness from -1 to 1.
+    Brightness(f32),
+    /// Scale the distance of each channel to the middle gray, 1 keeps the image unchanged and 0 results in gray.
+    Contrast(f32),
+    /// Apply a gamma correction, values above 1 brighten the dark parts of the image, values below 1 darken them.
+    Gamma(f32),
+    /// Scale the distance of each color to its gray value, 1 keeps the image unchanged and 0 results in grayscale.
+    Saturation(f32),
+    /// Rotate the hue of each color by the given degrees.
+    Hue(f32),
+    /// Sharpen the image using an unsharp mask, which adds the difference to a blurred copy of the image.
+    /// The sigma is the one of the gaussian blur and the amount how much of the difference is added.
+    Sharpen { sigma: f32, amount: f32 },
+    /// Stretch the luminosity, so the darkest part of the image is black and the brightest one white.
+    AutoLevels,
+    /// Equalize the histogram of the luminosity, so all brightness levels are used equally often.
+    Equalize,
+}
+
+impl Adjustment {
+    /// Returns true if the values of the adjustment can be used.
+    ///
+    /// All values have to be finite, the contrast and saturation can not be negative
+    /// and the gamma, as well as the sigma of sharpening have to be positive.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::Adjustment;
+    ///
+    /// assert!(Adjustment::Gamma(2f32).is_valid());
+    /// assert!(!Adjustment::Gamma(0f32).is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Adjustment::Brightness(value) | Adjustment::Hue(value) => value.is_finite(),
+            Adjustment::Contrast(value) | Adjustment::Saturation(value) => {
+                value.is_finite() && value >= 0f32
+            }
+            Adjustment::Gamma(value) => value.is_finite() && value > 0f32,
+            Adjustment::Sharpen { sigma, amount } => {
+                sigma.is_finite() && sigma > 0f32 && amount.is_finite()
+            }
+            Adjustment::AutoLevels | Adjustment::Equalize => true,
+        }
+    }
+}
+
+/// Orientation of the image, as stored in the EXIF metadata of photos.
+///
+/// Cameras often store the image as it was captured by the sensor and only record how it has to be
+/// rotated or flipped to be shown upright. The orientation is corrected before any other transformation.
+///
+/// # Examples
+/// ```
+/// use artem::config::Orientation;
+///
+/// assert_eq!(Some(Orientation::Rotate90), Orientation::from_exif(6));
+/// assert_eq!(Orientation::Normal, Orientation::default());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// The image is already upright.
+    #[default]
+    Normal,
+    /// The image has to be flipped horizontally.
+    FlipHorizontal,
+    /// The image has to be rotated by 180 degrees.
+    Rotate180,
+    /// The image has to be flipped vertically.
+    FlipVertical,
+    /// The image has to be flipped along the diagonal from the top left to the bottom right.
+    Transpose,
+    /// The image has to be rotated by 90 degrees clockwise.
+    Rotate90,
+    /// The image has to be flipped along the diagonal from the top right to the bottom left.
+    Transverse,
+    /// The image has to be rotated by 270 degrees clockwise.
+    Rotate270,
+}
+
+impl Orientation {
+    /// Returns the orientation for the value of the EXIF orientation tag, which ranges from 1 to 8.
+    ///
+    /// Other values are invalid and return [`None`].
+    pub fn from_exif(value: u32) -> Option<Self> {
+        match value {
+            1 => Some(Orientation::Normal),
+            2 => Some(Orientation::FlipHorizontal),
+            3 => Some(Orientation::Rotate180),
+            4 => Some(Orientation::FlipVertical),
+            5 => Some(Orientation::Transpose),
+            6 => Some(Orientation::Rotate90),
+            7 => Some(Orientation::Transverse),
+            8 => Some(Orientation::Rotate270),
+            _ => None,
+        }
+    }
+}
+
+/// Region of the image, which is used for the conversion, everything outside of it is cut off.
+///
+/// The region is clamped to the image, but it has to overlap with it, otherwise the conversion
+/// will return an [`Error::InvalidCrop`].
+///
+/// # Examples
+/// ```
+/// use artem::config::Crop;
+///
+/// let crop = Crop::Percentage { x: 25f32, y: 0f32, width: 50f32, height: 100f32 };
+/// assert_eq!(Some((50, 0, 100, 80)), crop.region(200, 80));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Crop {
+    /// The region in pixels, starting at the top left corner of the image.
+    Pixels {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    /// The region in percent of the width and height of the image, ranging from 0 to 100.
+    Percentage {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+}
+
+impl Crop {
+    /// Returns the region (x, y, width, height) in pixels for an image of the given size.
+    ///
+    /// The region is clamped to the image, if it does not overlap with the image [`None`] is returned.
+    pub fn region(&self, image_width: u32, image_height: u32) -> Option<(u32, u32, u32, u32)> {
+        let (x, y, width, height) = match *self {
+            Crop::Pixels {
+                x,
+                y,
+                width,
+                height,
+            } => (x, y, width, height),
+            Crop::Percentage {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let scale = |value: f32, size: u32| (value / 100f32 * size as f32).round() as u32;
+                (
+                    scale(x, image_width),
+                    scale(y, image_height),
+                    scale(width, image_width),
+                    scale(height, image_height),
+                )
+            }
+        };
+        let width = width.min(image_width.saturating_sub(x));
+        let height = height.min(image_height.saturating_sub(y));
+        (width > 0 && height > 0).then_some((x, y, width, height))
+    }
+
+    /// Returns true if the crop can be used, the width and height have to be positive and
+    /// percentages have to be between 0 and 100.
+    fn is_valid(&self) -> bool {
+        match *self {
+            Crop::Pixels { width, height, .. } => width > 0 && height > 0,
+            Crop::Percentage {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                [x, y, width, height]
+                    .iter()
+                    .all(|value| (0f32..=100f32).contains(value))
+                    && width > 0f32
+                    && height > 0f32
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_crop {
+    use super::*;
+
+    #[test]
+    fn pixels_are_clamped() {
+        let crop = Crop::Pixels {
+            x: 10,
+            y: 5,
+            width: 100,
+            height: 100,
+        };
+        assert_eq!(Some((10, 5, 30, 15)), crop.region(40, 20));
+    }
+
+    #[test]
+    fn outside_is_none() {
+        let crop = Crop::Pixels {
+            x: 40,
+            y: 0,
+            width: 10,
+            height: 10,
+        };
+        assert_eq!(None, crop.region(40, 20));
+    }
+
+    #[test]
+    fn percentage_of_size() {
+        let crop = Crop::Percentage {
+            x: 10f32,
+            y: 50f32,
+            width: 80f32,
+            height: 50f32,
+        };
+        assert_eq!(Some((10, 10, 80, 10)), crop.region(100, 20));
+    }
+
+    #[test]
+    fn invalid_percentage() {
+        let crop = Crop::Percentage {
+            x: 0f32,
+            y: 0f32,
+            width: 120f32,
+            height: 50f32,
+        };
+        assert!(!crop.is_valid());
+    }
+}
+
 ///Config for the conversion of the image to the ascii image.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     pub characters: String,
     pub scale: f32,
@@ -166,6 +619,26 @@ pub struct Config {
     pub outline: bool,
     pub hysteresis: bool,
     pub target: TargetType,
+    pub font_family: String,
+    pub cell_size: (u32, u32),
+    pub color_depth: ColorDepth,
+    pub color_metric: ColorMetric,
+    pub dither: Dithering,
+    pub render_mode: RenderMode,
+    pub sextant_fallback: bool,
+    pub edge_characters: bool,
+    pub outline_sigma: Option<f32>,
+    pub outline_low: f32,
+    pub outline_high: f32,
+    pub non_max_suppression: bool,
+    pub adjustments: Vec<Adjustment>,
+    pub orientation: Orientation,
+    pub crop: Option<Crop>,
+    pub rotation: f32,
+    pub blank_transparent: bool,
+    pub background_fill: Option<Rgb<u8>>,
+    pub compact: bool,
+    pub color_tolerance: u8,
 }
 
 impl Config {
@@ -180,6 +653,69 @@ impl Config {
     pub fn builder() -> ConfigBuilder {
         ConfigBuilder::default()
     }
+
+    /// Check if the [`Config`] can be used for a conversion.
+    ///
+    /// Since all fields are public, a [`Config`] might have been created without the [`ConfigBuilder`],
+    /// so this is checked again before converting an image.
+    ///
+    /// # Errors
+    /// Returns an [`Error`] if the characters are empty, the target size or cell size is zero,
+    /// the scale or outline sigma is not a positive, finite number, the rotation is not finite or
+    /// the crop, the outline thresholds or one of the adjustments are invalid.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if self.characters.is_empty() {
+            return Err(Error::EmptyCharacters);
+        }
+
+        if self.target_size == 0 {
+            return Err(Error::ZeroTargetSize);
+        }
+
+        if !self.scale.is_finite() || self.scale <= 0f32 {
+            return Err(Error::InvalidScale(self.scale));
+        }
+
+        if self.cell_size.0 == 0 || self.cell_size.1 == 0 {
+            return Err(Error::ZeroCellSize);
+        }
+
+        if let Some(sigma) = self
+            .outline_sigma
+            .filter(|sigma| !sigma.is_finite() || *sigma <= 0f32)
+        {
+            return Err(Error::InvalidOutlineSigma(sigma));
+        }
+
+        if !self.rotation.is_finite() {
+            return Err(Error::InvalidRotation(self.rotation));
+        }
+
+        if let Some(crop) = self.crop.filter(|crop| !crop.is_valid()) {
+            return Err(Error::InvalidCrop(crop));
+        }
+
+        if let Some(adjustment) = self
+            .adjustments
+            .iter()
+            .find(|adjustment| !adjustment.is_valid())
+        {
+            return Err(Error::InvalidAdjustment(*adjustment));
+        }
+
+        //also fails for nan, since all comparisons with it are false
+        if !(0f32 <= self.outline_low
+            && self.outline_low <= self.outline_high
+            && self.outline_high <= 1f32)
+        {
+            return Err(Error::InvalidOutlineThresholds(
+                self.outline_low,
+                self.outline_high,
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for Config {
@@ -198,6 +734,26 @@ impl Default for Config {
             outline: Default::default(),
             hysteresis: Default::default(),
             target: Default::default(),
+            font_family: "monospace".to_string(),
+            cell_size: (8, 16),
+            color_depth: ColorDepth::default(),
+            color_metric: ColorMetric::default(),
+            dither: Dithering::default(),
+            render_mode: RenderMode::default(),
+            sextant_fallback: Default::default(),
+            edge_characters: Default::default(),
+            outline_sigma: None,
+            outline_low: 0.3f32,
+            outline_high: 0.5f32,
+            non_max_suppression: Default::default(),
+            adjustments: Vec::new(),
+            orientation: Orientation::default(),
+            crop: None,
+            rotation: 0f32,
+            blank_transparent: Default::default(),
+            background_fill: None,
+            compact: Default::default(),
+            color_tolerance: 0,
         }
     }
 }
@@ -222,6 +778,27 @@ mod test_option {
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                calibration: Calibration::default(),
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
             Config::builder()
         );
@@ -244,6 +821,27 @@ pub struct ConfigBuilder {
     outline: bool,
     hysteresis: bool,
     target: TargetType,
+    font_family: String,
+    cell_size: (u32, u32),
+    color_depth: ColorDepth,
+    color_metric: ColorMetric,
+    dither: Dithering,
+    render_mode: RenderMode,
+    sextant_fallback: bool,
+    calibration: Calibration,
+    edge_characters: bool,
+    outline_sigma: Option<f32>,
+    outline_low: f32,
+    outline_high: f32,
+    non_max_suppression: bool,
+    adjustments: Vec<Adjustment>,
+    orientation: Orientation,
+    crop: Option<Crop>,
+    rotation: f32,
+    blank_transparent: bool,
+    background_fill: Option<Rgb<u8>>,
+    compact: bool,
+    color_tolerance: u8,
 }
 
 impl Default for ConfigBuilder {
@@ -263,6 +861,27 @@ impl Default for ConfigBuilder {
             outline: Default::default(),
             hysteresis: Default::default(),
             target: Default::default(),
+            font_family: "monospace".to_string(),
+            cell_size: (8, 16),
+            color_depth: ColorDepth::default(),
+            color_metric: ColorMetric::default(),
+            dither: Dithering::default(),
+            render_mode: RenderMode::default(),
+            sextant_fallback: Default::default(),
+            calibration: Calibration::default(),
+            edge_characters: Default::default(),
+            outline_sigma: None,
+            outline_low: 0.3f32,
+            outline_high: 0.5f32,
+            non_max_suppression: Default::default(),
+            adjustments: Vec::new(),
+            orientation: Orientation::default(),
+            crop: None,
+            rotation: 0f32,
+            blank_transparent: Default::default(),
+            background_fill: None,
+            compact: Default::default(),
+            color_tolerance: 0,
         }
     }
 }
@@ -323,12 +942,13 @@ impl ConfigBuilder {
         Config::builder()
     }
 
+    property! {
     ///Set the characters.
     ///
     /// The characters will determine how 'visible'/light/dark a character will be perceived.
     ///
     /// # Errors
-    /// When the given characters are empty, the characters will not be changed.
+    /// When the given characters are empty, [`ConfigBuilder::build`] will return an [`Error::EmptyCharacters`].
     ///
     /// # Examples
     /// ```
@@ -337,12 +957,7 @@ impl ConfigBuilder {
     /// let mut builder = ConfigBuilder::new();
     /// builder.characters("Mkl. ".to_string());
     /// ```
-    #[allow(clippy::needless_lifetimes)] //disable this, as the life is needed for the builder
-    pub fn characters<'a>(&'a mut self, characters: String) -> &'a Self {
-        if !characters.is_empty() {
-            self.characters = characters;
-        }
-        self
+    => characters, String
     }
 
     property! {
@@ -351,6 +966,9 @@ impl ConfigBuilder {
     /// Used to change the ratio between width and height of an character.
     /// Since a char is a bit higher than wide, the scale should compensate for that.
     ///
+    /// # Errors
+    /// When the scale is not a positive, finite number, [`ConfigBuilder::build`] will return an [`Error::InvalidScale`].
+    ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
@@ -532,111 +1150,1257 @@ impl ConfigBuilder {
     }
 
     property! {
-    ///Set the target type
+    /// When converting the image to an outline, use characters matching the direction of the edges.
     ///
-    /// This will effect the output, for example if the [`TargetType`] is set to html,
-    /// the output will be the content of a Html-file.
+    /// Tiles containing an edge are rendered as `-`, `_`, `|`, `/` or `\`, based on the dominant orientation of
+    /// the edge in the tile. Other tiles use the characters as usual.
     ///
-    /// See [`TargetType`] for more information. It defaults to the shell as the target.
+    /// It will only be used when outlining is set to true and the [`RenderMode::Ascii`] is used.
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.outline(true).edge_characters(true);
+    /// ```
+    => edge_characters, bool
+    }
+
+    property! {
+    /// Set the sigma of the gaussian blur, which is applied to the image before outlining it.
+    ///
+    /// The size of the blur kernel grows with the sigma, so larger values remove more details and noise
+    /// from the outline, but also take longer to compute. Without a sigma, a small 3x3 blur is used.
+    ///
+    /// It will only be used when outlining is set to true.
+    ///
+    /// # Errors
+    /// When the sigma is not a positive, finite number, [`ConfigBuilder::build`] will return an [`Error::InvalidOutlineSigma`].
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
-    /// use artem::config::TargetType;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// //use color, but don't color the background
-    /// builder.target(TargetType::HtmlFile(true, false));
+    /// builder.outline_sigma(Some(2.5f32));
     /// ```
-        => target,  TargetType
+    => outline_sigma, Option<f32>
     }
 
-    ///Build the [`Config`] struct.
+    property! {
+    /// Set the lower threshold of the hysteresis, as a fraction of the maximum brightness from 0 to 1.
     ///
-    /// This returns a [`Config`], which can than be used for the image conversion using [`super::convert()`].
-    /// If values are not explicitly specified, the default values will be used.
+    /// Edges weaker than it are removed, edges between it and the upper threshold are only kept
+    /// when they are next to a strong edge.
+    ///
+    /// It will only be used when outlining and hysteresis are set to true.
+    ///
+    /// # Errors
+    /// When the lower threshold is above the upper one or outside of 0 to 1,
+    /// [`ConfigBuilder::build`] will return an [`Error::InvalidOutlineThresholds`].
     ///
     /// # Examples
     /// ```
     /// use artem::config::ConfigBuilder;
     ///
     /// let mut builder = ConfigBuilder::new();
-    /// let options = builder.build();
+    /// builder.outline_low(0.2f32);
     /// ```
-    pub fn build(&self) -> Config {
-        Config {
-            characters: self.characters.to_owned(),
-            scale: self.scale,
-            target_size: self.target_size,
-            invert: self.invert,
-            border: self.border,
-            dimension: self.dimension,
-            transform_x: self.transform_x,
-            transform_y: self.transform_y,
-            center_x: self.center_x,
-            center_y: self.center_y,
-            outline: self.outline,
-            hysteresis: self.hysteresis,
-            target: self.target,
-        }
+    => outline_low, f32
     }
-}
 
-#[cfg(test)]
-mod test_conversion_configuration_builder {
-    use super::*;
-    #[test]
-    fn build_default() {
-        assert_eq!(
-            Config {
-                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
-                scale: 0.42f32,
-                target_size: 80,
-                invert: false,
-                border: false,
-                dimension: ResizingDimension::Width,
-                transform_x: false,
-                transform_y: false,
-                center_x: false,
-                center_y: false,
-                outline: false,
-                hysteresis: false,
-                target: TargetType::default(),
-            },
-            ConfigBuilder::new().build()
-        );
+    property! {
+    /// Set the upper threshold of the hysteresis, as a fraction of the maximum brightness from 0 to 1.
+    ///
+    /// Edges stronger than it are always kept.
+    ///
+    /// It will only be used when outlining and hysteresis are set to true.
+    ///
+    /// # Errors
+    /// When the upper threshold is below the lower one or outside of 0 to 1,
+    /// [`ConfigBuilder::build`] will return an [`Error::InvalidOutlineThresholds`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.outline_high(0.7f32);
+    /// ```
+    => outline_high, f32
     }
 
-    #[test]
-    fn change_characters() {
-        assert_eq!(
-            Config {
-                characters: r#"characters"#.to_string(),
-                scale: 0.42f32,
-                target_size: 80,
-                invert: false,
-                border: false,
-                dimension: ResizingDimension::Width,
-                transform_x: false,
+    property! {
+    /// When converting the image to an outline, only keep the strongest pixels across each edge.
+    ///
+    /// This is the non-maximum suppression step of the canny edge detection, which thins the edges to a width
+    /// of one pixel. Thin lines are often less visible in the resulting ascii, which is why it is disabled by default.
+    ///
+    /// It will only be used when outlining is set to true.
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.non_max_suppression(true);
+    /// ```
+    => non_max_suppression, bool
+    }
+
+    property! {
+    /// Set the adjustments, which are applied to the image in the given order before converting it.
+    ///
+    /// # Errors
+    /// When one of the adjustments has an invalid value, [`ConfigBuilder::build`] will return an [`Error::InvalidAdjustment`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{Adjustment, ConfigBuilder};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.adjustments(vec![Adjustment::Contrast(1.5f32), Adjustment::Sharpen { sigma: 1f32, amount: 0.8f32 }]);
+    /// ```
+    => adjustments, Vec<Adjustment>
+    }
+
+    property! {
+    /// Set the orientation of the image, which is corrected before any other transformation.
+    ///
+    /// It is usually read from the EXIF metadata of the image, see [`Orientation::from_exif`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, Orientation};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.orientation(Orientation::Rotate90);
+    /// ```
+    => orientation, Orientation
+    }
+
+    property! {
+    /// Set the region of the image, which is converted.
+    ///
+    /// The image is cropped after correcting its orientation and before rotating it.
+    ///
+    /// # Errors
+    /// When the crop has no width or height, or a percentage is outside of 0 to 100,
+    /// [`ConfigBuilder::build`] will return an [`Error::InvalidCrop`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, Crop};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.crop(Some(Crop::Pixels { x: 10, y: 10, width: 200, height: 100 }));
+    /// ```
+    => crop, Option<Crop>
+    }
+
+    property! {
+    /// Set the rotation of the image in degrees clockwise.
+    ///
+    /// Multiples of 90 degrees are rotated without any loss, other angles enlarge the image to fit the
+    /// rotated one, the new corners are transparent.
+    ///
+    /// # Errors
+    /// When the rotation is not a finite number, [`ConfigBuilder::build`] will return an [`Error::InvalidRotation`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.rotation(90f32);
+    /// ```
+    => rotation, f32
+    }
+
+    property! {
+    /// Set if fully transparent tiles are written as blank space without any colors.
+    ///
+    /// Otherwise they are converted like black tiles.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.blank_transparent(true);
+    /// ```
+    => blank_transparent, bool
+    }
+
+    property! {
+    /// Set the color, which transparent images are composited onto before the conversion.
+    ///
+    /// Semi-transparent pixels are blended with the color, so the image no longer contains any transparency.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    /// use image::Rgb;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.background_fill(Some(Rgb([255, 255, 255])));
+    /// ```
+    => background_fill, Option<Rgb<u8>>
+    }
+
+    property! {
+    /// Set if colored ansi and html output is compacted.
+    ///
+    /// A compacted output only changes the color when it differs from the previous character
+    /// and resets it once at the end of each row, instead of coloring every character on its own.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.compact(true);
+    /// ```
+    => compact, bool
+    }
+
+    property! {
+    /// Set how much each color channel may differ from the previous color, before a compacted output changes its color.
+    ///
+    /// Higher tolerances result in a smaller output, but less accurate colors. It is only used with [`ConfigBuilder::compact`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.compact(true);
+    /// builder.color_tolerance(8);
+    /// ```
+    => color_tolerance, u8
+    }
+
+    property! {
+    ///Set the target type
+    ///
+    /// This will effect the output, for example if the [`TargetType`] is set to html,
+    /// the output will be the content of a Html-file.
+    ///
+    /// See [`TargetType`] for more information. It defaults to the shell as the target.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    /// use artem::config::TargetType;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// //use color, but don't color the background
+    /// builder.target(TargetType::HtmlFile(true, false));
+    /// ```
+        => target,  TargetType
+    }
+
+    property! {
+    /// Set the font family used for svg output
+    ///
+    /// This is used as the `font-family` of the svg document, so it can be any valid
+    /// css font family list. It defaults to `monospace`.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.font_family("'Fira Code', monospace".to_string());
+    /// ```
+    => font_family, String
+    }
+
+    property! {
+    /// Set the size of a single character in pixels, used for png output
+    ///
+    /// The first value is the width, the second the height. It defaults to `(8, 16)`,
+    /// which is about the aspect ratio of characters in a terminal.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.cell_size((6, 12));
+    /// ```
+    => cell_size, (u32, u32)
+    }
+
+    property! {
+    /// Set the number of colors used for Ansi formatted output
+    ///
+    /// By default it is detected from the environment, see [`crate::COLOR_DEPTH`].
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ColorDepth, ConfigBuilder};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.color_depth(ColorDepth::Ansi256);
+    /// ```
+    => color_depth, ColorDepth
+    }
+
+    property! {
+    /// Set the metric used to find the most similar color, when using 16 or 256 colors
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ColorMetric, ConfigBuilder};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.color_metric(ColorMetric::Redmean);
+    /// ```
+    => color_metric, ColorMetric
+    }
+
+    property! {
+    /// Set the dithering method used for the characters and reduced colors
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, Dithering};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.dither(Dithering::FloydSteinberg);
+    /// ```
+    => dither, Dithering
+    }
+
+    property! {
+    /// Set the mode used to render the pixels of each tile, for example as half blocks
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, RenderMode};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.render_mode(RenderMode::HalfBlock);
+    /// ```
+    => render_mode, RenderMode
+    }
+
+    property! {
+    /// Set whether sextants are replaced by the quadrants of their top and bottom row, for fonts which do not support sextants.
+    ///
+    /// It is only used by [`RenderMode::Sextant`]. The middle row of the sextants can not be shown by the quadrants,
+    /// but the output keeps the vertical resolution of the sextants.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{ConfigBuilder, RenderMode};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.render_mode(RenderMode::Sextant).sextant_fallback(true);
+    /// ```
+    => sextant_fallback, bool
+    }
+
+    property! {
+    /// Set the calibration of the characters.
+    ///
+    /// When building the [`Config`], the characters are sorted and optionally spaced by how much of each one
+    /// is covered by ink, see [`crate::calibrate_characters`]. This is useful for custom characters,
+    /// which are not ordered from dark to light.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::{Calibration, ConfigBuilder};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// builder.characters(" .#:".to_string()).calibration(Calibration::Sort);
+    /// assert_eq!("#:. ", builder.build().unwrap().characters);
+    /// ```
+    => calibration, Calibration
+    }
+
+    ///Build the [`Config`] struct.
+    ///
+    /// This returns a [`Config`], which can than be used for the image conversion using [`super::convert()`].
+    /// If values are not explicitly specified, the default values will be used.
+    ///
+    /// # Errors
+    /// Returns an [`Error`] if one of the set values can not be used for a conversion,
+    /// for example when the characters are empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// let options = builder.build().unwrap();
+    /// ```
+    pub fn build(&self) -> Result<Config, Error> {
+        let config = Config {
+            characters: crate::calibrate_characters(&self.characters, self.calibration),
+            scale: self.scale,
+            target_size: self.target_size,
+            invert: self.invert,
+            border: self.border,
+            dimension: self.dimension,
+            transform_x: self.transform_x,
+            transform_y: self.transform_y,
+            center_x: self.center_x,
+            center_y: self.center_y,
+            outline: self.outline,
+            hysteresis: self.hysteresis,
+            target: self.target,
+            font_family: self.font_family.to_owned(),
+            cell_size: self.cell_size,
+            color_depth: self.color_depth,
+            color_metric: self.color_metric,
+            dither: self.dither,
+            render_mode: self.render_mode,
+            sextant_fallback: self.sextant_fallback,
+            edge_characters: self.edge_characters,
+            outline_sigma: self.outline_sigma,
+            outline_low: self.outline_low,
+            outline_high: self.outline_high,
+            non_max_suppression: self.non_max_suppression,
+            adjustments: self.adjustments.clone(),
+            orientation: self.orientation,
+            crop: self.crop,
+            rotation: self.rotation,
+            blank_transparent: self.blank_transparent,
+            background_fill: self.background_fill,
+            compact: self.compact,
+            color_tolerance: self.color_tolerance,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod test_conversion_configuration_builder {
+    use super::*;
+    #[test]
+    fn build_default() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_characters() {
+        assert_eq!(
+            Config {
+                characters: r#"characters"#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new()
+                .characters("characters".to_string())
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn change_scale() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 3.14f32, //change attribute
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().scale(3.14f32).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_target_size() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 314, //change attribute
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new()
+                .target_size(NonZeroU32::new(314).unwrap())
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn change_invert() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: true, //change attribute
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().invert(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_border() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: true, //change attribute
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().border(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_dimension() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Height, //change attribute
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new()
+                .dimension(ResizingDimension::Height)
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn change_transform_x() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: true, //change attribute
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().transform_x(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_transform_y() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: true, //change attribute
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().transform_y(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_center_x() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: true, //change attribute
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().center_x(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_center_y() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: true, //change attribute
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().center_y(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_outline() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: true, //change attribute
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().outline(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_hysteresis() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: true, //change attribute
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().hysteresis(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_edge_characters() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: true, //change attribute
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().edge_characters(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_outline_sigma() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: Some(2.5f32), //change attribute
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new()
+                .outline_sigma(Some(2.5f32))
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn change_outline_low() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.2f32, //change attribute
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().outline_low(0.2f32).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_outline_high() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.7f32, //change attribute
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().outline_high(0.7f32).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_non_max_suppression() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: true, //change attribute
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new()
+                .non_max_suppression(true)
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn change_adjustments() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
                 transform_y: false,
                 center_x: false,
                 center_y: false,
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: vec![Adjustment::Equalize, Adjustment::Gamma(2f32)], //change attribute
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
             ConfigBuilder::new()
-                .characters("characters".to_string())
+                .adjustments(vec![Adjustment::Equalize, Adjustment::Gamma(2f32)])
                 .build()
+                .unwrap()
         );
     }
 
     #[test]
-    fn change_scale() {
+    fn change_orientation() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
-                scale: 3.14f32, //change attribute
+                scale: 0.42f32,
                 target_size: 80,
                 invert: false,
                 border: false,
@@ -648,18 +2412,41 @@ mod test_conversion_configuration_builder {
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::Transpose, //change attribute
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
-            ConfigBuilder::new().scale(3.14f32).build()
+            ConfigBuilder::new()
+                .orientation(Orientation::Transpose)
+                .build()
+                .unwrap()
         );
     }
 
     #[test]
-    fn change_target_size() {
+    fn change_crop() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
                 scale: 0.42f32,
-                target_size: 314, //change attribute
+                target_size: 80,
                 invert: false,
                 border: false,
                 dimension: ResizingDimension::Width,
@@ -670,21 +2457,52 @@ mod test_conversion_configuration_builder {
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: Some(Crop::Percentage {
+                    x: 10f32,
+                    y: 20f32,
+                    width: 30f32,
+                    height: 40f32,
+                }), //change attribute
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
             ConfigBuilder::new()
-                .target_size(NonZeroU32::new(314).unwrap())
+                .crop(Some(Crop::Percentage {
+                    x: 10f32,
+                    y: 20f32,
+                    width: 30f32,
+                    height: 40f32,
+                }))
                 .build()
+                .unwrap()
         );
     }
 
     #[test]
-    fn change_invert() {
+    fn change_rotation() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
                 scale: 0.42f32,
                 target_size: 80,
-                invert: true, //change attribute
+                invert: false,
                 border: false,
                 dimension: ResizingDimension::Width,
                 transform_x: false,
@@ -694,20 +2512,40 @@ mod test_conversion_configuration_builder {
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 45f32, //change attribute
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
-            ConfigBuilder::new().invert(true).build()
+            ConfigBuilder::new().rotation(45f32).build().unwrap()
         );
     }
 
     #[test]
-    fn change_border() {
+    fn change_blank_transparent() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
                 scale: 0.42f32,
                 target_size: 80,
                 invert: false,
-                border: true, //change attribute
+                border: false,
                 dimension: ResizingDimension::Width,
                 transform_x: false,
                 transform_y: false,
@@ -716,13 +2554,36 @@ mod test_conversion_configuration_builder {
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: true, //change attribute
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
-            ConfigBuilder::new().border(true).build()
+            ConfigBuilder::new()
+                .blank_transparent(true)
+                .build()
+                .unwrap()
         );
     }
 
     #[test]
-    fn change_dimension() {
+    fn change_compact() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
@@ -730,7 +2591,91 @@ mod test_conversion_configuration_builder {
                 target_size: 80,
                 invert: false,
                 border: false,
-                dimension: ResizingDimension::Height, //change attribute
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: true, //change attribute
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new().compact(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_color_tolerance() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 8, //change attribute
+            },
+            ConfigBuilder::new().color_tolerance(8).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_background_fill() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
                 transform_x: false,
                 transform_y: false,
                 center_x: false,
@@ -738,15 +2683,36 @@ mod test_conversion_configuration_builder {
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: Some(Rgb([1, 2, 3])), //change attribute
+                compact: false,
+                color_tolerance: 0,
             },
             ConfigBuilder::new()
-                .dimension(ResizingDimension::Height)
+                .background_fill(Some(Rgb([1, 2, 3])))
                 .build()
+                .unwrap()
         );
     }
 
     #[test]
-    fn change_transform_x() {
+    fn change_file_type() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
@@ -755,20 +2721,88 @@ mod test_conversion_configuration_builder {
                 invert: false,
                 border: false,
                 dimension: ResizingDimension::Width,
-                transform_x: true, //change attribute
+                transform_x: false,
+                transform_y: false,
+                center_x: false,
+                center_y: false,
+                outline: false,
+                hysteresis: false,
+                target: TargetType::AnsiFile(false), //change attribute
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
+            },
+            ConfigBuilder::new()
+                .target(TargetType::AnsiFile(false))
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn change_font_family() {
+        assert_eq!(
+            Config {
+                characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
+                scale: 0.42f32,
+                target_size: 80,
+                invert: false,
+                border: false,
+                dimension: ResizingDimension::Width,
+                transform_x: false,
                 transform_y: false,
                 center_x: false,
                 center_y: false,
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "serif".to_string(), //change attribute
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
-            ConfigBuilder::new().transform_x(true).build()
+            ConfigBuilder::new()
+                .font_family("serif".to_string())
+                .build()
+                .unwrap()
         );
     }
 
     #[test]
-    fn change_transform_y() {
+    fn change_cell_size() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
@@ -778,19 +2812,39 @@ mod test_conversion_configuration_builder {
                 border: false,
                 dimension: ResizingDimension::Width,
                 transform_x: false,
-                transform_y: true, //change attribute
+                transform_y: false,
                 center_x: false,
                 center_y: false,
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (6, 12), //change attribute
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
-            ConfigBuilder::new().transform_y(true).build()
+            ConfigBuilder::new().cell_size((6, 12)).build().unwrap()
         );
     }
 
     #[test]
-    fn change_center_x() {
+    fn change_color_depth() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
@@ -801,18 +2855,41 @@ mod test_conversion_configuration_builder {
                 dimension: ResizingDimension::Width,
                 transform_x: false,
                 transform_y: false,
-                center_x: true, //change attribute
+                center_x: false,
                 center_y: false,
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::Ansi256, //change attribute
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
-            ConfigBuilder::new().center_x(true).build()
+            ConfigBuilder::new()
+                .color_depth(ColorDepth::Ansi256)
+                .build()
+                .unwrap()
         );
     }
 
     #[test]
-    fn change_center_y() {
+    fn change_color_metric() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
@@ -824,17 +2901,40 @@ mod test_conversion_configuration_builder {
                 transform_x: false,
                 transform_y: false,
                 center_x: false,
-                center_y: true, //change attribute
+                center_y: false,
                 outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::Redmean, //change attribute
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
-            ConfigBuilder::new().center_y(true).build()
+            ConfigBuilder::new()
+                .color_metric(ColorMetric::Redmean)
+                .build()
+                .unwrap()
         );
     }
 
     #[test]
-    fn change_outline() {
+    fn change_dither() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
@@ -847,16 +2947,39 @@ mod test_conversion_configuration_builder {
                 transform_y: false,
                 center_x: false,
                 center_y: false,
-                outline: true, //change attribute
+                outline: false,
                 hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::Atkinson, //change attribute
+                render_mode: RenderMode::default(),
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
-            ConfigBuilder::new().outline(true).build()
+            ConfigBuilder::new()
+                .dither(Dithering::Atkinson)
+                .build()
+                .unwrap()
         );
     }
 
     #[test]
-    fn change_hysteresis() {
+    fn change_render_mode() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
@@ -870,15 +2993,38 @@ mod test_conversion_configuration_builder {
                 center_x: false,
                 center_y: false,
                 outline: false,
-                hysteresis: true, //change attribute
+                hysteresis: false,
                 target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::HalfBlock, //change attribute
+                sextant_fallback: false,
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
-            ConfigBuilder::new().hysteresis(true).build()
+            ConfigBuilder::new()
+                .render_mode(RenderMode::HalfBlock)
+                .build()
+                .unwrap()
         );
     }
 
     #[test]
-    fn change_file_type() {
+    fn change_sextant_fallback() {
         assert_eq!(
             Config {
                 characters: r#"MWNXK0Okxdolc:;,'...   "#.to_string(),
@@ -893,13 +3039,160 @@ mod test_conversion_configuration_builder {
                 center_y: false,
                 outline: false,
                 hysteresis: false,
-                target: TargetType::AnsiFile(false), //change attribute
+                target: TargetType::default(),
+                font_family: "monospace".to_string(),
+                cell_size: (8, 16),
+                color_depth: ColorDepth::default(),
+                color_metric: ColorMetric::default(),
+                dither: Dithering::default(),
+                render_mode: RenderMode::default(),
+                sextant_fallback: true, //change attribute
+                edge_characters: false,
+                outline_sigma: None,
+                outline_low: 0.3f32,
+                outline_high: 0.5f32,
+                non_max_suppression: false,
+                adjustments: Vec::new(),
+                orientation: Orientation::default(),
+                crop: None,
+                rotation: 0f32,
+                blank_transparent: false,
+                background_fill: None,
+                compact: false,
+                color_tolerance: 0,
             },
+            ConfigBuilder::new().sextant_fallback(true).build().unwrap()
+        );
+    }
+
+    #[test]
+    fn change_calibration() {
+        assert_eq!(
+            "#:. ",
             ConfigBuilder::new()
-                .target(TargetType::AnsiFile(false))
+                .characters(" .#:".to_string())
+                .calibration(Calibration::Sort) //change attribute
                 .build()
+                .unwrap()
+                .characters
         );
     }
+
+    #[test]
+    fn invalid_outline_sigma_fails() {
+        assert!(matches!(
+            ConfigBuilder::new().outline_sigma(Some(0f32)).build(),
+            Err(Error::InvalidOutlineSigma(_))
+        ));
+        assert!(matches!(
+            ConfigBuilder::new().outline_sigma(Some(f32::NAN)).build(),
+            Err(Error::InvalidOutlineSigma(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_outline_thresholds_fail() {
+        assert!(matches!(
+            ConfigBuilder::new().outline_low(0.6f32).build(),
+            Err(Error::InvalidOutlineThresholds(_, _))
+        ));
+        assert!(matches!(
+            ConfigBuilder::new().outline_high(1.5f32).build(),
+            Err(Error::InvalidOutlineThresholds(_, _))
+        ));
+        assert!(matches!(
+            ConfigBuilder::new().outline_low(-0.1f32).build(),
+            Err(Error::InvalidOutlineThresholds(_, _))
+        ));
+    }
+
+    #[test]
+    fn invalid_adjustment_fails() {
+        assert!(matches!(
+            ConfigBuilder::new()
+                .adjustments(vec![Adjustment::AutoLevels, Adjustment::Gamma(-1f32)])
+                .build(),
+            Err(Error::InvalidAdjustment(Adjustment::Gamma(_)))
+        ));
+        assert!(matches!(
+            ConfigBuilder::new()
+                .adjustments(vec![Adjustment::Brightness(f32::NAN)])
+                .build(),
+            Err(Error::InvalidAdjustment(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_rotation_fails() {
+        assert!(matches!(
+            ConfigBuilder::new().rotation(f32::INFINITY).build(),
+            Err(Error::InvalidRotation(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_crop_fails() {
+        assert!(matches!(
+            ConfigBuilder::new()
+                .crop(Some(Crop::Pixels {
+                    x: 0,
+                    y: 0,
+                    width: 0,
+                    height: 10
+                }))
+                .build(),
+            Err(Error::InvalidCrop(_))
+        ));
+    }
+
+    #[test]
+    fn empty_characters_fail() {
+        assert!(matches!(
+            ConfigBuilder::new().characters(String::new()).build(),
+            Err(Error::EmptyCharacters)
+        ));
+    }
+
+    #[test]
+    fn zero_scale_fails() {
+        assert!(matches!(
+            ConfigBuilder::new().scale(0f32).build(),
+            Err(Error::InvalidScale(_))
+        ));
+    }
+
+    #[test]
+    fn negative_scale_fails() {
+        assert!(matches!(
+            ConfigBuilder::new().scale(-0.42f32).build(),
+            Err(Error::InvalidScale(_))
+        ));
+    }
+
+    #[test]
+    fn nan_scale_fails() {
+        assert!(matches!(
+            ConfigBuilder::new().scale(f32::NAN).build(),
+            Err(Error::InvalidScale(_))
+        ));
+    }
+
+    #[test]
+    fn zero_target_size_fails() {
+        let config = Config {
+            target_size: 0,
+            ..Default::default()
+        };
+        assert!(matches!(config.validate(), Err(Error::ZeroTargetSize)));
+    }
+
+    #[test]
+    fn zero_cell_size_fails() {
+        assert!(matches!(
+            ConfigBuilder::new().cell_size((8, 0)).build(),
+            Err(Error::ZeroCellSize)
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -916,7 +3209,8 @@ mod test_calculate_dimensions {
                 512,
                 0.42,
                 false,
-                ResizingDimension::Width
+                ResizingDimension::Width,
+                RenderMode::Ascii
             )
         );
     }
@@ -931,7 +3225,8 @@ mod test_calculate_dimensions {
                 512,
                 0.42,
                 false,
-                ResizingDimension::Width
+                ResizingDimension::Width,
+                RenderMode::Ascii
             )
         );
     }
@@ -946,7 +3241,8 @@ mod test_calculate_dimensions {
                 512,
                 0.42,
                 false,
-                ResizingDimension::Height
+                ResizingDimension::Height,
+                RenderMode::Ascii
             )
         );
     }
@@ -961,7 +3257,8 @@ mod test_calculate_dimensions {
                 1,
                 0.42,
                 false,
-                ResizingDimension::Height
+                ResizingDimension::Height,
+                RenderMode::Ascii
             )
         );
     }
@@ -976,7 +3273,8 @@ mod test_calculate_dimensions {
                 1,
                 0.42,
                 false,
-                ResizingDimension::Width
+                ResizingDimension::Width,
+                RenderMode::Ascii
             )
         );
     }
@@ -991,19 +3289,36 @@ mod test_calculate_dimensions {
             0.42,
             false,
             ResizingDimension::Height,
+            RenderMode::Ascii,
         );
     }
 
     #[test]
     #[should_panic]
     fn calculate_dimensions_width_zero() {
-        ResizingDimension::calculate_dimensions(0, 512, 512, 0.42, false, ResizingDimension::Width);
+        ResizingDimension::calculate_dimensions(
+            0,
+            512,
+            512,
+            0.42,
+            false,
+            ResizingDimension::Width,
+            RenderMode::Ascii,
+        );
     }
 
     #[test]
     #[should_panic]
     fn calculate_dimensions_img_width_zero() {
-        ResizingDimension::calculate_dimensions(100, 512, 0, 0.42, false, ResizingDimension::Width);
+        ResizingDimension::calculate_dimensions(
+            100,
+            512,
+            0,
+            0.42,
+            false,
+            ResizingDimension::Width,
+            RenderMode::Ascii,
+        );
     }
 
     #[test]
@@ -1016,13 +3331,22 @@ mod test_calculate_dimensions {
             0.42,
             false,
             ResizingDimension::Height,
+            RenderMode::Ascii,
         );
     }
 
     #[test]
     #[should_panic]
     fn calculate_dimensions_img_width_height_zero() {
-        ResizingDimension::calculate_dimensions(100, 0, 0, 0.42, false, ResizingDimension::Height);
+        ResizingDimension::calculate_dimensions(
+            100,
+            0,
+            0,
+            0.42,
+            false,
+            ResizingDimension::Height,
+            RenderMode::Ascii,
+        );
     }
 
     #[test]
@@ -1035,7 +3359,40 @@ mod test_calculate_dimensions {
                 512,
                 0f32,
                 false,
-                ResizingDimension::Width
+                ResizingDimension::Width,
+                RenderMode::Ascii
+            )
+        );
+    }
+
+    #[test]
+    fn calculate_dimensions_scale_larger_than_tile() {
+        assert_eq!(
+            (100, 512, 5, 1),
+            ResizingDimension::calculate_dimensions(
+                100,
+                512,
+                512,
+                10f32,
+                false,
+                ResizingDimension::Width,
+                RenderMode::Ascii
+            )
+        );
+    }
+
+    #[test]
+    fn calculate_dimensions_height_target_size_one() {
+        assert_eq!(
+            (2, 1, 216, 512),
+            ResizingDimension::calculate_dimensions(
+                1,
+                512,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Height,
+                RenderMode::Ascii
             )
         );
     }
@@ -1050,10 +3407,97 @@ mod test_calculate_dimensions {
                 512,
                 0f32,
                 true,
-                ResizingDimension::Width
+                ResizingDimension::Width,
+                RenderMode::Ascii
+            )
+        );
+    }
+
+    #[test]
+    fn calculate_dimensions_width_half_block() {
+        //the tile height is even, so it can be split into two pixels
+        assert_eq!(
+            (100, 51, 5, 10),
+            ResizingDimension::calculate_dimensions(
+                100,
+                512,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Width,
+                RenderMode::HalfBlock
+            )
+        );
+    }
+
+    #[test]
+    fn calculate_dimensions_height_half_block() {
+        assert_eq!(
+            (256, 99, 2, 4),
+            ResizingDimension::calculate_dimensions(
+                100,
+                512,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Height,
+                RenderMode::HalfBlock
+            )
+        );
+    }
+
+    #[test]
+    fn calculate_dimensions_half_block_1x1_img() {
+        for dimension in [ResizingDimension::Width, ResizingDimension::Height] {
+            assert_eq!(
+                (1, 1, 1, 2),
+                ResizingDimension::calculate_dimensions(
+                    100,
+                    1,
+                    1,
+                    0.42,
+                    false,
+                    dimension,
+                    RenderMode::HalfBlock
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn calculate_dimensions_width_braille() {
+        //the tiles are split into 2x4 dots
+        assert_eq!(
+            (100, 64, 4, 8),
+            ResizingDimension::calculate_dimensions(
+                100,
+                512,
+                512,
+                0.42,
+                false,
+                ResizingDimension::Width,
+                RenderMode::Braille
             )
         );
     }
+
+    #[test]
+    fn calculate_dimensions_braille_1x1_img() {
+        for dimension in [ResizingDimension::Width, ResizingDimension::Height] {
+            assert_eq!(
+                (1, 1, 2, 4),
+                ResizingDimension::calculate_dimensions(
+                    100,
+                    1,
+                    1,
+                    0.42,
+                    false,
+                    dimension,
+                    RenderMode::Braille
+                )
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1064,4 +3508,14 @@ mod test_dimensions_enum {
     fn default_is_width() {
         assert_eq!(ResizingDimension::Width, ResizingDimension::default());
     }
+
+    #[test]
+    fn render_mode_pixels_per_cell() {
+        assert_eq!((1, 1), RenderMode::Ascii.pixels_per_cell());
+        assert_eq!((1, 2), RenderMode::HalfBlock.pixels_per_cell());
+        assert_eq!((2, 4), RenderMode::Braille.pixels_per_cell());
+        assert_eq!((2, 2), RenderMode::Quadrant.pixels_per_cell());
+        assert_eq!((2, 3), RenderMode::Sextant.pixels_per_cell());
+        assert_eq!((1, 1), RenderMode::Shape.pixels_per_cell());
+    }
 }
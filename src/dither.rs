@@ -0,0 +1,207 @@
+use crate::config::Dithering;
+
+/// 4x4 Bayer matrix, used for ordered dithering.
+const BAYER_MATRIX: [[f32; 4]; 4] = [
+    [0f32, 8f32, 2f32, 10f32],
+    [12f32, 4f32, 14f32, 6f32],
+    [3f32, 11f32, 1f32, 9f32],
+    [15f32, 7f32, 13f32, 5f32],
+];
+
+/// Dithers values with `N` channels, which are passed in row by row.
+///
+/// For error diffusion, the quantization error of each value is distributed to the following
+/// values in the same row and the next rows. Since the values are passed in one after another,
+/// this can be used while streaming, without knowing the whole image.
+/// The rows grow as needed, so the number of columns does not have to be known beforehand.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let mut ditherer = Ditherer::<1>::new(Dithering::FloydSteinberg, 255f32);
+/// //quantize to black or white
+/// let level = ditherer.dither([100f32], |[value]| {
+///     let level = (value / 255f32).round().clamp(0f32, 1f32);
+///     (level as usize, [level * 255f32])
+/// });
+/// ditherer.end_row();
+/// ```
+#[derive(Debug, Clone)]
+pub(crate) struct Ditherer<const N: usize> {
+    method: Dithering,
+    //distance between two quantization levels, used by ordered dithering
+    spread: f32,
+    //accumulated errors of the current row and the two following rows
+    errors: [Vec<[f32; N]>; 3],
+    column: usize,
+    row: usize,
+}
+
+impl<const N: usize> Ditherer<N> {
+    /// Create a new [`Ditherer`] using the given method.
+    ///
+    /// The `spread` is the distance between two quantized values, for example `255 / (levels - 1)`.
+    pub(crate) fn new(method: Dithering, spread: f32) -> Self {
+        Self {
+            method,
+            spread,
+            errors: [Vec::new(), Vec::new(), Vec::new()],
+            column: 0,
+            row: 0,
+        }
+    }
+
+    /// Dither the next value of the current row.
+    ///
+    /// The `quantize` function has to reduce the (adjusted) value to the nearest available value,
+    /// it returns the result, as well as the value it represents, which is used to calculate the error.
+    pub(crate) fn dither<T>(
+        &mut self,
+        value: [f32; N],
+        quantize: impl FnOnce([f32; N]) -> (T, [f32; N]),
+    ) -> T {
+        let column = self.column;
+        self.column += 1;
+
+        let adjusted = match self.method {
+            Dithering::None => value,
+            Dithering::Bayer => {
+                //shift the value by up to half a level in either direction
+                let threshold = (BAYER_MATRIX[self.row % 4][column % 4] + 0.5) / 16f32 - 0.5;
+                value.map(|channel| channel + threshold * self.spread)
+            }
+            Dithering::FloydSteinberg | Dithering::Atkinson => {
+                let error = self.error(0, column);
+                let mut adjusted = value;
+                for channel in 0..N {
+                    adjusted[channel] += error[channel];
+                }
+                adjusted
+            }
+        };
+
+        let (result, quantized) = quantize(adjusted);
+
+        let mut error = [0f32; N];
+        for channel in 0..N {
+            error[channel] = adjusted[channel] - quantized[channel];
+        }
+
+        match self.method {
+            Dithering::FloydSteinberg => {
+                self.diffuse(0, column as isize + 1, error, 7f32 / 16f32);
+                self.diffuse(1, column as isize - 1, error, 3f32 / 16f32);
+                self.diffuse(1, column as isize, error, 5f32 / 16f32);
+                self.diffuse(1, column as isize + 1, error, 1f32 / 16f32);
+            }
+            Dithering::Atkinson => {
+                //only 3/4 of the error is distributed, which keeps more contrast
+                self.diffuse(0, column as isize + 1, error, 1f32 / 8f32);
+                self.diffuse(0, column as isize + 2, error, 1f32 / 8f32);
+                self.diffuse(1, column as isize - 1, error, 1f32 / 8f32);
+                self.diffuse(1, column as isize, error, 1f32 / 8f32);
+                self.diffuse(1, column as isize + 1, error, 1f32 / 8f32);
+                self.diffuse(2, column as isize, error, 1f32 / 8f32);
+            }
+            Dithering::None | Dithering::Bayer => {}
+        }
+
+        result
+    }
+
+    /// Skip the next value of the current row, without dithering it.
+    ///
+    /// The error diffused into the skipped value is dropped, so it does not end up in the following values.
+    pub(crate) fn skip(&mut self) {
+        self.column += 1;
+    }
+
+    /// Start a new row.
+    pub(crate) fn end_row(&mut self) {
+        self.errors.rotate_left(1);
+        self.errors[2].clear();
+        self.column = 0;
+        self.row += 1;
+    }
+
+    /// Returns the accumulated error at the column of the current row (0) or one of the following rows.
+    fn error(&self, row: usize, column: usize) -> [f32; N] {
+        self.errors[row].get(column).copied().unwrap_or([0f32; N])
+    }
+
+    /// Add a part of the error to the value at the column of the current row (0) or one of the following rows.
+    fn diffuse(&mut self, row: usize, column: isize, error: [f32; N], factor: f32) {
+        //the error can not be distributed to the left of the first column
+        let Ok(column) = usize::try_from(column) else {
+            return;
+        };
+
+        let errors = &mut self.errors[row];
+        if errors.len() <= column {
+            errors.resize(column + 1, [0f32; N]);
+        }
+        for channel in 0..N {
+            errors[column][channel] += error[channel] * factor;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_ditherer {
+    use super::*;
+
+    /// Dither a gray image to black and white and return the number of white pixels.
+    fn white_pixels(method: Dithering, value: f32, columns: usize, rows: usize) -> usize {
+        let mut ditherer = Ditherer::<1>::new(method, 255f32);
+        let mut white = 0;
+        for _ in 0..rows {
+            for _ in 0..columns {
+                let level = ditherer.dither([value], |[value]| {
+                    let level = (value / 255f32).round().clamp(0f32, 1f32);
+                    (level as usize, [level * 255f32])
+                });
+                white += level;
+            }
+            ditherer.end_row();
+        }
+        white
+    }
+
+    #[test]
+    fn none_only_quantizes() {
+        assert_eq!(0, white_pixels(Dithering::None, 100f32, 8, 8));
+        assert_eq!(64, white_pixels(Dithering::None, 160f32, 8, 8));
+    }
+
+    #[test]
+    fn floyd_steinberg_keeps_average() {
+        //about half of the pixels should be white for a medium gray
+        let white = white_pixels(Dithering::FloydSteinberg, 127.5, 16, 16);
+        assert!((120..=136).contains(&white), "{white}");
+    }
+
+    #[test]
+    fn atkinson_keeps_average() {
+        let white = white_pixels(Dithering::Atkinson, 127.5, 16, 16);
+        assert!((112..=144).contains(&white), "{white}");
+    }
+
+    #[test]
+    fn bayer_pattern() {
+        //a quarter gray results in exactly a quarter of white pixels
+        assert_eq!(16, white_pixels(Dithering::Bayer, 63.75, 8, 8));
+        //the pattern is the same for every 4x4 block
+        assert_eq!(4, white_pixels(Dithering::Bayer, 63.75, 4, 4));
+    }
+
+    #[test]
+    fn extremes_are_not_dithered() {
+        for method in [
+            Dithering::FloydSteinberg,
+            Dithering::Atkinson,
+            Dithering::Bayer,
+        ] {
+            assert_eq!(0, white_pixels(method, 0f32, 8, 8));
+            assert_eq!(64, white_pixels(method, 255f32, 8, 8));
+        }
+    }
+}
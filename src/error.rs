@@ -0,0 +1,123 @@
+use std::{fmt, io};
+
+use crate::config::{Adjustment, Crop};
+
+/// Errors that can occur when configuring or converting an image.
+///
+/// It is returned by [`crate::convert`] and [`crate::config::ConfigBuilder::build`], instead of panicking
+/// somewhere in the middle of the conversion.
+///
+/// New variants might be added in the future, so a `match` outside of this crate needs a wildcard arm.
+///
+/// # Examples
+/// ```
+/// use artem::{config::ConfigBuilder, Error};
+///
+/// let result = ConfigBuilder::new().characters(String::new()).build();
+/// assert!(matches!(result, Err(Error::EmptyCharacters)));
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The characters used for the conversion are empty.
+    EmptyCharacters,
+    /// The scale is either zero, negative or not a finite number.
+    InvalidScale(f32),
+    /// The target size is zero, which would result in an empty output.
+    ZeroTargetSize,
+    /// The width or height of a character cell is zero, which would result in an empty image.
+    ZeroCellSize,
+    /// The sigma of the blur used for outlining is either zero, negative or not a finite number.
+    InvalidOutlineSigma(f32),
+    /// The lower and upper outline thresholds are not between 0 and 1, or the lower one is above the upper one.
+    InvalidOutlineThresholds(f32, f32),
+    /// One of the adjustments of the image has a value, which can not be used, see [`crate::config::Adjustment::is_valid`].
+    InvalidAdjustment(Adjustment),
+    /// The rotation of the image is not a finite number.
+    InvalidRotation(f32),
+    /// The crop has no width or height, a percentage is outside of 0 to 100 or it does not overlap with the image.
+    InvalidCrop(Crop),
+    /// The input image has a width or height of zero, the values are the width and height of the image.
+    ZeroDimensions(u32, u32),
+    /// Writing the output failed, for example when using [`crate::convert_to_writer`].
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::EmptyCharacters => write!(f, "The characters used for the conversion are empty"),
+            Error::InvalidScale(scale) => {
+                write!(f, "The scale {scale} is not a positive, finite number")
+            }
+            Error::ZeroTargetSize => write!(f, "The target size has to be at least 1"),
+            Error::ZeroCellSize => write!(f, "The cell size has to be at least 1x1"),
+            Error::InvalidOutlineSigma(sigma) => {
+                write!(f, "The outline sigma {sigma} is not a positive, finite number")
+            }
+            Error::InvalidOutlineThresholds(low, high) => write!(
+                f,
+                "The outline thresholds {low} and {high} have to be between 0 and 1, with the lower one first"
+            ),
+            Error::ZeroDimensions(width, height) => write!(
+                f,
+                "The image has a size of {width}x{height}, but needs to be at least 1x1"
+            ),
+            Error::InvalidAdjustment(adjustment) => {
+                write!(f, "The adjustment {adjustment:?} has an invalid value")
+            }
+            Error::InvalidRotation(rotation) => {
+                write!(f, "The rotation {rotation} is not a finite number")
+            }
+            Error::InvalidCrop(crop) => write!(
+                f,
+                "The crop {crop:?} does not contain any part of the image"
+            ),
+            Error::Io(err) => write!(f, "Failed to write the output: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod test_error_message {
+    use super::*;
+
+    #[test]
+    fn zero_dimensions_message() {
+        assert_eq!(
+            "The image has a size of 0x12, but needs to be at least 1x1",
+            Error::ZeroDimensions(0, 12).to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_scale_message() {
+        assert_eq!(
+            "The scale -1 is not a positive, finite number",
+            Error::InvalidScale(-1f32).to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_outline_thresholds_message() {
+        assert_eq!(
+            "The outline thresholds 0.6 and 0.5 have to be between 0 and 1, with the lower one first",
+            Error::InvalidOutlineThresholds(0.6f32, 0.5f32).to_string()
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use std::time::Instant;
+
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+
+use crate::config::Adjustment;
+
+/// Apply the adjustments to the image, in the given order.
+///
+/// The image is converted to rgba, the alpha channel of each pixel is kept as it is.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let adjusted = adjust_image(img, &[Adjustment::AutoLevels, Adjustment::Contrast(1.2f32)]);
+/// ```
+pub(crate) fn adjust_image(img: DynamicImage, adjustments: &[Adjustment]) -> DynamicImage {
+    //start tracking to for this step
+    log::trace!("Started time tracking for adjusting the image");
+    let now = Instant::now();
+
+    let mut img = img.to_rgba8();
+    for adjustment in adjustments {
+        log::debug!("Applying adjustment {adjustment:?}");
+        img = match *adjustment {
+            Adjustment::Brightness(brightness) => {
+                map_channels(img, |value| value + brightness * 255f32)
+            }
+            Adjustment::Contrast(contrast) => {
+                map_channels(img, |value| (value - 127.5f32) * contrast + 127.5f32)
+            }
+            Adjustment::Gamma(gamma) => {
+                map_channels(img, |value| (value / 255f32).powf(1f32 / gamma) * 255f32)
+            }
+            Adjustment::Saturation(saturation) => map_pixels(img, |[red, green, blue]| {
+                let gray = luminosity(red, green, blue);
+                [red, green, blue].map(|value| gray + (value - gray) * saturation)
+            }),
+            Adjustment::Hue(degrees) => rotate_hue(img, degrees),
+            Adjustment::Sharpen { sigma, amount } => sharpen(img, sigma, amount),
+            Adjustment::AutoLevels => auto_levels(img),
+            Adjustment::Equalize => equalize(img),
+        };
+    }
+
+    log::info!(
+        "Successfully adjusted image in {:3} ms",
+        now.elapsed().as_millis()
+    );
+    DynamicImage::ImageRgba8(img)
+}
+
+/// Returns the luminosity of a color, using the same weights as [`crate::pixel::luminosity`].
+fn luminosity(red: f32, green: f32, blue: f32) -> f32 {
+    (0.21 * red) + (0.72 * green) + (0.07 * blue)
+}
+
+/// Map the red, green and blue values of each pixel, the results are clamped and rounded.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let inverted = map_pixels(img, |rgb| rgb.map(|value| 255f32 - value));
+/// ```
+fn map_pixels(mut img: RgbaImage, function: impl Fn([f32; 3]) -> [f32; 3]) -> RgbaImage {
+    for pixel in img.pixels_mut() {
+        let [red, green, blue, alpha] = pixel.0;
+        let [red, green, blue] = function([red as f32, green as f32, blue as f32]).map(to_channel);
+        *pixel = Rgba([red, green, blue, alpha]);
+    }
+    img
+}
+
+/// Map each red, green and blue value independently, see [`map_pixels`].
+fn map_channels(img: RgbaImage, function: impl Fn(f32) -> f32) -> RgbaImage {
+    map_pixels(img, |rgb| rgb.map(&function))
+}
+
+/// Clamp and round a value to a color channel.
+fn to_channel(value: f32) -> u8 {
+    value.round().clamp(0f32, 255f32) as u8
+}
+
+#[cfg(test)]
+mod test_adjust_image {
+    use super::*;
+
+    /// Returns the rgb values of the first pixel after applying the adjustments to a single pixel image.
+    fn adjust_pixel(pixel: [u8; 4], adjustments: &[Adjustment]) -> [u8; 4] {
+        let img = DynamicImage::ImageRgba8(RgbaImage::from_pixel(1, 1, Rgba(pixel)));
+        adjust_image(img, adjustments).get_pixel(0, 0).0
+    }
+
+    #[test]
+    fn no_adjustments() {
+        assert_eq!([12, 34, 56, 78], adjust_pixel([12, 34, 56, 78], &[]));
+    }
+
+    #[test]
+    fn brightness_is_clamped() {
+        assert_eq!(
+            [51, 255, 51, 255],
+            adjust_pixel([0, 230, 0, 255], &[Adjustment::Brightness(0.2f32)])
+        );
+        assert_eq!(
+            [0, 179, 0, 255],
+            adjust_pixel([0, 230, 0, 255], &[Adjustment::Brightness(-0.2f32)])
+        );
+    }
+
+    #[test]
+    fn contrast_around_gray() {
+        assert_eq!(
+            [65, 191, 129, 255],
+            adjust_pixel([96, 159, 128, 255], &[Adjustment::Contrast(2f32)])
+        );
+        assert_eq!(
+            [128, 128, 128, 255],
+            adjust_pixel([0, 255, 200, 255], &[Adjustment::Contrast(0f32)])
+        );
+    }
+
+    #[test]
+    fn gamma_brightens_dark_parts() {
+        assert_eq!(
+            [0, 128, 255, 255],
+            adjust_pixel([0, 64, 255, 255], &[Adjustment::Gamma(2f32)])
+        );
+    }
+
+    #[test]
+    fn no_saturation_is_gray() {
+        let [red, green, blue, alpha] =
+            adjust_pixel([200, 100, 0, 128], &[Adjustment::Saturation(0f32)]);
+        assert_eq!(red, green);
+        assert_eq!(green, blue);
+        assert_eq!(128, alpha);
+    }
+
+    #[test]
+    fn order_is_kept() {
+        //brightening first clamps the value, before it is darkened again
+        assert_eq!(
+            [204, 204, 204, 255],
+            adjust_pixel(
+                [230, 230, 230, 255],
+                &[
+                    Adjustment::Brightness(0.2f32),
+                    Adjustment::Brightness(-0.2f32)
+                ]
+            )
+        );
+        assert_eq!(
+            [230, 230, 230, 255],
+            adjust_pixel(
+                [230, 230, 230, 255],
+                &[
+                    Adjustment::Brightness(-0.2f32),
+                    Adjustment::Brightness(0.2f32)
+                ]
+            )
+        );
+    }
+}
+
+/// Rotate the hue of each pixel by the given degrees, using a rotation around the gray axis of the rgb color space.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let rotated = rotate_hue(img, 180f32);
+/// ```
+fn rotate_hue(img: RgbaImage, degrees: f32) -> RgbaImage {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let matrix = [
+        [
+            0.213 + cos * 0.787 - sin * 0.213,
+            0.715 - cos * 0.715 - sin * 0.715,
+            0.072 - cos * 0.072 + sin * 0.928,
+        ],
+        [
+            0.213 - cos * 0.213 + sin * 0.143,
+            0.715 + cos * 0.285 + sin * 0.140,
+            0.072 - cos * 0.072 - sin * 0.283,
+        ],
+        [
+            0.213 - cos * 0.213 - sin * 0.787,
+            0.715 - cos * 0.715 + sin * 0.715,
+            0.072 + cos * 0.928 + sin * 0.072,
+        ],
+    ];
+    map_pixels(img, |rgb| {
+        matrix.map(|row| row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2])
+    })
+}
+
+#[cfg(test)]
+mod test_rotate_hue {
+    use super::*;
+
+    #[test]
+    fn full_rotation_is_unchanged() {
+        let img = RgbaImage::from_pixel(1, 1, Rgba([200, 100, 20, 255]));
+        assert_eq!(img.clone(), rotate_hue(img, 360f32));
+    }
+
+    #[test]
+    fn gray_is_unchanged() {
+        let img = RgbaImage::from_pixel(1, 1, Rgba([100, 100, 100, 255]));
+        assert_eq!(img.clone(), rotate_hue(img, 123f32));
+    }
+
+    #[test]
+    fn red_turns_green() {
+        let rotated = rotate_hue(RgbaImage::from_pixel(1, 1, Rgba([255, 0, 0, 255])), 120f32);
+        let [red, green, blue, _] = rotated.get_pixel(0, 0).0;
+        assert!(green > red && green > blue);
+    }
+}
+
+/// Sharpen the image using an unsharp mask.
+///
+/// The image is blurred using the same gaussian blur as the outline and the difference to it is
+/// scaled by the amount and added to the image again, which increases the contrast of the edges.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let sharpened = sharpen(img, 1f32, 0.8f32);
+/// ```
+fn sharpen(mut img: RgbaImage, sigma: f32, amount: f32) -> RgbaImage {
+    let blurred = super::blur(DynamicImage::ImageRgba8(img.clone()), sigma);
+    for (x, y, pixel) in img.enumerate_pixels_mut() {
+        let blurred_pixel = blurred.get_pixel(x, y);
+        for channel in 0..3 {
+            let value = pixel.0[channel] as f32;
+            pixel.0[channel] =
+                to_channel(value + (value - blurred_pixel.0[channel] as f32) * amount);
+        }
+    }
+    img
+}
+
+#[cfg(test)]
+mod test_sharpen {
+    use super::*;
+
+    #[test]
+    fn flat_image_is_unchanged() {
+        let img = RgbaImage::from_pixel(5, 5, Rgba([100, 150, 200, 255]));
+        assert_eq!(img.clone(), sharpen(img, 1f32, 1f32));
+    }
+
+    #[test]
+    fn edge_gets_more_contrast() {
+        let img = RgbaImage::from_fn(6, 1, |x, _| {
+            if x < 3 {
+                Rgba([100, 100, 100, 255])
+            } else {
+                Rgba([150, 150, 150, 255])
+            }
+        });
+        let sharpened = sharpen(img, 1f32, 1f32);
+        assert!(sharpened.get_pixel(2, 0).0[0] < 100);
+        assert!(sharpened.get_pixel(3, 0).0[0] > 150);
+        //the far ends are not changed
+        assert_eq!(100, sharpened.get_pixel(0, 0).0[0]);
+    }
+}
+
+/// Stretch the luminosity of the image, so the darkest pixel becomes black and the brightest one white.
+///
+/// All channels are stretched by the same values, so the colors do not shift. Images with only one
+/// brightness level are not changed.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let stretched = auto_levels(img);
+/// ```
+fn auto_levels(img: RgbaImage) -> RgbaImage {
+    let (min, max) = img
+        .pixels()
+        .fold((f32::MAX, f32::MIN), |(min, max), pixel| {
+            let luminosity = luminosity(pixel.0[0] as f32, pixel.0[1] as f32, pixel.0[2] as f32);
+            (min.min(luminosity), max.max(luminosity))
+        });
+    log::debug!("Luminosity ranges from {min} to {max}");
+    if max <= min {
+        return img;
+    }
+    map_channels(img, |value| (value - min) * 255f32 / (max - min))
+}
+
+#[cfg(test)]
+mod test_auto_levels {
+    use super::*;
+
+    #[test]
+    fn range_is_stretched() {
+        let img = RgbaImage::from_fn(2, 1, |x, _| {
+            if x == 0 {
+                Rgba([50, 50, 50, 255])
+            } else {
+                Rgba([100, 100, 100, 255])
+            }
+        });
+        let stretched = auto_levels(img);
+        assert_eq!([0, 0, 0, 255], stretched.get_pixel(0, 0).0);
+        assert_eq!([255, 255, 255, 255], stretched.get_pixel(1, 0).0);
+    }
+
+    #[test]
+    fn single_level_is_unchanged() {
+        let img = RgbaImage::from_pixel(3, 3, Rgba([70, 80, 90, 255]));
+        assert_eq!(img.clone(), auto_levels(img));
+    }
+}
+
+/// Equalize the histogram of the luminosity, so each brightness level is used about equally often.
+///
+/// The difference between the old and the equalized luminosity is added to each channel of a pixel,
+/// which keeps the differences between the channels and therefore its color.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let equalized = equalize(img);
+/// ```
+fn equalize(img: RgbaImage) -> RgbaImage {
+    let levels = |pixel: &Rgba<u8>| -> usize {
+        to_channel(luminosity(
+            pixel.0[0] as f32,
+            pixel.0[1] as f32,
+            pixel.0[2] as f32,
+        )) as usize
+    };
+
+    let mut histogram = [0u32; 256];
+    for pixel in img.pixels() {
+        histogram[levels(pixel)] += 1;
+    }
+
+    //cumulative distribution of the levels, starting at the lowest used level
+    let mut cumulative = [0u32; 256];
+    let mut sum = 0;
+    for (level, count) in histogram.iter().enumerate() {
+        sum += count;
+        cumulative[level] = sum;
+    }
+    let lowest = cumulative
+        .iter()
+        .copied()
+        .find(|count| *count > 0)
+        .unwrap_or(0);
+    let total = img.width() * img.height();
+    if total <= lowest {
+        //all pixels have the same level
+        return img;
+    }
+
+    let mut equalized = img.clone();
+    for (pixel, source) in equalized.pixels_mut().zip(img.pixels()) {
+        let level = levels(source);
+        let target = (cumulative[level] - lowest) as f32 * 255f32 / (total - lowest) as f32;
+        let difference = target - level as f32;
+        for channel in 0..3 {
+            pixel.0[channel] = to_channel(source.0[channel] as f32 + difference);
+        }
+    }
+    equalized
+}
+
+#[cfg(test)]
+mod test_equalize {
+    use super::*;
+
+    #[test]
+    fn levels_are_spread() {
+        //three close gray levels are spread over the whole range
+        let img = RgbaImage::from_fn(3, 1, |x, _| {
+            let value = 100 + x as u8;
+            Rgba([value, value, value, 255])
+        });
+        let equalized = equalize(img);
+        assert_eq!([0, 0, 0, 255], equalized.get_pixel(0, 0).0);
+        assert_eq!([128, 128, 128, 255], equalized.get_pixel(1, 0).0);
+        assert_eq!([255, 255, 255, 255], equalized.get_pixel(2, 0).0);
+    }
+
+    #[test]
+    fn single_level_is_unchanged() {
+        let img = RgbaImage::from_pixel(2, 2, Rgba([10, 20, 30, 40]));
+        assert_eq!(img.clone(), equalize(img));
+    }
+}
@@ -0,0 +1,100 @@
+use image::{DynamicImage, GenericImageView, LumaA, Rgb, RgbImage};
+
+/// Composite the image onto the given background color.
+///
+/// Each pixel is blended with the background by its alpha value, so the resulting image is fully opaque.
+/// Images without an alpha channel are returned unchanged.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let filled = fill_background(img, Rgb([255, 255, 255]));
+/// ```
+pub(crate) fn fill_background(img: DynamicImage, background: Rgb<u8>) -> DynamicImage {
+    if !img.color().has_alpha() {
+        return img;
+    }
+
+    let filled = RgbImage::from_fn(img.width(), img.height(), |x, y| {
+        let [red, green, blue, alpha] = img.get_pixel(x, y).0;
+        let alpha = alpha as f32 / 255f32;
+        let blend = |value: u8, background: u8| {
+            (value as f32 * alpha + background as f32 * (1f32 - alpha)).round() as u8
+        };
+        Rgb([
+            blend(red, background.0[0]),
+            blend(green, background.0[1]),
+            blend(blue, background.0[2]),
+        ])
+    });
+    DynamicImage::ImageRgb8(filled)
+}
+
+#[cfg(test)]
+mod test_fill_background {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    #[test]
+    fn opaque_image_is_unchanged() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_pixel(2, 2, Rgb([1, 2, 3])));
+        assert_eq!(img.clone(), fill_background(img, Rgb([255, 255, 255])));
+    }
+
+    #[test]
+    fn transparent_pixels_are_blended() {
+        let img = DynamicImage::ImageRgba8(RgbaImage::from_fn(3, 1, |x, _| {
+            Rgba([255, 0, 0, [0, 51, 255][x as usize]])
+        }));
+        let filled = fill_background(img, Rgb([0, 0, 255])).to_rgb8();
+        assert_eq!([0, 0, 255], filled.get_pixel(0, 0).0);
+        assert_eq!([51, 0, 204], filled.get_pixel(1, 0).0);
+        assert_eq!([255, 0, 0], filled.get_pixel(2, 0).0);
+    }
+}
+
+/// Returns the grayscale image with the alpha channel of the source image.
+///
+/// Filters like the outline only work on the luminosity of the image, this keeps the transparency of the original.
+/// If the source image has no alpha channel, the image is returned unchanged.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let outline = with_alpha(outline, &img);
+/// ```
+pub(crate) fn with_alpha(img: DynamicImage, source: &DynamicImage) -> DynamicImage {
+    if !source.color().has_alpha() || img.dimensions() != source.dimensions() {
+        return img;
+    }
+
+    let gray = img.to_luma8();
+    let transparent = image::ImageBuffer::from_fn(gray.width(), gray.height(), |x, y| {
+        LumaA([gray.get_pixel(x, y).0[0], source.get_pixel(x, y).0[3]])
+    });
+    DynamicImage::ImageLumaA8(transparent)
+}
+
+#[cfg(test)]
+mod test_with_alpha {
+    use image::{GrayImage, Luma, Rgba, RgbaImage};
+
+    use super::*;
+
+    #[test]
+    fn alpha_is_copied() {
+        let img = DynamicImage::ImageLuma8(GrayImage::from_pixel(2, 1, Luma([200])));
+        let source = DynamicImage::ImageRgba8(RgbaImage::from_fn(2, 1, |x, _| {
+            Rgba([0, 0, 0, x as u8 * 255])
+        }));
+        let transparent = with_alpha(img, &source).to_rgba8();
+        assert_eq!([200, 200, 200, 0], transparent.get_pixel(0, 0).0);
+        assert_eq!([200, 200, 200, 255], transparent.get_pixel(1, 0).0);
+    }
+
+    #[test]
+    fn opaque_source_is_unchanged() {
+        let img = DynamicImage::ImageLuma8(GrayImage::from_pixel(2, 1, Luma([200])));
+        let source = DynamicImage::ImageRgb8(RgbImage::new(2, 1));
+        assert_eq!(img.clone(), with_alpha(img, &source));
+    }
+}
@@ -1,41 +1,73 @@
-use std::time::Instant;
+use std::{f32::consts::PI, time::Instant};
 
-use image::{DynamicImage, GenericImageView, GrayImage, ImageBuffer};
+use image::{imageops, DynamicImage, GenericImageView, GrayImage, ImageBuffer, Luma};
+
+use crate::config::Config;
+
+//adjustments of the image before converting it
+mod adjust;
+pub(crate) use adjust::adjust_image;
+
+//transparency of the image
+mod alpha;
+pub(crate) use alpha::{fill_background, with_alpha};
+
+//orientation and rotation of the image
+mod transform;
+pub(crate) use transform::{orient, rotate};
+
+/// Orientation of the gradient at each pixel, in radians from 0 to π.
+///
+/// The orientation is perpendicular to the edge, so a vertical edge has an orientation of 0.
+pub(crate) type Angles = ImageBuffer<Luma<f32>, Vec<f32>>;
 
 /// Filter an image using a technique similar to canny edge detection.
 ///
-/// The image will first be blurred and then as grayscale converted using the sobel operators.
-/// It will not use non-maximum suppression, since this would make the lines thinner, which is something that is not actually wanted,
-/// since the ascii image will look much better if it is based off thicker lines.
+/// The image will first be blurred and then as grayscale converted using the sobel operators. With an `outline_sigma` in the config,
+/// the image is blurred with a gaussian kernel of that sigma, otherwise a small 3x3 kernel is used.
+/// By default it will not use non-maximum suppression, since this would make the lines thinner, which is something that is usually not wanted,
+/// since the ascii image will look much better if it is based off thicker lines. It can be enabled using `non_max_suppression`.
 ///
-/// When `hysteresis` is set to true, it will additionally use the hysteresis method to improve the outlines of the image.
+/// When `hysteresis` is set to true, it will additionally use the hysteresis method with the `outline_low` and `outline_high`
+/// thresholds to improve the outlines of the image.
 /// This will result in thinner lines, with less imperfections, but at the cost of less good looking ascii chars,
 /// since it will mostly consist of dots.
 ///
+/// Besides the outline, the orientation of the gradient at each pixel is returned, see [`Angles`].
+///
 /// # Example
 /// ```compile_fail, compile will fail, this is an internal example
-///  let outlined_image = edge_detection_filter(img, 4);
+///  let (outlined_image, angles) = edge_detection_filter(img, &config);
 /// ```
-pub fn edge_detection_filter(img: DynamicImage, hysteresis: bool) -> DynamicImage {
-    //blur
-    let blurred_img = blur(img, 6.4f32);
+pub fn edge_detection_filter(img: DynamicImage, config: &Config) -> (DynamicImage, Angles) {
+    //blur, the small blur is used by default, since its outline is the one the characters are chosen for
+    let blurred_img = match config.outline_sigma {
+        Some(sigma) => blur(img, sigma),
+        None => blur_3x3(img, 6.4f32),
+    };
     //apply sobel
-    let sobel_img = apply_sobel_kernel(blurred_img);
+    let (sobel_img, angles) = apply_sobel_kernel(blurred_img, config.non_max_suppression);
     //double threshold and hysteresis
-    if hysteresis {
-        edge_tracking(sobel_img)
+    if config.hysteresis {
+        (
+            edge_tracking(sobel_img, config.outline_low, config.outline_high),
+            angles,
+        )
     } else {
-        sobel_img
+        (sobel_img, angles)
     }
 }
 
 ///Blur the given image using an gaussian blur, based on the given sigma.
 ///
+/// The blur is applied horizontally and vertically one after another, which is the same as a two dimensional
+/// kernel, but a lot faster for larger sigmas.
+///
 /// This returns a new (blurred) image.
 ///
 /// # Examples
 /// ```compile_fail, compile will fail, this is an internal example
-/// let blurred = blur(image, 1.4f32, 4)
+/// let blurred = blur(image, 1.4f32)
 /// ```
 fn blur(img: DynamicImage, sigma: f32) -> DynamicImage {
     log::info!("Blurring image");
@@ -45,54 +77,48 @@ fn blur(img: DynamicImage, sigma: f32) -> DynamicImage {
 
     log::debug!("Creating gauss kernel");
     let kernel = create_gauss_kernel(sigma);
+    log::trace!("Kernel length: {}", kernel.len());
 
     let offset = (kernel.len() / 2) as u32;
 
     let (width, height) = img.dimensions();
-
-    //create empty target img
-    log::debug!("Creating target blur image");
-    let mut destination_img = ImageBuffer::new(width, height);
-
-    //use iter to iter over every pixel and create a new img
-    img.pixels().for_each(|(x, y, _)| {
-        //kernel values for rgb
-        let mut kernel_values_red = 0f32;
-        let mut kernel_values_green = 0f32;
-        let mut kernel_values_blue = 0f32;
-
-        //iterate through the kernel for this pixel
-        for (k_y, row) in kernel.iter().enumerate() {
-            for (k_x, kernel_value) in row.iter().enumerate() {
-                // for k_x in 0..kernel_len {
-                //get pixel pos for kernel
-                let pixel_pos_x = (x + k_x as u32).saturating_sub(offset).clamp(0, width - 1);
-                let pixel_pos_y = (y + k_y as u32).saturating_sub(offset).clamp(0, height - 1);
-
-                //check if pixel is in img, since the kernel will overlap to outside pixels, if not ignored it
-                if destination_img.in_bounds(pixel_pos_x, pixel_pos_y) {
-                    //get the current pixel
-                    let pixel = img.get_pixel(pixel_pos_x, pixel_pos_y);
-                    //add rgb values
-                    kernel_values_red += pixel.0[0] as f32 * kernel_value;
-                    kernel_values_green += pixel.0[1] as f32 * kernel_value;
-                    kernel_values_blue += pixel.0[2] as f32 * kernel_value;
-                }
+    let source_img = img.to_rgb8();
+
+    //blur the rows first, the values are kept as floats, so they are not rounded twice
+    log::debug!("Blurring rows");
+    let rows = crate::parallel::map_indices((width * height) as usize, |index| {
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        let mut kernel_values = [0f32; 3];
+        for (k_x, kernel_value) in kernel.iter().enumerate() {
+            //pixels outside of the image are clamped to the nearest one inside of it
+            let pixel_pos_x = (x + k_x as u32).saturating_sub(offset).clamp(0, width - 1);
+            let pixel = source_img.get_pixel(pixel_pos_x, y);
+            for (value, channel) in kernel_values.iter_mut().zip(pixel.0) {
+                *value += channel as f32 * kernel_value;
             }
         }
+        kernel_values
+    });
 
-        //add filtered/blurred pixel to new img
-        destination_img.put_pixel(
-            x,
-            y,
-            image::Rgb([
-                (kernel_values_red as u8),
-                (kernel_values_green as u8),
-                (kernel_values_blue as u8),
-            ]),
-        );
+    log::debug!("Blurring columns");
+    let columns = crate::parallel::map_indices((width * height) as usize, |index| {
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        let mut kernel_values = [0f32; 3];
+        for (k_y, kernel_value) in kernel.iter().enumerate() {
+            let pixel_pos_y = (y + k_y as u32).saturating_sub(offset).clamp(0, height - 1);
+            let row_value = rows[(pixel_pos_y * width + x) as usize];
+            for (value, channel) in kernel_values.iter_mut().zip(row_value) {
+                *value += channel * kernel_value;
+            }
+        }
+        image::Rgb(kernel_values.map(|value| value.round() as u8))
     });
 
+    //create target img
+    log::debug!("Creating target blur image");
+    let destination_img =
+        ImageBuffer::from_fn(width, height, |x, y| columns[(y * width + x) as usize]);
+
     log::info!(
         "Successfully blurred image in {:3} ms",
         now.elapsed().as_millis()
@@ -125,6 +151,164 @@ mod test_blur {
         let blur = blur(img.clone(), 1.4f32);
         assert_eq!(img, blur);
     }
+
+    #[test]
+    fn img_middle_white() {
+        //
+        // █
+        //
+        let img = DynamicImage::ImageRgb8(ImageBuffer::from_fn(5, 5, |x, y| {
+            if y == 2 && x == 2 {
+                image::Rgb([255, 255, 255])
+            } else {
+                image::Rgb([0, 0, 0])
+            }
+        }));
+        let blur = blur(img.clone(), 0.5f32);
+        assert_ne!(img, blur);
+        //the kernel is about [0, 0.106, 0.787, 0.106, 0] for a sigma of 0.5, so the corners of the 3x3 area are dark
+        let result = DynamicImage::ImageRgb8(ImageBuffer::from_fn(5, 5, |x, y| {
+            match (x.abs_diff(2), y.abs_diff(2)) {
+                (0, 0) => image::Rgb([158, 158, 158]),
+                (0, 1) | (1, 0) => image::Rgb([21, 21, 21]),
+                (1, 1) => image::Rgb([3, 3, 3]),
+                _ => image::Rgb([0, 0, 0]),
+            }
+        }));
+        assert_eq!(result, blur);
+    }
+}
+
+///Creates a one dimensional gaussian kernel based on the given sigma.
+///
+/// The kernel covers three times the sigma in each direction, which contains nearly all of the gaussian curve.
+/// Its values are normalized, so they sum up to one.
+///
+/// # Panics
+/// This will panic if the given `sigma` is smaller or equal to zero.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let kernel = create_gauss_kernel(1.4f32);
+/// ```
+fn create_gauss_kernel(sigma: f32) -> Vec<f32> {
+    if sigma <= 0f32 {
+        panic!("The given sigma {} was smaller or equal to zero", sigma)
+    }
+    let radius = (3f32 * sigma).ceil() as isize;
+
+    let mut kernel: Vec<f32> = (-radius..=radius)
+        .map(|x| f32::exp(-((x * x) as f32) / (2f32 * sigma * sigma)))
+        .collect();
+
+    let sum: f32 = kernel.iter().sum();
+    for value in kernel.iter_mut() {
+        *value /= sum;
+    }
+
+    kernel
+}
+
+#[cfg(test)]
+mod test_create_gauss_kernel {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn sigma_zero_panics() {
+        create_gauss_kernel(0f32);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sigma_minus_one_panics() {
+        create_gauss_kernel(-1f32);
+    }
+
+    #[test]
+    fn size_depends_on_sigma() {
+        assert_eq!(3, create_gauss_kernel(0.3f32).len());
+        assert_eq!(11, create_gauss_kernel(1.4f32).len());
+        assert_eq!(41, create_gauss_kernel(6.4f32).len());
+    }
+
+    #[test]
+    fn sigma_1_4() {
+        let kernel = create_gauss_kernel(1.4f32);
+        assert!((1f32 - kernel.iter().sum::<f32>()).abs() < 1e-6);
+        //the kernel is symmetric and the highest value is in the middle
+        assert_eq!(kernel[4], kernel[6]);
+        assert!(kernel[5] > kernel[4]);
+        assert!((0.285 - kernel[5]).abs() < 1e-3);
+    }
+}
+
+///Blur the given image using an gaussian blur with a 3x3 kernel, based on the given sigma.
+///
+/// Larger sigmas only make the kernel more even, since its size is fixed. The blurred values are truncated.
+///
+/// This returns a new (blurred) image.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let blurred = blur_3x3(image, 6.4f32)
+/// ```
+fn blur_3x3(img: DynamicImage, sigma: f32) -> DynamicImage {
+    log::info!("Blurring image");
+    //measure timing for this step
+    log::trace!("Started time tracking for blurring");
+    let now = Instant::now();
+
+    log::debug!("Creating gauss kernel");
+    let kernel = create_gauss_kernel_3x3(sigma);
+
+    let offset = (kernel.len() / 2) as u32;
+
+    let (width, height) = img.dimensions();
+    let source_img = img.to_rgb8();
+
+    let pixels = crate::parallel::map_indices((width * height) as usize, |index| {
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        //kernel values for rgb
+        let mut kernel_values = [0f32; 3];
+
+        //iterate through the kernel for this pixel, pixels outside of the image are clamped to the nearest one inside of it
+        for (k_y, row) in kernel.iter().enumerate() {
+            for (k_x, kernel_value) in row.iter().enumerate() {
+                let pixel_pos_x = (x + k_x as u32).saturating_sub(offset).clamp(0, width - 1);
+                let pixel_pos_y = (y + k_y as u32).saturating_sub(offset).clamp(0, height - 1);
+                let pixel = source_img.get_pixel(pixel_pos_x, pixel_pos_y);
+                for (value, channel) in kernel_values.iter_mut().zip(pixel.0) {
+                    *value += channel as f32 * kernel_value;
+                }
+            }
+        }
+        image::Rgb(kernel_values.map(|value| value as u8))
+    });
+
+    //create target img
+    log::debug!("Creating target blur image");
+    let destination_img =
+        ImageBuffer::from_fn(width, height, |x, y| pixels[(y * width + x) as usize]);
+
+    log::info!(
+        "Successfully blurred image in {:3} ms",
+        now.elapsed().as_millis()
+    );
+    DynamicImage::ImageRgb8(destination_img)
+}
+
+#[cfg(test)]
+mod test_blur_3x3 {
+    use super::*;
+
+    #[test]
+    fn black_img_remains_black() {
+        let img = DynamicImage::ImageRgb8(ImageBuffer::new(3, 3));
+        let blur = blur_3x3(img.clone(), 1.4f32);
+        assert_eq!(img, blur);
+    }
+
     #[test]
     fn img_middle_white() {
         //
@@ -137,7 +321,7 @@ mod test_blur {
                 image::Rgb([0, 0, 0])
             }
         }));
-        let blur = blur(img.clone(), 1.4f32);
+        let blur = blur_3x3(img.clone(), 1.4f32);
         assert_ne!(img, blur);
         let result = DynamicImage::ImageRgb8(ImageBuffer::from_fn(3, 3, |x, y| {
             if y == 1 && x == 1 {
@@ -152,7 +336,7 @@ mod test_blur {
     }
 }
 
-///Creates a gaussian kernel based on the given sigma.
+///Creates a 3x3 gaussian kernel based on the given sigma.
 ///
 /// This is based on the c++ implementation on <https://www.geeksforgeeks.org/gaussian-filter-generation-c/>
 ///
@@ -161,9 +345,9 @@ mod test_blur {
 ///
 /// # Examples
 /// ```compile_fail, compile will fail, this is an internal example
-/// let kernel = create_gauss_kernel(1.4f32);
+/// let kernel = create_gauss_kernel_3x3(1.4f32);
 /// ```
-fn create_gauss_kernel(sigma: f32) -> [[f32; 3]; 3] {
+fn create_gauss_kernel_3x3(sigma: f32) -> [[f32; 3]; 3] {
     if sigma <= 0f32 {
         panic!("The given sigma {} was smaller or equal to zero", sigma)
     }
@@ -177,7 +361,7 @@ fn create_gauss_kernel(sigma: f32) -> [[f32; 3]; 3] {
     for x in -1..=1isize {
         for y in -1..=1isize {
             r = ((x * x + y * y) as f32).sqrt();
-            let value = (f32::exp(-(r * r) / s)) / (std::f32::consts::PI * s);
+            let value = (f32::exp(-(r * r) / s)) / (PI * s);
             kernel[(x + 1) as usize][(y + 1) as usize] = value;
             sum += value;
         }
@@ -193,19 +377,13 @@ fn create_gauss_kernel(sigma: f32) -> [[f32; 3]; 3] {
 }
 
 #[cfg(test)]
-mod test_create_gauss_kernel {
+mod test_create_gauss_kernel_3x3 {
     use super::*;
 
     #[test]
     #[should_panic]
     fn sigma_zero_panics() {
-        create_gauss_kernel(0f32);
-    }
-
-    #[test]
-    #[should_panic]
-    fn sigma_minus_one_panics() {
-        create_gauss_kernel(-1f32);
+        create_gauss_kernel_3x3(0f32);
     }
 
     #[test]
@@ -216,20 +394,24 @@ mod test_create_gauss_kernel {
                 [0.119190335, 0.15382625, 0.119190335],
                 [0.09235313, 0.119190335, 0.09235313]
             ],
-            create_gauss_kernel(1.4f32)
+            create_gauss_kernel_3x3(1.4f32)
         )
     }
 }
 
 /// Detect edges in an image by using the sobel operators.
 ///
-/// This returns a new, grayscale image with only the edges in white visible.
+/// This returns a new, grayscale image with only the edges in white visible,
+/// as well as the orientation of the gradient at each pixel.
+///
+/// When `non_max_suppression` is set, only the pixels with the strongest gradient along its orientation are kept,
+/// which thins the edges to a width of one pixel.
 ///
 /// # Examples
 /// ```compile_fail, compile will fail, this is an internal example
-/// let outline = edge_detection(image, 4)
+/// let (outline, angles) = apply_sobel_kernel(image, false)
 /// ```
-fn apply_sobel_kernel(img: DynamicImage) -> DynamicImage {
+fn apply_sobel_kernel(img: DynamicImage, non_max_suppression: bool) -> (DynamicImage, Angles) {
     log::info!("Creating outline image");
     //create stop watch
     log::trace!("Started time tracking for sobel");
@@ -254,11 +436,9 @@ fn apply_sobel_kernel(img: DynamicImage) -> DynamicImage {
 
     let (width, height) = img.dimensions();
 
-    //create empty target img
-    log::debug!("Creating target sobel image");
-    let mut destination_img = ImageBuffer::new(width, height);
-
-    img.pixels().for_each(|(x, y, _)| {
+    //the magnitudes are kept as floats, so the suppression can compare them before they are clamped
+    let gradients = crate::parallel::map_indices((width * height) as usize, |index| {
+        let (x, y) = (index as u32 % width, index as u32 / width);
         //kernel values for rgb
         let mut kernel_values_x = 0f32;
         let mut kernel_values_y = 0f32;
@@ -280,26 +460,72 @@ fn apply_sobel_kernel(img: DynamicImage) -> DynamicImage {
             }
         }
 
-        //usually in the canny edge detection algorithm, a non-maximum suppression would now be performed,
-        //to have thinner lines. In this case this is not needed, since thicker lines will produce a more clearly ascii like image.
-
-        //add filtered pixel to new img
-        destination_img.put_pixel(
-            x,
-            y,
-            image::Luma([
-                (((kernel_values_x * kernel_values_x + kernel_values_y * kernel_values_y).sqrt())
-                    .round() as u8)
-                    .saturating_mul(3),
-            ]),
-        );
+        let magnitude =
+            (kernel_values_x * kernel_values_x + kernel_values_y * kernel_values_y).sqrt();
+
+        //the direction of the gradient does not matter for the edge, so it is turned around to be between 0 and π
+        let (gradient_x, gradient_y) =
+            if kernel_values_y < 0f32 || (kernel_values_y == 0f32 && kernel_values_x < 0f32) {
+                (-kernel_values_x, -kernel_values_y)
+            } else {
+                (kernel_values_x, kernel_values_y)
+            };
+        (magnitude, gradient_y.atan2(gradient_x))
+    });
+    let (magnitudes, angles): (Vec<f32>, Vec<f32>) = gradients.into_iter().unzip();
+    //the buffer has exactly one angle for each pixel
+    let angles = Angles::from_raw(width, height, angles).unwrap();
+
+    //usually in the canny edge detection algorithm, a non-maximum suppression is performed, to have thinner lines.
+    //by default this is not done, since thicker lines will produce a more clearly ascii like image.
+    log::debug!("Creating target sobel image");
+    let edges = crate::parallel::map_indices((width * height) as usize, |index| {
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        if non_max_suppression && !is_local_maximum(&magnitudes, &angles, x, y) {
+            return 0u8;
+        }
+        (magnitudes[index].round() as u8).saturating_mul(3)
     });
+    //the buffer has exactly one value for each pixel
+    let destination_img = GrayImage::from_raw(width, height, edges).unwrap();
 
     log::info!(
         "Successfully outlined image in {:3} ms",
         now.elapsed().as_millis()
     );
-    DynamicImage::ImageLuma8(destination_img)
+    (DynamicImage::ImageLuma8(destination_img), angles)
+}
+
+/// Check if the magnitude of a pixel is the highest compared to its neighbours along the orientation of the gradient.
+///
+/// Of equally strong pixels, only the first one along the orientation is kept, so thick edges with the same
+/// magnitude are thinned as well.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let keep = is_local_maximum(&magnitudes, &angles, 4, 2);
+/// ```
+fn is_local_maximum(magnitudes: &[f32], angles: &Angles, x: u32, y: u32) -> bool {
+    let (width, height) = angles.dimensions();
+    //0 is horizontal, 1 diagonal to the bottom right, 2 vertical and 3 diagonal to the bottom left
+    let (step_x, step_y) = match (angles.get_pixel(x, y).0[0] / (PI / 4f32)).round() as usize % 4 {
+        0 => (1, 0),
+        1 => (1, 1),
+        2 => (0, 1),
+        _ => (-1, 1),
+    };
+    //neighbours outside of the image have no edge
+    let neighbour = |direction: i64| {
+        let neighbour_x = x as i64 + direction * step_x;
+        let neighbour_y = y as i64 + direction * step_y;
+        if (0..width as i64).contains(&neighbour_x) && (0..height as i64).contains(&neighbour_y) {
+            magnitudes[(neighbour_y * width as i64 + neighbour_x) as usize]
+        } else {
+            0f32
+        }
+    };
+    let magnitude = magnitudes[(y * width + x) as usize];
+    magnitude > neighbour(-1) && magnitude >= neighbour(1)
 }
 
 #[cfg(test)]
@@ -310,7 +536,7 @@ mod test_sobel {
     fn no_edge() {
         //create empty image with no edge
         let img = DynamicImage::ImageLuma8(ImageBuffer::new(3, 3));
-        let edge_img = apply_sobel_kernel(img.clone());
+        let (edge_img, _) = apply_sobel_kernel(img.clone(), false);
         assert_eq!(img, edge_img);
     }
 
@@ -327,8 +553,10 @@ mod test_sobel {
                 image::Luma([255u8])
             }
         }));
-        let edge_img = apply_sobel_kernel(img.clone());
+        let (edge_img, angles) = apply_sobel_kernel(img.clone(), false);
         assert_eq!(img, edge_img);
+        //the gradient is horizontal
+        assert!(angles.get_pixel(0, 1).0[0].abs() < 1e-6);
     }
 
     #[test]
@@ -344,8 +572,242 @@ mod test_sobel {
                 image::Luma([255u8])
             }
         }));
-        let edge_img = apply_sobel_kernel(img.clone());
+        let (edge_img, angles) = apply_sobel_kernel(img.clone(), false);
         assert_eq!(img, edge_img);
+        //the gradient is vertical
+        assert!((PI / 2f32 - angles.get_pixel(1, 0).0[0]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn edge_diagonal() {
+        //the top left corner is bright, so the edge goes from the bottom left to the top right
+        let img = DynamicImage::ImageLuma8(ImageBuffer::from_fn(3, 3, |x, y| {
+            if x + y < 2 {
+                image::Luma([255u8])
+            } else {
+                image::Luma([0u8])
+            }
+        }));
+        let (_, angles) = apply_sobel_kernel(img, false);
+        assert!((PI / 4f32 - angles.get_pixel(1, 1).0[0]).abs() < 1e-6);
+    }
+
+    /// Returns the columns of the first row, which contain an edge.
+    fn edge_columns(img: &DynamicImage) -> Vec<u32> {
+        (0..img.width())
+            .filter(|x| img.get_pixel(*x, 0).0[0] > 0)
+            .collect()
+    }
+
+    #[test]
+    fn soft_edge_is_thick() {
+        //a vertical edge, which slowly gets brighter from left to right
+        let img = DynamicImage::ImageLuma8(ImageBuffer::from_fn(9, 3, |x, _| {
+            image::Luma([(x.saturating_sub(2) * 64).min(255) as u8])
+        }));
+        let (edge_img, _) = apply_sobel_kernel(img, false);
+        assert_eq!(vec![2, 3, 4, 5, 6], edge_columns(&edge_img));
+    }
+
+    #[test]
+    fn non_max_suppression_thins_edge() {
+        let img = DynamicImage::ImageLuma8(ImageBuffer::from_fn(9, 3, |x, _| {
+            image::Luma([(x.saturating_sub(2) * 64).min(255) as u8])
+        }));
+        let (edge_img, _) = apply_sobel_kernel(img, true);
+        //the two strongest columns are equal, so only the first one is kept
+        assert_eq!(vec![3], edge_columns(&edge_img));
+    }
+
+    #[test]
+    fn non_max_suppression_keeps_hard_edge() {
+        let img = DynamicImage::ImageLuma8(ImageBuffer::from_fn(3, 3, |x, _| {
+            if x == 1 {
+                image::Luma([0u8])
+            } else {
+                image::Luma([255u8])
+            }
+        }));
+        let (edge_img, _) = apply_sobel_kernel(img, true);
+        //the edges on both sides of the dark line are kept, even at the border of the image
+        assert_eq!(vec![0, 2], edge_columns(&edge_img));
+    }
+}
+
+/// Flip the angles horizontally or vertically, which mirrors their orientations as well.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let flipped = flip_angles(&angles, true);
+/// ```
+pub(crate) fn flip_angles(angles: &Angles, horizontal: bool) -> Angles {
+    let mut flipped = if horizontal {
+        imageops::flip_horizontal(angles)
+    } else {
+        imageops::flip_vertical(angles)
+    };
+    for angle in flipped.pixels_mut() {
+        //vertical edges stay at 0, instead of becoming π
+        if angle.0[0] > 0f32 {
+            angle.0[0] = PI - angle.0[0];
+        }
+    }
+    flipped
+}
+
+#[cfg(test)]
+mod test_flip_angles {
+    use super::*;
+
+    #[test]
+    fn horizontal() {
+        //a vertical and a rising edge next to each other
+        let angles = Angles::from_fn(2, 1, |x, _| Luma([x as f32 * PI / 4f32]));
+        let flipped = flip_angles(&angles, true);
+        assert!((flipped.get_pixel(0, 0).0[0] - 3f32 * PI / 4f32).abs() < 1e-6);
+        assert_eq!(0f32, flipped.get_pixel(1, 0).0[0]);
+    }
+
+    #[test]
+    fn vertical() {
+        let angles = Angles::from_fn(2, 1, |x, _| Luma([x as f32 * PI / 4f32]));
+        let flipped = flip_angles(&angles, false);
+        assert_eq!(0f32, flipped.get_pixel(0, 0).0[0]);
+        assert!((flipped.get_pixel(1, 0).0[0] - 3f32 * PI / 4f32).abs() < 1e-6);
+    }
+}
+
+/// Resize the angles to the given dimensions, using the nearest angle for each pixel.
+///
+/// The angles can not be interpolated, since they wrap around at π.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let resized = resize_angles(&angles, 80, 160);
+/// ```
+pub(crate) fn resize_angles(angles: &Angles, width: u32, height: u32) -> Angles {
+    Angles::from_fn(width, height, |x, y| {
+        *angles.get_pixel(
+            (x as u64 * angles.width() as u64 / width as u64) as u32,
+            (y as u64 * angles.height() as u64 / height as u64) as u32,
+        )
+    })
+}
+
+#[cfg(test)]
+mod test_resize_angles {
+    use super::*;
+
+    #[test]
+    fn angles_are_kept() {
+        //angles above 1 must not be clamped
+        let angles = Angles::from_fn(2, 2, |x, _| Luma([if x == 0 { 0f32 } else { PI / 2f32 }]));
+        let resized = resize_angles(&angles, 4, 1);
+        assert_eq!(vec![0f32, 0f32, PI / 2f32, PI / 2f32], resized.into_raw());
+    }
+}
+
+/// Minimum brightness of a pixel in the outline, for it to be counted as part of an edge.
+const EDGE_THRESHOLD: f32 = u8::MAX as f32 * 0.5;
+
+/// Returns the character matching the direction of the edge in a tile of the outline.
+///
+/// Each pixel of the tile, which is part of an edge, votes for the nearest of the four directions of its orientation.
+/// A tile only contains an edge, if it has at least as many of these pixels as a line through the tile, otherwise [`None`] is returned.
+/// Horizontal edges in the lower third of the tile use `_` instead of `-`.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let (outline, angles) = edge_detection_filter(img, &config);
+/// let character = edge_char(&outline, &angles, 0, 0, 8, 16);
+/// ```
+pub(crate) fn edge_char(
+    img: &DynamicImage,
+    angles: &Angles,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Option<char> {
+    let mut votes = [0u32; 4];
+    //sum of the rows of the horizontal votes, to find out where the edge is in the tile
+    let mut horizontal_rows = 0u32;
+    for p_y in y..y + height {
+        for p_x in x..x + width {
+            let pixel = img.get_pixel(p_x, p_y);
+            if crate::pixel::luminosity(pixel.0[0], pixel.0[1], pixel.0[2]) < EDGE_THRESHOLD {
+                continue;
+            }
+            //0 is vertical, 1 rising, 2 horizontal and 3 falling, orientations close to π are vertical again
+            let direction = (angles.get_pixel(p_x, p_y).0[0] / (PI / 4f32)).round() as usize % 4;
+            votes[direction] += 1;
+            if direction == 2 {
+                horizontal_rows += p_y - y;
+            }
+        }
+    }
+
+    if votes.iter().sum::<u32>() < width.min(height) {
+        return None;
+    }
+    let (direction, count) = votes.iter().enumerate().max_by_key(|(_, count)| **count)?;
+    Some(match direction {
+        0 => '|',
+        1 => '/',
+        2 if horizontal_rows / count >= height * 2 / 3 => '_',
+        2 => '-',
+        _ => '\\',
+    })
+}
+
+#[cfg(test)]
+mod test_edge_char {
+    use super::*;
+
+    /// Returns the outline and angles of a tile, where the given pixels are part of an edge with the given orientation.
+    fn tile(edge: impl Fn(u32, u32) -> bool, angle: f32) -> (DynamicImage, Angles) {
+        let img = GrayImage::from_fn(8, 16, |x, y| Luma([if edge(x, y) { 255 } else { 0 }]));
+        (
+            DynamicImage::ImageLuma8(img),
+            Angles::from_pixel(8, 16, Luma([angle])),
+        )
+    }
+
+    #[test]
+    fn no_edge() {
+        let (img, angles) = tile(|_, _| false, 0f32);
+        assert_eq!(None, edge_char(&img, &angles, 0, 0, 8, 16));
+    }
+
+    #[test]
+    fn too_few_edge_pixels() {
+        let (img, angles) = tile(|x, y| x == 0 && y < 4, 0f32);
+        assert_eq!(None, edge_char(&img, &angles, 0, 0, 8, 16));
+    }
+
+    #[test]
+    fn vertical_edge() {
+        let (img, angles) = tile(|x, _| x == 4, 0f32);
+        assert_eq!(Some('|'), edge_char(&img, &angles, 0, 0, 8, 16));
+        //orientations close to π are vertical as well
+        let (img, angles) = tile(|x, _| x == 4, PI * 0.95);
+        assert_eq!(Some('|'), edge_char(&img, &angles, 0, 0, 8, 16));
+    }
+
+    #[test]
+    fn diagonal_edges() {
+        let (img, angles) = tile(|x, y| x == 7 - y / 2, PI / 4f32);
+        assert_eq!(Some('/'), edge_char(&img, &angles, 0, 0, 8, 16));
+        let (img, angles) = tile(|x, y| x == y / 2, 3f32 * PI / 4f32);
+        assert_eq!(Some('\\'), edge_char(&img, &angles, 0, 0, 8, 16));
+    }
+
+    #[test]
+    fn horizontal_edges() {
+        let (img, angles) = tile(|_, y| y == 8, PI / 2f32);
+        assert_eq!(Some('-'), edge_char(&img, &angles, 0, 0, 8, 16));
+        let (img, angles) = tile(|_, y| y == 14, PI / 2f32);
+        assert_eq!(Some('_'), edge_char(&img, &angles, 0, 0, 8, 16));
     }
 }
 
@@ -355,31 +817,32 @@ mod test_sobel {
 /// The resulting image will have better edge detection, at the cost of a less good looking ascii image.
 /// This method uses two thresholds to determine between strong, weak and irrelevant pixels.weak ones can be converted into strong ones,
 /// if at least one neighboring pixel is strong. Every non-strong pixel will be removed.
+/// The thresholds are fractions of the maximum brightness, ranging from 0 to 1.
 ///
 /// # Examples
 /// ```compile_fail, compile will fail, this is an internal example
-/// let hysteresis_img = edge_tracking(img);
+/// let hysteresis_img = edge_tracking(img, 0.3, 0.5);
 /// ```
-fn edge_tracking(img: DynamicImage) -> DynamicImage {
+fn edge_tracking(img: DynamicImage, low: f32, high: f32) -> DynamicImage {
     //start tracking to for this step
     log::trace!("Started time tracking for hysteresis");
     let now = Instant::now();
 
-    log::debug!("Creating target hysteresis image");
-    let mut destination_img: GrayImage = ImageBuffer::new(img.width(), img.height());
-
-    let upper_threshold = u8::MAX as f32 * 0.5;
+    let upper_threshold = u8::MAX as f32 * high;
     log::debug!("Upper threshold: {}", upper_threshold);
-    let lower_threshold = u8::MAX as f32 * 0.3;
+    let lower_threshold = u8::MAX as f32 * low;
     log::debug!("Lower threshold: {}", lower_threshold);
 
-    img.pixels().for_each(|(x, y, pixel)| {
+    let (width, height) = img.dimensions();
+    let edges = crate::parallel::map_indices((width * height) as usize, |index| {
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        let pixel = img.get_pixel(x, y);
         let grayscale_pixel = crate::pixel::luminosity(pixel.0[0], pixel.0[1], pixel.0[2]);
 
         //check if pixel is at least weak or strong
         if grayscale_pixel >= upper_threshold {
-            //pixel is already strong, set to completely white and continue continue loop
-            destination_img.put_pixel(x, y, image::Luma([255]));
+            //pixel is already strong, set to completely white
+            255u8
         } else if grayscale_pixel >= lower_threshold {
             //check if an adjacent pixel is strong
             let mut strong = false;
@@ -407,17 +870,21 @@ fn edge_tracking(img: DynamicImage) -> DynamicImage {
 
             if strong {
                 //pixel has strong adjacent ones, make strong as well
-                destination_img.put_pixel(x, y, image::Luma([255]))
+                255u8
             } else {
                 //no strong pixels around, pixel is irrelevant, remove
-                destination_img.put_pixel(x, y, image::Luma([0]))
+                0u8
             }
         } else {
             //pixel is irrelevant, remove
-            destination_img.put_pixel(x, y, image::Luma([0]))
+            0u8
         }
     });
 
+    log::debug!("Creating target hysteresis image");
+    //the buffer has exactly one value for each pixel
+    let destination_img = GrayImage::from_raw(width, height, edges).unwrap();
+
     log::info!(
         "Successfully applied hysteresis to target image in {:3} ms",
         now.elapsed().as_millis()
@@ -432,7 +899,7 @@ mod test_edge_detection {
     #[test]
     fn no_strong_results_in_black_img() {
         let img = DynamicImage::ImageLuma8(ImageBuffer::new(3, 3));
-        let result = edge_tracking(img.clone());
+        let result = edge_tracking(img.clone(), 0.3, 0.5);
         assert_eq!(img, result);
     }
 
@@ -446,7 +913,7 @@ mod test_edge_detection {
                 image::Luma([0u8])
             }
         }));
-        let result = edge_tracking(img.clone());
+        let result = edge_tracking(img.clone(), 0.3, 0.5);
         assert_eq!(img, result);
     }
 
@@ -460,7 +927,7 @@ mod test_edge_detection {
                 image::Luma([0u8])
             }
         }));
-        let result = edge_tracking(img);
+        let result = edge_tracking(img, 0.3, 0.5);
         //result is equal to a black image
         assert_eq!(DynamicImage::ImageLuma8(ImageBuffer::new(3, 3)), result);
     }
@@ -475,7 +942,7 @@ mod test_edge_detection {
                 image::Luma([0u8])
             }
         }));
-        let result = edge_tracking(img);
+        let result = edge_tracking(img, 0.3, 0.5);
         //result is equal to a black image
         assert_eq!(DynamicImage::ImageLuma8(ImageBuffer::new(3, 3)), result);
     }
@@ -502,7 +969,7 @@ mod test_edge_detection {
                 image::Luma([0u8])
             }
         }));
-        let result = edge_tracking(img);
+        let result = edge_tracking(img, 0.3, 0.5);
         //result is equal to a black image
         assert_eq!(desired_result, result);
     }
@@ -526,7 +993,7 @@ mod test_edge_detection {
                 image::Luma([0u8])
             }
         }));
-        let result = edge_tracking(img);
+        let result = edge_tracking(img, 0.3, 0.5);
         //result is equal to a black image
         assert_eq!(desired_result, result);
     }
@@ -0,0 +1,191 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use crate::config::Orientation;
+
+/// Correct the orientation of the image, so it is shown upright.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let upright = orient(img, Orientation::Rotate90);
+/// ```
+pub(crate) fn orient(img: DynamicImage, orientation: Orientation) -> DynamicImage {
+    match orientation {
+        Orientation::Normal => img,
+        Orientation::FlipHorizontal => img.fliph(),
+        Orientation::Rotate180 => img.rotate180(),
+        Orientation::FlipVertical => img.flipv(),
+        //flipping along a diagonal is the same as rotating and flipping it
+        Orientation::Transpose => img.rotate90().fliph(),
+        Orientation::Rotate90 => img.rotate90(),
+        Orientation::Transverse => img.rotate270().fliph(),
+        Orientation::Rotate270 => img.rotate270(),
+    }
+}
+
+#[cfg(test)]
+mod test_orient {
+    use image::{GenericImageView, ImageBuffer, Luma};
+
+    use super::*;
+
+    /// Returns a 2x3 image, where each pixel has its index as value.
+    fn numbered() -> DynamicImage {
+        DynamicImage::ImageLuma8(ImageBuffer::from_fn(2, 3, |x, y| Luma([(y * 2 + x) as u8])))
+    }
+
+    /// Returns the values of the image row by row.
+    fn values(img: &DynamicImage) -> Vec<u8> {
+        img.to_luma8().into_raw()
+    }
+
+    #[test]
+    fn normal_is_unchanged() {
+        let img = DynamicImage::ImageRgba8(RgbaImage::new(4, 2));
+        assert_eq!(img.clone(), orient(img, Orientation::Normal));
+    }
+
+    #[test]
+    fn rotate_90_is_clockwise() {
+        //0 1
+        //2 3
+        //4 5
+        let rotated = orient(numbered(), Orientation::Rotate90);
+        assert_eq!((3, 2), rotated.dimensions());
+        assert_eq!(vec![4, 2, 0, 5, 3, 1], values(&rotated));
+    }
+
+    #[test]
+    fn transpose_swaps_axes() {
+        let transposed = orient(numbered(), Orientation::Transpose);
+        assert_eq!(vec![0, 2, 4, 1, 3, 5], values(&transposed));
+    }
+
+    #[test]
+    fn transverse_swaps_axes() {
+        let transversed = orient(numbered(), Orientation::Transverse);
+        assert_eq!(vec![5, 3, 1, 4, 2, 0], values(&transversed));
+    }
+}
+
+/// Rotate the image by the given degrees clockwise.
+///
+/// Multiples of 90 degrees are rotated exactly. For other angles the image is enlarged to fit the rotated image
+/// and each pixel is interpolated bilinearly from the original image, the new corners are transparent.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let rotated = rotate(img, 30f32);
+/// ```
+pub(crate) fn rotate(img: DynamicImage, degrees: f32) -> DynamicImage {
+    let degrees = degrees.rem_euclid(360f32);
+    //allow for small rounding errors, so for example -90 is still rotated exactly
+    let quarter_turns = (degrees / 90f32).round();
+    if (degrees - quarter_turns * 90f32).abs() < 1e-3 {
+        return match quarter_turns as u32 % 4 {
+            0 => img,
+            1 => img.rotate90(),
+            2 => img.rotate180(),
+            _ => img.rotate270(),
+        };
+    }
+
+    let source = img.to_rgba8();
+    let (width, height) = source.dimensions();
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let target_width = (width as f32 * cos.abs() + height as f32 * sin.abs()).round() as u32;
+    let target_height = (width as f32 * sin.abs() + height as f32 * cos.abs()).round() as u32;
+    log::debug!("Rotated image size: {target_width}x{target_height}");
+
+    let center = (width as f32 / 2f32, height as f32 / 2f32);
+    let target_center = (target_width as f32 / 2f32, target_height as f32 / 2f32);
+    let rotated = RgbaImage::from_fn(target_width.max(1), target_height.max(1), |x, y| {
+        //rotate the center of the target pixel back into the source image
+        let dx = x as f32 + 0.5 - target_center.0;
+        let dy = y as f32 + 0.5 - target_center.1;
+        let source_x = dx * cos + dy * sin + center.0 - 0.5;
+        let source_y = -dx * sin + dy * cos + center.1 - 0.5;
+        sample_bilinear(&source, source_x, source_y)
+    });
+    DynamicImage::ImageRgba8(rotated)
+}
+
+/// Returns the bilinearly interpolated color at the given position, pixels outside of the image are transparent.
+fn sample_bilinear(img: &RgbaImage, x: f32, y: f32) -> Rgba<u8> {
+    let (width, height) = img.dimensions();
+    if x <= -1f32 || y <= -1f32 || x >= width as f32 || y >= height as f32 {
+        return Rgba([0, 0, 0, 0]);
+    }
+    let (left, top) = (x.floor(), y.floor());
+    let (fraction_x, fraction_y) = (x - left, y - top);
+    let pixel = |p_x: f32, p_y: f32| -> [f32; 4] {
+        if p_x < 0f32 || p_y < 0f32 || p_x >= width as f32 || p_y >= height as f32 {
+            [0f32; 4]
+        } else {
+            img.get_pixel(p_x as u32, p_y as u32)
+                .0
+                .map(|value| value as f32)
+        }
+    };
+    let top_left = pixel(left, top);
+    let top_right = pixel(left + 1f32, top);
+    let bottom_left = pixel(left, top + 1f32);
+    let bottom_right = pixel(left + 1f32, top + 1f32);
+
+    let mut color = [0u8; 4];
+    for (channel, value) in color.iter_mut().enumerate() {
+        let top = top_left[channel] * (1f32 - fraction_x) + top_right[channel] * fraction_x;
+        let bottom =
+            bottom_left[channel] * (1f32 - fraction_x) + bottom_right[channel] * fraction_x;
+        *value = (top * (1f32 - fraction_y) + bottom * fraction_y)
+            .round()
+            .clamp(0f32, 255f32) as u8;
+    }
+    Rgba(color)
+}
+
+#[cfg(test)]
+mod test_rotate {
+    use image::GenericImageView;
+
+    use super::*;
+
+    #[test]
+    fn quarter_turns_are_exact() {
+        let img = DynamicImage::ImageRgba8(RgbaImage::from_fn(4, 2, |x, y| {
+            Rgba([x as u8, y as u8, 0, 255])
+        }));
+        assert_eq!(img.rotate90(), rotate(img.clone(), 90f32));
+        assert_eq!(img.rotate270(), rotate(img.clone(), -90f32));
+        assert_eq!(img.rotate180(), rotate(img.clone(), 540f32));
+        assert_eq!(img.clone(), rotate(img, 360f32));
+    }
+
+    #[test]
+    fn arbitrary_angle_enlarges_image() {
+        let img = DynamicImage::ImageRgba8(RgbaImage::from_pixel(10, 10, Rgba([255; 4])));
+        let rotated = rotate(img, 45f32);
+        assert_eq!((14, 14), rotated.dimensions());
+        //the center is still part of the image, the corners are transparent
+        assert_eq!(Rgba([255; 4]), rotated.get_pixel(7, 7));
+        assert_eq!(0, rotated.get_pixel(0, 0).0[3]);
+    }
+
+    #[test]
+    fn rotation_is_clockwise() {
+        //a bright pixel right of the center moves below it
+        let img = DynamicImage::ImageRgba8(RgbaImage::from_fn(9, 9, |x, y| {
+            if x == 8 && y == 4 {
+                Rgba([255; 4])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        }));
+        let rotated = rotate(img, 89f32).to_rgba8();
+        assert_eq!((9, 9), rotated.dimensions());
+        let brightest = rotated
+            .enumerate_pixels()
+            .max_by_key(|(_, _, pixel)| pixel.0[0])
+            .unwrap();
+        assert_eq!((4, 8), (brightest.0, brightest.1));
+    }
+}
@@ -0,0 +1,138 @@
+use font8x8::{UnicodeFonts, BASIC_FONTS, BLOCK_FONTS, BOX_FONTS, GREEK_FONTS, LATIN_FONTS};
+
+use crate::pixel;
+
+/// Size of the glyphs in the embedded bitmap font, they are square.
+pub(crate) const GLYPH_SIZE: u32 = 8;
+
+/// Returns the bitmap of a character, each byte is one row of the glyph and the lowest bit is the leftmost pixel.
+///
+/// Braille patterns are not part of the font, so they are drawn from their dots. Sextants are replaced
+/// by the most similar quadrant. Other unknown characters return [`None`].
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(Some([0; 8]), glyph(' '));
+/// ```
+pub(crate) fn glyph(char: char) -> Option<[u8; 8]> {
+    if let Some(dots) = pixel::braille_dots(char) {
+        return Some(braille_glyph(dots));
+    }
+    let char = pixel::sextant_to_quadrant(char).unwrap_or(char);
+    BASIC_FONTS
+        .get(char)
+        .or_else(|| BOX_FONTS.get(char))
+        .or_else(|| BLOCK_FONTS.get(char))
+        .or_else(|| LATIN_FONTS.get(char))
+        .or_else(|| GREEK_FONTS.get(char))
+}
+
+/// Returns the bitmap of a braille pattern with the given dots, see [`pixel::braille_char`].
+///
+/// Each of the 2x4 dots is two pixels wide and one pixel high, with a gap of one row between the dots.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!([0b0000_0110, 0, 0, 0, 0, 0, 0, 0], braille_glyph(0b1));
+/// ```
+fn braille_glyph(dots: u8) -> [u8; 8] {
+    //the lowest bit is the leftmost pixel
+    const DOT_COLUMNS: [u8; 2] = [0b0000_0110, 0b0110_0000];
+    let mut glyph = [0; 8];
+    for row in 0..4 {
+        for (column, pixels) in DOT_COLUMNS.iter().enumerate() {
+            if dots & (1 << (row * 2 + column)) != 0 {
+                glyph[row * 2] |= pixels;
+            }
+        }
+    }
+    glyph
+}
+
+#[cfg(test)]
+mod test_glyph {
+    use super::*;
+
+    #[test]
+    fn space_is_empty() {
+        assert_eq!(Some([0; 8]), glyph(' '));
+    }
+
+    #[test]
+    fn sextants_are_drawn_as_quadrants() {
+        assert_eq!(glyph('▟'), glyph(pixel::sextant_char(0b111110)));
+    }
+
+    #[test]
+    fn box_chars_are_known() {
+        assert_ne!(None, glyph('║'));
+        assert_ne!(Some([0; 8]), glyph('║'));
+    }
+
+    #[test]
+    fn braille_is_drawn() {
+        assert_eq!(Some([0; 8]), glyph('\u{2800}'));
+        assert_eq!(
+            Some([
+                0b0110_0110,
+                0,
+                0b0110_0110,
+                0,
+                0b0110_0110,
+                0,
+                0b0110_0110,
+                0
+            ]),
+            glyph('⣿')
+        );
+        assert_eq!(
+            Some([0b0000_0110, 0, 0, 0, 0, 0, 0b0110_0000, 0]),
+            glyph('⢁')
+        );
+    }
+
+    #[test]
+    fn sextant_uses_quadrant() {
+        assert_eq!(glyph('▘'), glyph('\u{1FB00}'));
+        assert_eq!(glyph('▟'), glyph('\u{1FB3B}'));
+    }
+
+    #[test]
+    fn unknown_char_is_none() {
+        assert_eq!(None, glyph('😀'));
+    }
+}
+
+/// Returns the part of the glyph of a character that is covered by ink, from 0 (empty) to 1 (filled).
+///
+/// Unknown characters return [`None`], see [`glyph`].
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(Some(1f32), coverage('█'));
+/// ```
+pub(crate) fn coverage(char: char) -> Option<f32> {
+    let pixels: u32 = glyph(char)?.iter().map(|row| row.count_ones()).sum();
+    Some(pixels as f32 / (GLYPH_SIZE * GLYPH_SIZE) as f32)
+}
+
+#[cfg(test)]
+mod test_coverage {
+    use super::*;
+
+    #[test]
+    fn empty_and_full() {
+        assert_eq!(Some(0f32), coverage(' '));
+        assert_eq!(Some(1f32), coverage('█'));
+    }
+
+    #[test]
+    fn denser_chars_cover_more() {
+        assert!(coverage('@').unwrap() > coverage('.').unwrap());
+    }
+
+    #[test]
+    fn unknown_char() {
+        assert_eq!(None, coverage('😀'));
+    }
+}
@@ -0,0 +1,168 @@
+use image::Rgb;
+
+/// A single converted tile of the image.
+///
+/// It contains the character chosen for the tile, as well as the average color and luminosity
+/// of all pixels in the tile. This can be used to create a custom output, for example in a TUI,
+/// without having to parse the colored string output.
+///
+/// Some render modes, like [`crate::config::RenderMode::HalfBlock`], use a character with two colors.
+/// In this case the `color` is the foreground color of the character and the `background` is set.
+///
+/// New fields might be added in the future, so cells outside of this crate have to be created with [`Cell::new`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct Cell {
+    /// Character that represents the tile.
+    pub character: char,
+    /// Average color of the tile.
+    pub color: Rgb<u8>,
+    /// Luminosity of the average color, ranges from 0 to 255.
+    pub luminosity: f32,
+    /// Background color of the character, if the render mode uses one.
+    pub background: Option<Rgb<u8>>,
+    /// The tile is fully transparent, so it is written as blank space without any colors.
+    ///
+    /// This is only set when [`crate::config::ConfigBuilder::blank_transparent`] is enabled.
+    pub transparent: bool,
+}
+
+impl Cell {
+    /// Create a new cell with a single color, which is neither transparent nor has a background color.
+    ///
+    /// The other fields can be changed afterwards, for example to add a background color.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::grid::Cell;
+    /// use image::Rgb;
+    ///
+    /// let mut cell = Cell::new('#', Rgb([255, 0, 0]), 54f32);
+    /// cell.background = Some(Rgb([0, 0, 255]));
+    /// assert!(!cell.transparent);
+    /// ```
+    pub fn new(character: char, color: Rgb<u8>, luminosity: f32) -> Self {
+        Self {
+            character,
+            color,
+            luminosity,
+            background: None,
+            transparent: false,
+        }
+    }
+}
+
+/// The converted image as rows of [`Cell`]s.
+///
+/// It only contains the image itself, decorations like a border or centering are
+/// added when the grid is written to an output target.
+///
+/// # Examples
+/// ```
+/// use artem::config::ConfigBuilder;
+///
+/// let img = image::open("assets/images/standard_test_img.png").unwrap();
+/// let grid = artem::convert_to_grid(img, &ConfigBuilder::new().build().unwrap()).unwrap();
+/// for row in grid.iter_rows() {
+///     let line = row.iter().map(|cell| cell.character).collect::<String>();
+///     assert_eq!(grid.columns() as usize, line.chars().count());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    columns: u32,
+    rows: u32,
+    cells: Vec<Cell>,
+}
+
+impl Grid {
+    /// Create a new grid from cells, which are stored row by row.
+    ///
+    /// # Panics
+    /// Panics if the number of cells does not match the number of columns and rows.
+    pub(crate) fn new(columns: u32, rows: u32, cells: Vec<Cell>) -> Self {
+        assert_eq!((columns * rows) as usize, cells.len());
+        Self {
+            columns,
+            rows,
+            cells,
+        }
+    }
+
+    /// Returns the number of columns, which is the number of cells in each row.
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    /// Returns the cell at the given position, or `None` if it is outside of the grid.
+    ///
+    /// # Examples
+    /// ```
+    /// use artem::config::ConfigBuilder;
+    ///
+    /// let img = image::open("assets/images/standard_test_img.png").unwrap();
+    /// let grid = artem::convert_to_grid(img, &ConfigBuilder::new().build().unwrap()).unwrap();
+    /// assert!(grid.get(0, 0).is_some());
+    /// assert!(grid.get(grid.columns(), 0).is_none());
+    /// ```
+    pub fn get(&self, column: u32, row: u32) -> Option<&Cell> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+        self.cells.get((row * self.columns + column) as usize)
+    }
+
+    /// Returns all cells, stored row by row.
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Returns an iterator over the rows of the grid.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks_exact(self.columns as usize)
+    }
+}
+
+#[cfg(test)]
+mod test_grid {
+    use super::*;
+
+    fn cell(character: char) -> Cell {
+        Cell::new(character, Rgb([0, 0, 0]), 0f32)
+    }
+
+    #[test]
+    fn get_returns_row_major_cell() {
+        let grid = Grid::new(2, 2, vec![cell('a'), cell('b'), cell('c'), cell('d')]);
+        assert_eq!('c', grid.get(0, 1).unwrap().character);
+        assert_eq!('b', grid.get(1, 0).unwrap().character);
+    }
+
+    #[test]
+    fn get_outside_is_none() {
+        let grid = Grid::new(2, 1, vec![cell('a'), cell('b')]);
+        assert_eq!(None, grid.get(0, 1));
+        assert_eq!(None, grid.get(2, 0));
+    }
+
+    #[test]
+    fn iter_rows_splits_by_columns() {
+        let grid = Grid::new(3, 2, "abcdef".chars().map(cell).collect());
+        let rows = grid
+            .iter_rows()
+            .map(|row| row.iter().map(|cell| cell.character).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["abc", "def"], rows);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_cell_count_panics() {
+        Grid::new(3, 2, vec![cell('a')]);
+    }
+}
@@ -10,209 +10,845 @@
 //! ```
 //! # let path = "./assets/images/standard_test_img.png";
 //! let image = image::open(path).expect("Failed to open image");
-//! let ascii_art = artem::convert(image, &artem::config::ConfigBuilder::new().build());
+//! let config = artem::config::ConfigBuilder::new().build().expect("Invalid config");
+//! let ascii_art = artem::convert(image, &config).expect("Failed to convert image");
 //! ```
 
 //condense all arguments into a single struct
 pub mod config;
 
+//errors that can occur during the conversion
+mod error;
+
+//structured output of the conversion
+pub mod grid;
+
 //functions for working with pixels
 mod pixel;
 
+//color palettes and metrics for reducing colors
+mod color;
+
+//dithering for characters and reduced colors
+mod dither;
+
+//embedded bitmap font for rasterizing characters
+mod font;
+
+//matching tiles to characters by their shape
+mod shape;
+
+//iteration, which is parallelized with the parallel feature
+mod parallel;
+
 //outlining filter
 mod filter;
-//functions for dealing with output targets/files
-mod target;
+//output targets/files and the renderer trait to create custom ones
+pub mod target;
 
-use image::{DynamicImage, GenericImageView};
+use std::io::Write;
+
+use image::{DynamicImage, GenericImageView, Rgb, Rgba};
 use once_cell::sync::Lazy;
 
 pub use crate::config::ConfigBuilder;
-use crate::config::{Config, ResizingDimension, TargetType};
+use crate::config::{Calibration, ColorDepth, Config, Dithering, RenderMode, ResizingDimension};
+pub use crate::error::Error;
+use crate::grid::{Cell, Grid};
 
 /// Takes an image and returns it as an ascii art string.
 ///
-/// The result can be changed using the [`crate::config::Config`] argument
+/// The result can be changed using the [`crate::config::Config`] argument.
+/// Since the whole ascii art is kept in memory, it is recommended to use [`convert_to_writer`]
+/// for large outputs instead.
+///
+/// # Errors
+/// Returns an [`Error`] if the [`Config`] contains invalid values or the image is empty,
+/// for example when it has a width of zero.
+///
+/// # Examples
+/// ```no_run
+/// use artem::config::ConfigBuilder;
+///
+/// let img = image::open("examples/abraham_lincoln.jpg").unwrap();
+/// let converted_image = artem::convert(img, &ConfigBuilder::new().build().unwrap()).unwrap();
+/// ```
+pub fn convert(image: DynamicImage, config: &Config) -> Result<String, Error> {
+    let mut output = Vec::new();
+    convert_to_writer(image, config, &mut output)?;
+    //the output is only ever created from strings, so it is always valid utf-8
+    Ok(String::from_utf8(output).expect("Output is not valid UTF-8"))
+}
+
+/// Takes an image and returns it as a [`Grid`] of cells.
+///
+/// Each [`grid::Cell`] contains the chosen character, as well as the average color and luminosity of the tile.
+/// This can be used to create custom outputs, for example for a TUI, without having to parse the
+/// colored output strings. Decorations like a border or centering are not part of the grid,
+/// since they only exist in the written output.
+///
+/// # Errors
+/// Returns an [`Error`] if the [`Config`] contains invalid values or the image is empty,
+/// for example when it has a width of zero.
+///
 /// # Examples
 /// ```no_run
 /// use artem::config::ConfigBuilder;
 ///
 /// let img = image::open("examples/abraham_lincoln.jpg").unwrap();
-/// let converted_image = artem::convert(img, &ConfigBuilder::new().build());
+/// let grid = artem::convert_to_grid(img, &ConfigBuilder::new().build().unwrap()).unwrap();
+/// let first_cell = grid.get(0, 0).unwrap();
+/// println!("{} {:?}", first_cell.character, first_cell.color);
 /// ```
-pub fn convert(image: DynamicImage, config: &Config) -> String {
-    log::debug!("Using inverted color: {}", config.invert);
-    //get img dimensions
-    let input_width = image.width();
-    let input_height = image.height();
-    log::debug!("Input Image Width: {input_width}");
-    log::debug!("Input Image Height: {input_height}");
-
-    //calculate the needed dimensions
-    let (columns, rows, tile_width, tile_height) = ResizingDimension::calculate_dimensions(
-        config.target_size,
-        input_height,
-        input_width,
-        config.scale,
-        config.border,
-        config.dimension,
-    );
-    log::debug!("Columns: {columns}");
-    log::debug!("Rows: {rows}");
-    log::debug!("Tile Width: {tile_width}");
-    log::debug!("Tile Height: {tile_height}");
-
-    let mut input_img = image;
-
-    if config.outline {
-        //create an outline using an algorithm loosely based on the canny edge algorithm
-        input_img = filter::edge_detection_filter(input_img, config.hysteresis);
+pub fn convert_to_grid(image: DynamicImage, config: &Config) -> Result<Grid, Error> {
+    let converter = Converter::new(image, config)?;
+    let (columns, rows) = (converter.columns, converter.rows);
+    let cells = converter.flatten().collect();
+    Ok(Grid::new(columns, rows, cells))
+}
+
+/// Converts a prepared image row by row into [`Cell`]s.
+///
+/// Everything which needs the whole image, like the resizing, outline or dots, is done when it is created.
+/// Each row is only converted when it is requested, so it can be written before the next one is converted.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let converter = Converter::new(img, &config)?;
+/// for row in converter {
+///     println!("{}", row.iter().map(|cell| cell.character).collect::<String>());
+/// }
+/// ```
+struct Converter<'a> {
+    config: &'a Config,
+    /// Resized image, which is split into the tiles.
+    source_img: DynamicImage,
+    /// Orientation of the edges, which is only needed for the edge characters.
+    angles: Option<filter::Angles>,
+    /// Dots of the whole image, row by row, if the render mode thresholds them.
+    image_dots: Option<Vec<bool>>,
+    /// Glyphs of the characters, if they are matched by their shape.
+    shapes: Option<shape::Shapes>,
+    columns: u32,
+    rows: u32,
+    tile_width: u32,
+    tile_height: u32,
+    colored: bool,
+    ditherer: dither::Ditherer<1>,
+    half_block_ditherer: dither::Ditherer<2>,
+    dither_characters: bool,
+    dither_half_blocks: bool,
+    /// Index of the next row, which will be converted.
+    row: u32,
+}
+
+impl<'a> Converter<'a> {
+    /// Prepare the image for the conversion.
+    ///
+    /// # Errors
+    /// Returns an [`Error`] if the [`Config`] contains invalid values or the image is empty.
+    fn new(image: DynamicImage, config: &'a Config) -> Result<Self, Error> {
+        //the config fields are public, so they might not have been checked by the builder
+        config.validate()?;
+
+        log::debug!("Using inverted color: {}", config.invert);
+        if image.width() == 0 || image.height() == 0 {
+            return Err(Error::ZeroDimensions(image.width(), image.height()));
+        }
+
+        //the orientation, crop and rotation change the size of the image, so they are applied first
+        let mut image = filter::orient(image, config.orientation);
+        if let Some(crop) = config.crop {
+            let Some((x, y, width, height)) = crop.region(image.width(), image.height()) else {
+                return Err(Error::InvalidCrop(crop));
+            };
+            log::info!("Cropping image to {width}x{height} at {x},{y}");
+            image = image.crop_imm(x, y, width, height);
+        }
+        if config.rotation != 0f32 {
+            log::info!("Rotating image by {} degrees", config.rotation);
+            image = filter::rotate(image, config.rotation);
+        }
+
+        //the new corners of a rotated image are transparent as well, so they are filled afterwards
+        if let Some(background) = config.background_fill {
+            log::info!("Filling transparent background with {background:?}");
+            image = filter::fill_background(image, background);
+        }
+
+        //get img dimensions
+        let input_width = image.width();
+        let input_height = image.height();
+        log::debug!("Input Image Width: {input_width}");
+        log::debug!("Input Image Height: {input_height}");
+
+        //calculate the needed dimensions
+        let (columns, rows, tile_width, tile_height) = ResizingDimension::calculate_dimensions(
+            config.target_size,
+            input_height,
+            input_width,
+            config.scale,
+            config.border,
+            config.dimension,
+            config.render_mode,
+        );
+        log::debug!("Columns: {columns}");
+        log::debug!("Rows: {rows}");
+        log::debug!("Tile Width: {tile_width}");
+        log::debug!("Tile Height: {tile_height}");
+
+        let mut input_img = image;
+        //orientation of the edges, which is only needed for the edge characters
+        let mut angles = None;
+
+        if !config.adjustments.is_empty() {
+            log::info!("Adjusting image");
+            input_img = filter::adjust_image(input_img, &config.adjustments);
+        }
+
+        if config.outline {
+            //the outline only keeps the luminosity, so the transparency of the image is added back afterwards
+            let transparent_img = input_img.color().has_alpha().then(|| input_img.clone());
+            //create an outline using an algorithm loosely based on the canny edge algorithm
+            let (outline_img, outline_angles) = filter::edge_detection_filter(input_img, config);
+            input_img = match &transparent_img {
+                Some(transparent_img) => filter::with_alpha(outline_img, transparent_img),
+                None => outline_img,
+            };
+            if config.edge_characters && config.render_mode == RenderMode::Ascii {
+                angles = Some(outline_angles);
+            }
+        }
+
+        if config.transform_x {
+            log::info!("Flipping image horizontally");
+            input_img = input_img.fliph();
+            angles = angles.map(|angles| filter::flip_angles(&angles, true));
+        }
+
+        if config.transform_y {
+            log::info!("Flipping image vertically");
+            input_img = input_img.flipv();
+            angles = angles.map(|angles| filter::flip_angles(&angles, false));
+        }
+
+        log::info!("Resizing image to fit new dimensions");
+        //use the thumbnail method, since its way faster, it may result in artifacts, but the ascii art will be pixelate anyway
+        let source_img = input_img.thumbnail_exact(columns * tile_width, rows * tile_height);
+        let angles = angles
+            .map(|angles| filter::resize_angles(&angles, source_img.width(), source_img.height()));
+
+        log::debug!("Resized Image Width: {}", source_img.width());
+        log::debug!("Resized Image Height: {}", source_img.height());
+
+        log::info!("Starting conversion to ascii");
+
+        //the characters are evenly spaced brightness levels, when dithering them
+        let density_length = config.characters.chars().count();
+        let ditherer = dither::Ditherer::<1>::new(
+            config.dither,
+            255f32 / density_length.saturating_sub(1).max(1) as f32,
+        );
+        //half blocks are dithered for each half, which is either filled or empty
+        let half_block_ditherer = dither::Ditherer::<2>::new(config.dither, 255f32);
+        let colored = config.target.is_colored();
+
+        let (pixels_x, pixels_y) = config.render_mode.pixels_per_cell();
+        let (pixel_width, pixel_height) = (tile_width / pixels_x, tile_height / pixels_y);
+        //dots are thresholded for the whole image first, so the dithering can spread between the dots,
+        //quadrants and sextants only need them without colors, since they are split into two colors otherwise
+        let image_dots = match config.render_mode {
+            RenderMode::Braille => true,
+            RenderMode::Quadrant | RenderMode::Sextant => !colored,
+            RenderMode::Ascii | RenderMode::HalfBlock | RenderMode::Shape => false,
+        }
+        .then(|| {
+            dots(
+                &source_img,
+                (columns * pixels_x, rows * pixels_y),
+                (pixel_width, pixel_height),
+                config.dither,
+                config.invert,
+            )
+        });
+
+        //without any characters of the embedded font, the shapes can not be matched, so the luminosity is used instead
+        let shapes = match config.render_mode {
+            RenderMode::Shape => shape::Shapes::new(&config.characters).or_else(|| {
+                log::warn!(
+                    "None of the characters can be rasterized, falling back to their luminosity"
+                );
+                None
+            }),
+            _ => None,
+        };
+
+        //the characters are only dithered, if they are chosen by their luminosity
+        let dither_characters = config.dither != Dithering::None
+            && matches!(
+                (config.render_mode, &shapes),
+                (RenderMode::Ascii, _) | (RenderMode::Shape, None)
+            );
+        //colored half blocks show both colors, so only the uncolored ones need dithering
+        let dither_half_blocks = config.dither != Dithering::None
+            && config.render_mode == RenderMode::HalfBlock
+            && !colored;
+
+        Ok(Self {
+            config,
+            source_img,
+            angles,
+            image_dots,
+            shapes,
+            columns,
+            rows,
+            tile_width,
+            tile_height,
+            colored,
+            ditherer,
+            half_block_ditherer,
+            dither_characters,
+            dither_half_blocks,
+            row: 0,
+        })
     }
 
-    if config.transform_x {
-        log::info!("Flipping image horizontally");
-        input_img = input_img.fliph();
+    /// Converts the tile at the given pixel position, without dithering it.
+    fn tile(&self, x: u32, y: u32) -> Tile {
+        let config = self.config;
+        let (source_img, shapes, angles) = (&self.source_img, &self.shapes, &self.angles);
+        let (image_dots, colored) = (&self.image_dots, self.colored);
+        let (tile_width, tile_height) = (self.tile_width, self.tile_height);
+        let (pixels_x, pixels_y) = config.render_mode.pixels_per_cell();
+        let (pixel_width, pixel_height) = (tile_width / pixels_x, tile_height / pixels_y);
+        let dot_columns = (self.columns * pixels_x) as usize;
+        //the pixels gathered for the cell are reused to check if the tile is transparent
+        let is_transparent = |pixels: &[Rgba<u8>]| {
+            config.blank_transparent && pixels.iter().all(|pixel| pixel.0[3] == 0)
+        };
+        let mut halves = [0f32; 2];
+        let mut edge = None;
+        let (cell, transparent) = match (config.render_mode, shapes) {
+            (RenderMode::Shape, Some(shapes)) => {
+                let shape =
+                    shape::tile_shape(source_img, x, y, tile_width, tile_height, config.invert);
+                let pixels = block_pixels(source_img, x, y, tile_width, tile_height);
+                (
+                    pixel::average_cell(&pixels, shapes.matching_char(&shape)),
+                    is_transparent(&pixels),
+                )
+            }
+            (RenderMode::Ascii | RenderMode::Shape, _) => {
+                edge = angles.as_ref().and_then(|angles| {
+                    filter::edge_char(source_img, angles, x, y, tile_width, tile_height)
+                });
+                //convert pixels to a cell
+                let pixels = block_pixels(source_img, x, y, tile_width, tile_height);
+                (
+                    pixel::correlating_cell(&pixels, &config.characters, config.invert),
+                    is_transparent(&pixels),
+                )
+            }
+            (RenderMode::HalfBlock, _) => {
+                //the tile height is always even, so both halves have the same size
+                let half_height = tile_height / 2;
+                let top_pixels = block_pixels(source_img, x, y, tile_width, half_height);
+                let bottom_pixels =
+                    block_pixels(source_img, x, y + half_height, tile_width, half_height);
+                let top = pixel::correlating_cell(&top_pixels, &config.characters, config.invert);
+                let bottom =
+                    pixel::correlating_cell(&bottom_pixels, &config.characters, config.invert);
+                halves = [top.luminosity, bottom.luminosity];
+                (
+                    pixel::half_block_cell(&top, &bottom, colored, config.invert),
+                    is_transparent(&top_pixels) && is_transparent(&bottom_pixels),
+                )
+            }
+            (RenderMode::Braille | RenderMode::Quadrant | RenderMode::Sextant, _) => {
+                let glyph: fn(u8) -> char = match config.render_mode {
+                    RenderMode::Quadrant => pixel::quadrant_char,
+                    //the font does not support sextants, so only their top and bottom row is shown
+                    RenderMode::Sextant if config.sextant_fallback => |sextants| {
+                        let char = pixel::sextant_char(sextants);
+                        pixel::sextant_to_quadrant(char).unwrap_or(char)
+                    },
+                    RenderMode::Sextant => pixel::sextant_char,
+                    _ => pixel::braille_char,
+                };
+                if let Some(image_dots) = image_dots {
+                    //collect the dots of the cell row by row, the first one is the lowest bit
+                    let (column, row) = ((x / tile_width) as usize, (y / tile_height) as usize);
+                    let mut cell_dots = 0u8;
+                    for dot_y in 0..pixels_y as usize {
+                        for dot_x in 0..pixels_x as usize {
+                            let index = (row * pixels_y as usize + dot_y) * dot_columns
+                                + column * pixels_x as usize
+                                + dot_x;
+                            if image_dots[index] {
+                                cell_dots |= 1 << (dot_y * pixels_x as usize + dot_x);
+                            }
+                        }
+                    }
+                    let pixels = block_pixels(source_img, x, y, tile_width, tile_height);
+                    (
+                        pixel::average_cell(&pixels, glyph(cell_dots)),
+                        is_transparent(&pixels),
+                    )
+                } else {
+                    let mut sub_pixels = Vec::with_capacity((pixels_x * pixels_y) as usize);
+                    for p_y in (0..pixels_y).map(|p_y| y + p_y * pixel_height) {
+                        for p_x in (0..pixels_x).map(|p_x| x + p_x * pixel_width) {
+                            sub_pixels.push(block_pixels(
+                                source_img,
+                                p_x,
+                                p_y,
+                                pixel_width,
+                                pixel_height,
+                            ));
+                        }
+                    }
+                    (
+                        pixel::two_color_cell(&sub_pixels, glyph),
+                        sub_pixels.iter().all(|pixels| is_transparent(pixels)),
+                    )
+                }
+            }
+        };
+        Tile {
+            cell,
+            halves,
+            edge,
+            transparent,
+        }
     }
+}
+
+impl Iterator for Converter<'_> {
+    type Item = Vec<Cell>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row >= self.rows {
+            return None;
+        }
+        let y = self.row * self.tile_height;
+        self.row += 1;
+
+        //the tiles only depend on their own pixels, so they can be converted in parallel,
+        //only the dithering depends on the previous tiles and is applied to them afterwards in order
+        let converter = &*self;
+        let tiles = parallel::map_indices(self.columns as usize, |column| {
+            converter.tile(column as u32 * converter.tile_width, y)
+        });
 
-    if config.transform_y {
-        log::info!("Flipping image vertically");
-        input_img = input_img.flipv();
+        let config = self.config;
+        let mut cells = Vec::with_capacity(tiles.len());
+        for tile in tiles {
+            let mut cell = tile.cell;
+            if self.dither_characters {
+                cell.character = self.ditherer.dither([cell.luminosity], |[luminosity]| {
+                    let (character, level) =
+                        pixel::quantize_luminosity(luminosity, &config.characters, config.invert);
+                    (character, [level])
+                });
+            }
+            if self.dither_half_blocks {
+                cell.character = self.half_block_ditherer.dither(tile.halves, |luminosity| {
+                    pixel::quantize_half_blocks(luminosity, config.invert)
+                });
+            }
+            //edges are drawn with the character of their direction instead
+            if let Some(character) = tile.edge {
+                cell.character = character;
+            }
+
+            //fully transparent tiles are left blank, they are still dithered to keep the dithering in place
+            if tile.transparent {
+                cells.push(Cell {
+                    character: ' ',
+                    color: Rgb([0, 0, 0]),
+                    luminosity: 0f32,
+                    background: None,
+                    transparent: true,
+                });
+            } else {
+                cells.push(cell);
+            }
+        }
+        self.ditherer.end_row();
+        self.half_block_ditherer.end_row();
+        Some(cells)
     }
+}
 
-    log::info!("Resizing image to fit new dimensions");
-    //use the thumbnail method, since its way faster, it may result in artifacts, but the ascii art will be pixelate anyway
-    let source_img = input_img.thumbnail_exact(columns * tile_width, rows * tile_height);
+#[cfg(test)]
+mod test_converter {
+    use super::*;
 
-    log::debug!("Resized Image Width: {}", source_img.width());
-    log::debug!("Resized Image Height: {}", source_img.height());
+    #[test]
+    fn rows_are_converted_one_by_one() {
+        let img = image::open("assets/images/standard_test_img.png").unwrap();
+        let config = ConfigBuilder::new().build().unwrap();
+        let mut converter = Converter::new(img, &config).unwrap();
+        let first_row = converter.next().unwrap();
+        assert_eq!(converter.columns as usize, first_row.len());
+        //only the first row is converted so far
+        assert_eq!(1, converter.row);
+        assert_eq!(converter.rows as usize - 1, converter.count());
+    }
 
-    //output string
-    let mut output = String::with_capacity((tile_width * tile_height) as usize);
-    log::trace!("Created output string");
+    #[test]
+    fn rows_match_grid() {
+        let img = image::open("assets/images/standard_test_img.png").unwrap();
+        let mut builder = ConfigBuilder::new();
+        builder.dither(Dithering::FloydSteinberg);
+        let config = builder.build().unwrap();
+        let grid = convert_to_grid(img.clone(), &config).unwrap();
+        let rows: Vec<Vec<Cell>> = Converter::new(img, &config).unwrap().collect();
+        assert_eq!(grid.rows() as usize, rows.len());
+        assert_eq!(grid.cells(), rows.concat());
+    }
+}
 
-    if matches!(&config.target, &TargetType::HtmlFile(true, true)) {
-        log::trace!("Adding html top part");
-        output.push_str(&target::html::html_top());
+/// Cell of a single tile, before the dithering is applied.
+///
+/// The dithering depends on the previous tiles, so it can only be applied once all of them are converted.
+struct Tile {
+    cell: Cell,
+    /// Luminosity of the top and bottom half, which are dithered separately for half blocks.
+    halves: [f32; 2],
+    /// Character of the edge in the tile, which replaces the dithered character.
+    edge: Option<char>,
+    /// Whether all pixels of the tile are transparent.
+    transparent: bool,
+}
+
+/// Returns all pixels of a block of the image, column by column.
+///
+/// The block is not checked to be inside of the image, this has to be ensured by the caller.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let pixels = block_pixels(&img, 0, 0, 2, 2);
+/// assert_eq!(4, pixels.len());
+/// ```
+fn block_pixels(img: &DynamicImage, x: u32, y: u32, width: u32, height: u32) -> Vec<Rgba<u8>> {
+    //pre-allocate vector with the with space for all pixels in the block
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for p_x in 0..width {
+        for p_y in 0..height {
+            pixels.push(unsafe { img.unsafe_get_pixel(x + p_x, y + p_y) })
+        }
+    }
+    pixels
+}
+
+/// Returns which dots of a grid of dots, each covering a block of the image, are set.
+///
+/// The dots are thresholded by the luminosity of their block, using [`pixel::quantize_dot`], and returned row by row.
+/// Since the whole grid is available, the dithering spreads the error between single dots instead of whole cells.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// //2x4 dots for a single braille character, each covering a single pixel
+/// let dots = dots(&img, (2, 4), (1, 1), Dithering::None, false);
+/// assert_eq!(8, dots.len());
+/// ```
+fn dots(
+    img: &DynamicImage,
+    (dot_columns, dot_rows): (u32, u32),
+    (dot_width, dot_height): (u32, u32),
+    dithering: Dithering,
+    invert: bool,
+) -> Vec<bool> {
+    let mut ditherer = dither::Ditherer::<1>::new(dithering, 255f32);
+    let mut dots = Vec::with_capacity((dot_columns * dot_rows) as usize);
+    for y in (0..dot_rows).map(|row| row * dot_height) {
+        for x in (0..dot_columns).map(|column| column * dot_width) {
+            let luminosity =
+                pixel::average_luminosity(&block_pixels(img, x, y, dot_width, dot_height));
+            dots.push(ditherer.dither([luminosity], |[luminosity]| {
+                let (dot, level) = pixel::quantize_dot(luminosity, invert);
+                (dot, [level])
+            }));
+        }
+        ditherer.end_row();
     }
+    dots
+}
+
+/// Takes an image and writes it as ascii art to the given writer.
+///
+/// Instead of creating the whole ascii art in memory, like [`convert`], the output will be written
+/// row by row, so the first lines are available as soon as they are converted.
+/// The writer is not flushed, this has to be done by the caller if needed.
+///
+/// # Errors
+/// Returns an [`Error`] if the [`Config`] contains invalid values, the image is empty,
+/// or if writing to the writer failed.
+///
+/// # Examples
+/// ```no_run
+/// use artem::config::ConfigBuilder;
+///
+/// let img = image::open("examples/abraham_lincoln.jpg").unwrap();
+/// let mut stdout = std::io::stdout().lock();
+/// artem::convert_to_writer(img, &ConfigBuilder::new().build().unwrap(), &mut stdout).unwrap();
+/// ```
+pub fn convert_to_writer<W: Write>(
+    image: DynamicImage,
+    config: &Config,
+    writer: &mut W,
+) -> Result<(), Error> {
+    convert_with_renderer(image, config, target::renderer(config).as_mut(), writer)
+}
+
+/// Takes an image and writes it to the given writer, using a custom [`target::Renderer`].
+///
+/// This works like [`convert_to_writer`], but instead of the built-in target from the [`Config`],
+/// the given renderer is used to create the output. This allows to create custom output formats.
+///
+/// # Errors
+/// Returns an [`Error`] if the [`Config`] contains invalid values, the image is empty,
+/// or if writing to the writer failed.
+///
+/// # Examples
+/// ```no_run
+/// use artem::{config::ConfigBuilder, target::html::HtmlRenderer};
+///
+/// let img = image::open("examples/abraham_lincoln.jpg").unwrap();
+/// let mut output = Vec::new();
+/// artem::convert_with_renderer(
+///     img,
+///     &ConfigBuilder::new().build().unwrap(),
+///     &mut HtmlRenderer::new(false),
+///     &mut output,
+/// )
+/// .unwrap();
+/// ```
+pub fn convert_with_renderer<W: Write>(
+    image: DynamicImage,
+    config: &Config,
+    renderer: &mut dyn target::Renderer,
+    writer: &mut W,
+) -> Result<(), Error> {
+    let converter = Converter::new(image, config)?;
+    let (columns, rows) = (converter.columns, converter.rows);
+    render_rows(columns, rows, converter, config, renderer, writer)
+}
+
+/// Writes an already converted [`Grid`] to the given writer, using the built-in target from the [`Config`].
+///
+/// This allows to use the size of the grid, before it is written. Decorations like the border or centering
+/// are added while writing, so the [`Config`] should be the same one, which was used for the conversion.
+///
+/// # Errors
+/// Returns an [`Error`] if writing to the writer failed.
+///
+/// # Examples
+/// ```no_run
+/// use artem::config::ConfigBuilder;
+///
+/// let img = image::open("examples/abraham_lincoln.jpg").unwrap();
+/// let config = ConfigBuilder::new().build().unwrap();
+/// let grid = artem::convert_to_grid(img, &config).unwrap();
+/// let mut output = Vec::new();
+/// artem::write_grid(&grid, &config, &mut output).unwrap();
+/// assert_eq!(grid.rows() as usize, output.iter().filter(|byte| **byte == b'\n').count());
+/// ```
+pub fn write_grid<W: Write>(grid: &Grid, config: &Config, writer: &mut W) -> Result<(), Error> {
+    render_rows(
+        grid.columns(),
+        grid.rows(),
+        grid.iter_rows(),
+        config,
+        target::renderer(config).as_mut(),
+        writer,
+    )
+}
+
+/// Writes the rows of cells with the renderer, including the border and centering of the [`Config`].
+///
+/// Each row is written as soon as the iterator returns it, so rows can be converted while the previous ones are written.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// render_rows(grid.columns(), grid.rows(), grid.iter_rows(), &config, &mut PlainRenderer, &mut output)?;
+/// ```
+fn render_rows<W: Write, R: AsRef<[Cell]>>(
+    columns: u32,
+    rows: u32,
+    cell_rows: impl Iterator<Item = R>,
+    config: &Config,
+    renderer: &mut dyn target::Renderer,
+    writer: &mut W,
+) -> Result<(), Error> {
+    //the border takes up two additional rows and columns
+    let (output_columns, output_rows) = if config.border {
+        (columns + 2, rows + 2)
+    } else {
+        (columns, rows)
+    };
+    renderer.header(writer, output_columns, output_rows)?;
 
     log::trace!("Calculating horizontal spacing");
-    let horizontal_spacing = if config.center_x {
-        spacing_horizontal(if config.border {
-            //two columns are missing because the border takes up two lines
-            columns + 2
-        } else {
-            columns
-        })
+    let center_x = config.center_x && renderer.centered();
+    let horizontal_spacing = if center_x {
+        spacing_horizontal(output_columns)
     } else {
         String::with_capacity(0)
     };
 
-    if config.center_y && matches!(&config.target, &TargetType::Shell(true, true)) {
-        log::trace!("Adding vertical top spacing");
-        output.push_str(&spacing_vertical(if config.border {
-            //two rows are missing because the border takes up two lines
-            rows + 2
-        } else {
-            rows
-        }));
-    }
-
     if config.border {
-        //add spacing for centering
-        if config.center_x {
-            output.push_str(&horizontal_spacing);
-        }
-
         //add top part of border before conversion
         log::trace!("Adding top part of border");
-        output.push('╔');
-        output.push_str(&"═".repeat(columns as usize));
-        output.push_str("╗\n");
+        renderer.text(
+            writer,
+            &format!("{horizontal_spacing}╔{}╗", "═".repeat(columns as usize)),
+        )?;
+        renderer.end_row(writer)?;
     }
 
-    log::info!("Starting conversion to ascii");
-    let width = source_img.width();
-
-    //convert source img to a target string
-    let target = source_img
-        .pixels()
-        .step_by(tile_width as usize)
-        .filter(|(x, y, _)| y % tile_height == 0 && x % tile_width == 0)
-        .map(|(x, y, _)| {
-            //pre-allocate vector with the with space for all pixels in the tile
-            let mut pixels = Vec::with_capacity((tile_height * tile_width) as usize);
-
-            //get all pixel of the tile
-            for p_x in 0..tile_width {
-                for p_y in 0..tile_height {
-                    pixels.push(unsafe { source_img.unsafe_get_pixel(x + p_x, y + p_y) })
-                }
+    //write the rows one by one, so each row is available as soon as it is converted
+    for row in cell_rows {
+        //add spacing for centering the image
+        if center_x {
+            renderer.text(writer, &horizontal_spacing)?;
+        }
+
+        //add outer border (left)
+        if config.border {
+            renderer.text(writer, "║")?;
+        }
+
+        for cell in row.as_ref() {
+            //transparent cells are written without any colors
+            if cell.transparent {
+                renderer.blank_cell(writer, cell)?;
+            } else {
+                renderer.cell(writer, cell)?;
             }
+        }
 
-            //convert pixels to a char/string
-            let mut ascii_char =
-                pixel::correlating_char(&pixels, &config.characters, config.invert, config.target);
+        //add outer border (right)
+        if config.border {
+            renderer.text(writer, "║")?;
+        }
 
-            //add border at the start
-            //this cannot be done in single if-else, since the image might only be a single pixel wide
-            if x == 0 {
-                //add outer border (left)
-                if config.border {
-                    ascii_char.insert(0, '║');
-                }
+        //add a break at line end
+        renderer.end_row(writer)?;
+    }
 
-                //add spacing for centering the image
-                if config.center_x {
-                    ascii_char.insert_str(0, &horizontal_spacing);
-                }
-            }
+    if config.border {
+        //add bottom part of border after conversion
+        log::trace!("Adding bottom border");
+        renderer.text(
+            writer,
+            &format!("{horizontal_spacing}╚{}╝", "═".repeat(columns as usize)),
+        )?;
+    }
 
-            //add a break at line end
-            if x == width - tile_width {
-                //add outer border (right)
-                if config.border {
-                    ascii_char.push('║');
-                }
+    renderer.footer(writer)?;
 
-                ascii_char.push('\n');
-            }
+    Ok(())
+}
+
+/// Calibrate the characters by how much of each one is covered by ink.
+///
+/// The coverage is measured using an embedded bitmap font. With [`Calibration::Sort`], the characters are sorted
+/// from the densest to the lightest one, equally dense characters keep their order. With [`Calibration::Linear`],
+/// they are also spaced, so the coverage decreases linearly. Characters, which are not part of the font, are removed.
+/// If none of them are part of it or [`Calibration::None`] is used, the characters are returned unchanged.
+///
+/// # Examples
+/// ```
+/// use artem::config::Calibration;
+///
+/// assert_eq!("@o. ", artem::calibrate_characters(". @o", Calibration::Sort));
+/// ```
+pub fn calibrate_characters(characters: &str, calibration: Calibration) -> String {
+    if calibration == Calibration::None {
+        return characters.to_string();
+    }
 
-            ascii_char
+    let mut measured: Vec<(char, f32)> = characters
+        .chars()
+        .filter_map(|char| {
+            let coverage = font::coverage(char);
+            if coverage.is_none() {
+                log::warn!(
+                    "Character '{char}' is not part of the embedded font, it will be removed"
+                );
+            }
+            Some((char, coverage?))
         })
-        .collect::<String>();
+        .collect();
+    if measured.is_empty() {
+        log::warn!("None of the characters can be calibrated, using them unchanged");
+        return characters.to_string();
+    }
+    //the sort is stable, so equally dense characters keep their order
+    measured.sort_by(|a, b| b.1.total_cmp(&a.1));
 
-    output.push_str(&target);
+    if calibration == Calibration::Sort {
+        return measured.iter().map(|(char, _)| char).collect();
+    }
 
-    if config.border {
-        //add spacing for centering
-        if config.center_x {
-            output.push_str(&horizontal_spacing);
+    let mut unique: Vec<(char, f32)> = Vec::with_capacity(measured.len());
+    for (char, coverage) in measured {
+        if !unique.iter().any(|(other, _)| *other == char) {
+            unique.push((char, coverage));
         }
+    }
+    let densest = unique[0].1;
+    let lightest = unique[unique.len() - 1].1;
+    let steps = unique.len().saturating_sub(1).max(1) as f32;
+    //each step of the ramp uses the character with the nearest coverage, so characters with
+    //nearly the same coverage are dropped and the ones with a large gap to the next one are repeated
+    (0..unique.len())
+        .map(|step| {
+            let target = densest - (densest - lightest) * step as f32 / steps;
+            let mut nearest = unique[0];
+            for candidate in &unique {
+                if (candidate.1 - target).abs() < (nearest.1 - target).abs() {
+                    nearest = *candidate;
+                }
+            }
+            nearest.0
+        })
+        .collect()
+}
 
-        //add bottom part of border after conversion
-        log::trace!("Adding bottom border");
-        output.push('╚');
-        output.push_str(&"═".repeat(columns as usize));
-        output.push('╝');
+#[cfg(test)]
+mod test_calibrate_characters {
+    use super::*;
+
+    #[test]
+    fn none_is_unchanged() {
+        assert_eq!(" .#:", calibrate_characters(" .#:", Calibration::None));
     }
 
-    //compare it, ignoring the enum value such as true, true
-    if matches!(&config.target, &TargetType::HtmlFile(true, true)) {
-        log::trace!("Adding html bottom part");
-        output.push_str(&target::html::html_bottom());
+    #[test]
+    fn sort_dense_first() {
+        assert_eq!("█#:. ", calibrate_characters(" .█:#", Calibration::Sort));
     }
 
-    if config.center_y && matches!(&config.target, &TargetType::Shell(true, true)) {
-        log::trace!("Adding vertical bottom spacing");
-        output.push_str(&spacing_vertical(if config.border {
-            //two rows are missing because the border takes up two lines
-            rows + 2
-        } else {
-            rows
-        }));
+    #[test]
+    fn sort_keeps_duplicates() {
+        assert_eq!("#..  ", calibrate_characters(". # .", Calibration::Sort));
+    }
+
+    #[test]
+    fn unknown_chars_are_removed() {
+        assert_eq!("# ", calibrate_characters(" 😀#", Calibration::Sort));
+        assert_eq!("😀", calibrate_characters("😀", Calibration::Linear));
     }
 
-    output
+    #[test]
+    fn linear_spacing() {
+        //the steps are 1, 0.75, 0.5, 0.25 and 0, the period is nearly as light as the space, so it is dropped,
+        //while the full block is repeated, since there is no character for the large gap to the half filled shade
+        assert_eq!("██▒░ ", calibrate_characters("█▒░. ", Calibration::Linear));
+    }
 }
 
 /// Return a spacer string, which can be used to center the ascii image in the middle of the terminal.
@@ -228,7 +864,7 @@ fn spacing_horizontal(width: u32) -> String {
 /// Return a spacer string, which can be used to center the ascii image in the middle of the terminal.
 ///
 /// When the terminal height is not existing, for example when the output is not a terminal, the returned string will be empty.
-fn spacing_vertical(height: u32) -> String {
+pub(crate) fn spacing_vertical(height: u32) -> String {
     let term_height = terminal_size::terminal_size()
         .map(|dimensions| dimensions.1 .0 as u32)
         .unwrap_or_default();
@@ -258,3 +894,143 @@ pub static SUPPORTS_TRUECOLOR: Lazy<bool> = Lazy::new(|| {
     std::env::var("COLORTERM")
         .is_ok_and(|value| value.contains("truecolor") || value.contains("24bit"))
 });
+
+/// Returns the [`ColorDepth`] supported by the terminal.
+///
+/// If [`SUPPORTS_TRUECOLOR`] is true, [`ColorDepth::TrueColor`] is used.
+/// Otherwise, if the `TERM` environment variable contains `256color` (for example `xterm-256color`),
+/// the 256 color palette ([`ColorDepth::Ansi256`]) is used.
+/// In all other cases it falls back to the 16 basic Ansi colors.
+///
+/// # Examples
+/// ```
+/// use artem::{config::ColorDepth, COLOR_DEPTH};
+/// # use std::env;
+///
+/// # env::set_var("COLORTERM", "truecolor");
+/// //only true when run in a shell that supports true color
+/// assert_eq!(ColorDepth::TrueColor, *COLOR_DEPTH);
+/// ```
+pub static COLOR_DEPTH: Lazy<ColorDepth> = Lazy::new(|| {
+    if *SUPPORTS_TRUECOLOR {
+        ColorDepth::TrueColor
+    } else if std::env::var("TERM").is_ok_and(|value| value.contains("256color")) {
+        ColorDepth::Ansi256
+    } else {
+        ColorDepth::Ansi16
+    }
+});
+
+#[cfg(test)]
+mod test_convert {
+    use image::RgbImage;
+
+    use super::*;
+    use crate::config::TargetType;
+
+    #[test]
+    fn empty_image_fails() {
+        let img = DynamicImage::ImageRgb8(RgbImage::new(0, 10));
+        assert!(matches!(
+            convert(img, &Config::default()),
+            Err(Error::ZeroDimensions(0, 10))
+        ));
+    }
+
+    #[test]
+    fn empty_characters_fail() {
+        let img = DynamicImage::ImageRgb8(RgbImage::new(10, 10));
+        let config = Config {
+            characters: String::new(),
+            ..Default::default()
+        };
+        assert!(matches!(convert(img, &config), Err(Error::EmptyCharacters)));
+    }
+
+    #[test]
+    fn large_scale_does_not_panic() {
+        let img = DynamicImage::ImageRgb8(RgbImage::new(10, 10));
+        let config = Config {
+            scale: 100f32,
+            target: TargetType::File,
+            ..Default::default()
+        };
+        assert_eq!("          \n".repeat(10), convert(img, &config).unwrap());
+    }
+
+    #[test]
+    fn transparent_tiles_are_blank() {
+        let img = DynamicImage::ImageRgba8(image::RgbaImage::from_fn(20, 10, |x, _| {
+            image::Rgba([255, 255, 255, if x < 10 { 0 } else { 255 }])
+        }));
+        let config = Config {
+            target_size: 2,
+            blank_transparent: true,
+            ..Default::default()
+        };
+        let grid = convert_to_grid(img, &config).unwrap();
+        let left = grid.get(0, 0).unwrap();
+        assert!(left.transparent);
+        assert_eq!(' ', left.character);
+        assert!(!grid.get(1, 0).unwrap().transparent);
+    }
+
+    #[test]
+    fn sextant_fallback_uses_quadrants() {
+        let img = image::open("assets/images/standard_test_img.png").unwrap();
+        let is_sextant = |cell: &Cell| ('\u{1FB00}'..='\u{1FB3B}').contains(&cell.character);
+        let mut config = Config {
+            render_mode: RenderMode::Sextant,
+            ..Default::default()
+        };
+        let sextants = convert_to_grid(img.clone(), &config).unwrap();
+        assert!(sextants.cells().iter().any(is_sextant));
+
+        config.sextant_fallback = true;
+        let quadrants = convert_to_grid(img, &config).unwrap();
+        assert!(!quadrants.cells().iter().any(is_sextant));
+        //the colors and size are the same, only the characters are replaced
+        assert_eq!(sextants.columns(), quadrants.columns());
+        for (sextant, quadrant) in sextants.cells().iter().zip(quadrants.cells()) {
+            assert_eq!(
+                pixel::sextant_to_quadrant(sextant.character).unwrap_or(sextant.character),
+                quadrant.character
+            );
+            assert_eq!(sextant.color, quadrant.color);
+        }
+    }
+
+    #[test]
+    fn writer_output_equals_string_output() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_fn(32, 32, |x, y| {
+            image::Rgb([(x * 8) as u8, (y * 8) as u8, 128])
+        }));
+        let config = Config {
+            border: true,
+            target: TargetType::HtmlFile(true, true),
+            ..Default::default()
+        };
+
+        let mut output = Vec::new();
+        convert_to_writer(img.clone(), &config, &mut output).unwrap();
+        assert_eq!(convert(img, &config).unwrap().as_bytes(), output);
+    }
+
+    #[test]
+    fn grid_matches_plain_output() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_fn(32, 32, |x, y| {
+            image::Rgb([(x * 8) as u8, (y * 8) as u8, 128])
+        }));
+        let config = Config {
+            target: TargetType::File,
+            ..Default::default()
+        };
+
+        let grid = convert_to_grid(img.clone(), &config).unwrap();
+        let text = grid
+            .iter_rows()
+            .map(|row| row.iter().map(|cell| cell.character).collect::<String>() + "\n")
+            .collect::<String>();
+        assert_eq!(convert(img, &config).unwrap(), text);
+    }
+}
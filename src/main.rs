@@ -10,21 +10,36 @@
 //! ```
 //! # let path = "./assets/images/standard_test_img.png";
 //! let image = image::open(path).expect("Failed to open image");
-//! let ascii_art = artem::convert(image, &artem::config::ConfigBuilder::new().build());
+//! let config = artem::config::ConfigBuilder::new().build().expect("Invalid config");
+//! let ascii_art = artem::convert(image, &config).expect("Failed to convert image");
 //! ```
 
 use std::{
+    borrow::Cow,
+    collections::HashSet,
     fs::File,
-    io::Write,
+    io::{BufWriter, Read, Write},
     num::NonZeroU32,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
-use artem::config::{self, ConfigBuilder, TargetType};
+use artem::config::{
+    self, Adjustment, Calibration, ColorDepth, ColorMetric, ConfigBuilder, Crop, Dithering,
+    Orientation, RenderMode, TargetType,
+};
 
 //import cli
+mod animation;
 mod cli;
 
+/// Input, which reads the image bytes from stdin instead of a file.
+const STDIN_INPUT: &str = "-";
+
 fn main() {
     //get args from cli
     let matches = cli::build_cli().get_matches();
@@ -51,18 +66,99 @@ fn main() {
     //at least one input must exist, so its safe to unwrap
     let input = matches.get_many::<String>("INPUT").unwrap();
 
-    let mut img_paths = Vec::with_capacity(input.len());
+    let output_dir = matches.get_one::<PathBuf>("output-dir");
+
+    //the patterns only filter the images found in directory inputs
+    let [include, exclude] = ["include", "exclude"].map(|id| {
+        matches
+            .get_many::<String>(id)
+            .unwrap_or_default()
+            .map(|pattern| {
+                glob::Pattern::new(pattern).unwrap_or_else(|err| {
+                    fatal_error(&format!("Invalid glob pattern {pattern}: {err}"), Some(64))
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+
+    //each input is kept together with the path of its output inside of the output directory
+    let mut inputs: Vec<(String, PathBuf)> = Vec::with_capacity(input.len());
 
     log::info!("Checking inputs");
     for value in input {
+        //stdin can only be read once, so it can not be used for multiple inputs
+        if value == STDIN_INPUT {
+            if inputs.iter().any(|(path, _)| path == STDIN_INPUT) {
+                fatal_error("Stdin can only be used as input once", Some(64));
+            }
+            log::debug!("Input is stdin");
+            inputs.push((value.to_owned(), PathBuf::from("stdin")));
+            continue;
+        }
+
         #[cfg(feature = "web_image")]
         if value.starts_with("http") {
             log::debug!("Input {} is a URL", value);
-            img_paths.push(value);
+            inputs.push((value.to_owned(), url_file_name(value)));
             continue;
         }
 
         let path = Path::new(value);
+        if path.is_dir() {
+            if output_dir.is_none() {
+                fatal_error(
+                    &format!("{value} is a directory, directories can only be converted with --output-dir"),
+                    Some(64),
+                );
+            }
+            log::debug!("Input {} is a directory", value);
+
+            let images = directory_images(path).unwrap_or_else(|err| {
+                fatal_error(
+                    &format!("Could not read directory {value}: {err}"),
+                    Some(66),
+                )
+            });
+            let count = inputs.len();
+            for image in images {
+                //the output keeps the path inside of the directory, so images with the same name do not overwrite each other
+                let relative_path = image.strip_prefix(path).unwrap_or(&image).to_path_buf();
+                let included = include.is_empty()
+                    || include
+                        .iter()
+                        .any(|pattern| pattern.matches_path(&relative_path));
+                if included
+                    && !exclude
+                        .iter()
+                        .any(|pattern| pattern.matches_path(&relative_path))
+                {
+                    inputs.push((image.to_string_lossy().into_owned(), relative_path));
+                }
+            }
+            log::debug!("Found {} images in {}", inputs.len() - count, value);
+            continue;
+        }
+
+        //expand glob patterns, which were not already expanded by the shell
+        if !path.exists() && value.contains(['*', '?', '[']) {
+            let paths = glob::glob(value).unwrap_or_else(|err| {
+                fatal_error(&format!("Invalid glob pattern {value}: {err}"), Some(64))
+            });
+            let files: Vec<PathBuf> = paths
+                .filter_map(Result::ok)
+                .filter(|path| path.is_file())
+                .collect();
+            if files.is_empty() {
+                fatal_error(&format!("No files match {value}"), Some(66));
+            }
+            log::debug!("Input {} matches {} files", value, files.len());
+            for file in files {
+                let name = file_name(&file);
+                inputs.push((file.to_string_lossy().into_owned(), name));
+            }
+            continue;
+        }
+
         //check if file exist and is a file (not a directory)
         if !path.exists() {
             fatal_error(&format!("File {value} does not exist"), Some(66));
@@ -70,8 +166,13 @@ fn main() {
             fatal_error(&format!("{value} is not a file"), Some(66));
         }
         log::debug!("Input {} is a file", value);
-        img_paths.push(value);
+        inputs.push((value.to_owned(), file_name(path)));
+    }
+
+    if inputs.is_empty() {
+        fatal_error("No images found in the inputs", Some(66));
     }
+    let img_paths: Vec<&String> = inputs.iter().map(|(path, _)| path).collect();
 
     //density char map
     let density = match matches
@@ -96,6 +197,15 @@ fn main() {
     log::debug!("Characters used: '{density}'");
     config_builder.characters(density.to_string());
 
+    //sort and space the characters by their measured brightness
+    let calibration = match matches.get_one::<String>("calibrate").map(String::as_str) {
+        Some("sort") => Calibration::Sort,
+        Some("linear") => Calibration::Linear,
+        _ => Calibration::None,
+    };
+    log::debug!("Calibration: {calibration:?}");
+    config_builder.calibration(calibration);
+
     //set the default resizing dimension to width
     config_builder.dimension(config::ResizingDimension::Width);
 
@@ -134,14 +244,12 @@ fn main() {
     config_builder.target_size(NonZeroU32::new(target_size).unwrap()); //safe to unwrap, since it is clamped before
 
     //best ratio between height and width is 0.43
-    let Some(scale) = matches
-        .get_one::<f32>("scale")
-        .map(|scale| {
-            scale.clamp(
-                0.1f32, //a negative or 0 scale is not allowed
-                1f32,   //even a scale above 0.43 is not looking good
-            )
-        }) else {
+    let Some(scale) = matches.get_one::<f32>("scale").map(|scale| {
+        scale.clamp(
+            0.1f32, //a negative or 0 scale is not allowed
+            1f32,   //even a scale above 0.43 is not looking good
+        )
+    }) else {
         fatal_error("Could not work with ratio input value", Some(65));
     };
     log::debug!("Scale: {scale}");
@@ -154,6 +262,55 @@ fn main() {
     let background_color = matches.get_flag("background-color");
     log::debug!("BackgroundColor is set to: {background_color}");
 
+    //get the number of colors, either from the args or detected from the environment
+    let color_depth = match matches.get_one::<String>("color-depth").map(String::as_str) {
+        Some("16") => ColorDepth::Ansi16,
+        Some("256") => ColorDepth::Ansi256,
+        Some("truecolor") => ColorDepth::TrueColor,
+        _ => *artem::COLOR_DEPTH,
+    };
+    log::debug!("Color depth: {color_depth:?}");
+    config_builder.color_depth(color_depth);
+
+    //get the metric for reducing colors to the 16 or 256 color palette
+    let color_metric = match matches
+        .get_one::<String>("color-metric")
+        .map(String::as_str)
+    {
+        Some("euclidean") => ColorMetric::Euclidean,
+        Some("redmean") => ColorMetric::Redmean,
+        Some("ciede2000") => ColorMetric::Ciede2000,
+        _ => ColorMetric::Cie76,
+    };
+    log::debug!("Color metric: {color_metric:?}");
+    config_builder.color_metric(color_metric);
+
+    //get the mode used to render the pixels of each character
+    let render_mode = match matches.get_one::<String>("mode").map(String::as_str) {
+        Some("half-block") => RenderMode::HalfBlock,
+        Some("braille") => RenderMode::Braille,
+        Some("quadrant") => RenderMode::Quadrant,
+        Some("sextant") => RenderMode::Sextant,
+        Some("shape") => RenderMode::Shape,
+        _ => RenderMode::Ascii,
+    };
+    log::debug!("Render mode: {render_mode:?}");
+    config_builder.render_mode(render_mode);
+
+    let sextant_fallback = matches.get_flag("sextant-fallback");
+    config_builder.sextant_fallback(sextant_fallback);
+    log::debug!("Sextant fallback: {sextant_fallback}");
+
+    //get the dithering method for the characters and reduced colors
+    let dither = match matches.get_one::<String>("dither").map(String::as_str) {
+        Some("floyd-steinberg") => Dithering::FloydSteinberg,
+        Some("atkinson") => Dithering::Atkinson,
+        Some("bayer") => Dithering::Bayer,
+        _ => Dithering::None,
+    };
+    log::debug!("Dithering: {dither:?}");
+    config_builder.dither(dither);
+
     //check if no colors should be used or the if a output file will be used
     //since text documents don`t support ansi ascii colors
     let color = if matches.get_flag("no-color") {
@@ -166,19 +323,31 @@ fn main() {
             //still set colors  to true, since grayscale has different gray tones
         }
 
-        //print colored terminal conversion, this should already respect truecolor support/use ansi colors if not supported
+        //print colored terminal conversion, using the given or detected color depth
         log::info!("Using colored ascii");
-        if !*artem::SUPPORTS_TRUECOLOR {
-            if background_color {
-                log::warn!("Background flag will be ignored, since truecolor is not supported.")
+        match color_depth {
+            ColorDepth::TrueColor => log::info!("Using truecolor ascii"),
+            ColorDepth::Ansi256 => log::info!("Using 256 color ascii"),
+            ColorDepth::Ansi16 => {
+                if background_color {
+                    log::warn!("Background flag will be ignored, since only 16 colors are used.")
+                }
+                log::warn!("Truecolor is not supported. Using ansi color.")
             }
-            log::warn!("Truecolor is not supported. Using ansi color.")
-        } else {
-            log::info!("Using truecolor ascii")
         }
         true
     };
 
+    //get flags for compacting the colored output
+    let compact = matches.get_flag("compact");
+    config_builder.compact(compact);
+    log::debug!("Compact: {compact}");
+
+    if let Some(tolerance) = matches.get_one::<u8>("color-tolerance") {
+        log::debug!("Color tolerance: {tolerance}");
+        config_builder.color_tolerance(*tolerance);
+    }
+
     //get flag for border around image
     let border = matches.get_flag("border");
     config_builder.border(border);
@@ -203,6 +372,79 @@ fn main() {
     config_builder.center_y(center_y);
     log::debug!("Center Y-Axis: {center_y}");
 
+    if let Some((percentage, [x, y, width, height])) = matches.get_one::<(bool, [f32; 4])>("crop") {
+        let crop = if *percentage {
+            Crop::Percentage {
+                x: *x,
+                y: *y,
+                width: *width,
+                height: *height,
+            }
+        } else {
+            Crop::Pixels {
+                x: *x as u32,
+                y: *y as u32,
+                width: *width as u32,
+                height: *height as u32,
+            }
+        };
+        log::debug!("Crop: {crop:?}");
+        config_builder.crop(Some(crop));
+    }
+
+    if let Some(rotation) = matches.get_one::<f32>("rotate") {
+        log::debug!("Rotation: {rotation}");
+        config_builder.rotation(*rotation);
+    }
+
+    if let Some(color) = matches.get_one::<[u8; 3]>("background-fill") {
+        log::debug!("Background fill: {color:?}");
+        config_builder.background_fill(Some(image::Rgb(*color)));
+    }
+
+    let blank_transparent = matches.get_flag("blank-transparent");
+    config_builder.blank_transparent(blank_transparent);
+    log::debug!("Blank transparent: {blank_transparent}");
+
+    //adjustments are applied in the order they were given
+    let mut adjustments: Vec<(usize, Adjustment)> = Vec::new();
+    for (id, adjustment) in [
+        (
+            "brightness",
+            Adjustment::Brightness as fn(f32) -> Adjustment,
+        ),
+        ("contrast", Adjustment::Contrast),
+        ("gamma", Adjustment::Gamma),
+        ("saturation", Adjustment::Saturation),
+        ("hue", Adjustment::Hue),
+        ("sharpen", |amount| Adjustment::Sharpen {
+            sigma: 1f32,
+            amount,
+        }),
+    ] {
+        if let (Some(values), Some(indices)) = (matches.get_many::<f32>(id), matches.indices_of(id))
+        {
+            adjustments.extend(indices.zip(values.map(|value| adjustment(*value))));
+        }
+    }
+    for (id, adjustment) in [
+        ("auto-levels", Adjustment::AutoLevels),
+        ("equalize", Adjustment::Equalize),
+    ] {
+        if matches.get_flag(id) {
+            if let Some(index) = matches.index_of(id) {
+                adjustments.push((index, adjustment));
+            }
+        }
+    }
+    adjustments.sort_by_key(|(index, _)| *index);
+    let adjustments: Vec<Adjustment> = adjustments
+        .into_iter()
+        .map(|(_, adjustment)| adjustment)
+        .collect();
+    log::debug!("Adjustments: {adjustments:?}");
+    config_builder.adjustments(adjustments);
+
     //get flag for creating an outline
     let outline = matches.get_flag("outline");
     config_builder.outline(outline);
@@ -216,14 +458,42 @@ fn main() {
         if hysteresis {
             log::warn!("Using hysteresis might result in an worse looking ascii image than only using --outline")
         }
+
+        if let Some(low) = matches.get_one::<f32>("outline-low") {
+            config_builder.outline_low(*low);
+            log::debug!("Outline low threshold: {low}");
+        }
+        if let Some(high) = matches.get_one::<f32>("outline-high") {
+            config_builder.outline_high(*high);
+            log::debug!("Outline high threshold: {high}");
+        }
+        if let Some(sigma) = matches.get_one::<f32>("outline-sigma") {
+            config_builder.outline_sigma(Some(*sigma));
+            log::debug!("Outline sigma: {sigma}");
+        }
+
+        let non_max_suppression = matches.get_flag("non-max-suppression");
+        config_builder.non_max_suppression(non_max_suppression);
+        log::debug!("Non-maximum suppression: {non_max_suppression}");
+
+        let edge_characters = matches.get_flag("edge-characters");
+        config_builder.edge_characters(edge_characters);
+        log::debug!("Edge characters: {edge_characters}");
     }
 
-    //get output file extension for specific output, default to plain text
-    if let Some(output_file) = matches.get_one::<PathBuf>("output-file") {
+    //get output file extension for specific output or the format of the output directory, default to plain text
+    let file_extension = if let Some(output_file) = matches.get_one::<PathBuf>("output-file") {
         log::debug!("Output-file: {}", output_file.to_str().unwrap());
+        Some(output_file.extension().and_then(std::ffi::OsStr::to_str))
+    } else if let Some(output_dir) = output_dir {
+        log::debug!("Output-dir: {}", output_dir.display());
+        Some(matches.get_one::<String>("format").map(String::as_str))
+    } else {
+        None
+    };
 
+    if let Some(file_extension) = file_extension {
         //check file extension
-        let file_extension = output_file.extension().and_then(std::ffi::OsStr::to_str);
         log::debug!("FileExtension: {:?}", file_extension);
 
         config_builder.target(match file_extension {
@@ -231,6 +501,24 @@ fn main() {
                 log::debug!("Target: Html-File");
                 TargetType::HtmlFile(color, background_color)
             }
+            Some("svg") => {
+                log::debug!("Target: Svg-File");
+                TargetType::SvgFile(color, background_color)
+            }
+            Some("png") => {
+                log::debug!("Target: Png-File");
+                TargetType::PngFile(color, background_color)
+            }
+            Some("cast") => {
+                log::debug!("Target: Asciinema-File");
+
+                //the recording is replayed in a terminal, so it uses the same output as .ansi files
+                if matches.get_flag("no-color") {
+                    TargetType::File
+                } else {
+                    TargetType::AnsiFile(background_color)
+                }
+            }
             Some("ansi") | Some("ans") => {
                 log::debug!("Target: Ansi-File");
 
@@ -239,7 +527,7 @@ fn main() {
                     log::warn!("The --no-color argument conflicts with the target file type. Falling back to plain text file without colors.");
                     TargetType::File
                 } else {
-                    if !*artem::SUPPORTS_TRUECOLOR {
+                    if color_depth != ColorDepth::TrueColor {
                         log::warn!("truecolor is disabled, output file will not use truecolor chars")
                     }
                     TargetType::AnsiFile(background_color)
@@ -250,7 +538,7 @@ fn main() {
 
                 if !matches.get_flag("no-color") {
                     //warn user that output is not colored
-                    log::warn!("Filetype does not support using colors. For colored output file please use either .html or .ansi files");
+                    log::warn!("Filetype does not support using colors. For colored output file please use either .html, .svg, .png or .ansi files");
                 }
                 TargetType::File
             }
@@ -260,81 +548,645 @@ fn main() {
         config_builder.target(TargetType::Shell(color, background_color));
     }
 
-    let config = config_builder.build();
-    let mut output = img_paths
-        .iter()
-        .map(|path| load_image(path))
-        .filter(|img| img.height() != 0 || img.width() != 0)
-        .map(|img| artem::convert(img, &config))
-        .collect::<String>();
+    //get the font family for svg files
+    if let Some(font_family) = matches.get_one::<String>("font-family") {
+        log::debug!("Font family: {font_family}");
+        config_builder.font_family(font_family.to_owned());
+    }
 
-    //remove last linebreak, we cannot use `.trim_end()` here
-    //as it may end up remove whitespace that is part of the image
-    if output.ends_with('\n') {
-        output.remove(output.len() - 1);
+    //get the size of a character for png files
+    if let Some(cell_size) = matches.get_one::<(u32, u32)>("cell-size") {
+        log::debug!("Cell size: {}x{}", cell_size.0, cell_size.1);
+        config_builder.cell_size(*cell_size);
+    }
+
+    let config = match config_builder.build() {
+        Ok(config) => config,
+        Err(err) => fatal_error(&err.to_string(), Some(65)),
+    };
+
+    //the frames of animations are either played or exported with the same delay
+    let frame_delay = matches
+        .get_one::<f32>("fps")
+        .map(|fps| Duration::from_secs_f32(1f32 / fps));
+
+    //convert each input into its own file in the output directory
+    if let Some(output_dir) = output_dir {
+        log::info!("Writing outputs to output directory");
+        //the format has a default value, so it is always present
+        let format = matches.get_one::<String>("format").unwrap();
+        convert_batch(&inputs, output_dir, format, &config, frame_delay);
+        return;
     }
 
     //create and write to output file
     if let Some(output_file) = matches.get_one::<PathBuf>("output-file") {
         log::info!("Writing output to output file");
 
-        let Ok(mut file) = File::create(output_file) else {
+        let Ok(file) = File::create(output_file) else {
             fatal_error("Could not create output file", Some(73));
         };
 
         log::trace!("Created output file");
-        let Ok(bytes_count) = file.write(output.as_bytes()) else {
-                fatal_error("Could not write to output file", Some(74));
-        };
+        let mut writer = OutputWriter::new(BufWriter::new(file), config.target);
+        if output_file
+            .extension()
+            .is_some_and(|extension| extension == "cast")
+        {
+            export_cast(&img_paths, &config, frame_delay, &mut writer)
+                .unwrap_or_else(|err| err.exit());
+        } else {
+            convert_images(&img_paths, &config, &mut writer);
+        }
+        if writer.flush().is_err() {
+            fatal_error("Could not write to output file", Some(74));
+        }
         log::info!("Written ascii chars to output file");
-        println!("Written {} bytes to {}", bytes_count, output_file.display())
+        println!(
+            "Written {} bytes to {}",
+            writer.bytes_written,
+            output_file.display()
+        )
     } else {
+        //play the frames of animated images in place
+        if matches.get_flag("animate") {
+            log::info!("Playing animations");
+            animate_images(&img_paths, &config, matches.get_flag("loop"), frame_delay);
+            return;
+        }
+
         //print the ascii img to the terminal
         log::info!("Printing output");
-        println!("{}", output);
+        let mut writer = OutputWriter::new(std::io::stdout().lock(), config.target);
+        convert_images(&img_paths, &config, &mut writer);
+        //end the output with a single line break, like `println!` would
+        if writer.inner.write_all(b"\n").is_err() || writer.flush().is_err() {
+            fatal_error("Could not write to output file", Some(74));
+        }
     }
 }
 
-/// Return the image from the specified path.
+/// Convert all images and write them to the given writer.
 ///
-/// Loads the image from the specified path.
-/// If the path is a url and the web_image feature is enabled,
-/// the image will be downloaded and opened from memory.
+/// The images are loaded and converted one after another, so only a single image needs to be kept in memory.
+/// Empty images are skipped, if a conversion fails the program will exit.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let mut stdout = std::io::stdout().lock();
+/// convert_images(&img_paths, &config, &mut stdout);
+/// ```
+fn convert_images(img_paths: &[&String], config: &artem::config::Config, writer: &mut impl Write) {
+    for path in img_paths {
+        convert_image(path, config, writer).unwrap_or_else(|err| err.exit());
+    }
+}
+
+/// Convert a single image and write it to the given writer.
+///
+/// Empty images are skipped.
+///
+/// # Errors
+/// Returns an error if the image can not be loaded, converted or written.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// convert_image("../examples/abraham_lincoln.jpg", &config, &mut writer)?;
+/// ```
+fn convert_image(
+    path: &str,
+    config: &artem::config::Config,
+    writer: &mut impl Write,
+) -> Result<(), InputError> {
+    let (img, orientation) = load_image(path)?;
+    if img.height() == 0 && img.width() == 0 {
+        log::warn!("Skipping empty image {path}");
+        return Ok(());
+    }
+
+    artem::convert_to_writer(img, &oriented_config(config, orientation), writer).map_err(|err| {
+        match err {
+            artem::Error::Io(_) => InputError::new(err.to_string(), 74),
+            _ => InputError::new(err.to_string(), 65),
+        }
+    })
+}
+
+/// Return the config with the orientation of the image, which is different for each image.
+///
+/// The config is only cloned, if the orientation differs from the one in the config.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let config = oriented_config(&config, Orientation::Rotate90);
+/// assert_eq!(Orientation::Rotate90, config.orientation);
+/// ```
+fn oriented_config(
+    config: &artem::config::Config,
+    orientation: Orientation,
+) -> Cow<'_, artem::config::Config> {
+    if orientation == config.orientation {
+        return Cow::Borrowed(config);
+    }
+    log::info!("Correcting orientation {orientation:?}");
+    Cow::Owned(artem::config::Config {
+        orientation,
+        ..config.clone()
+    })
+}
+
+/// Convert each image into its own file in the output directory.
+///
+/// The output files keep the relative path of their input, with the extension of the `format`.
+/// A failed image does not stop the other conversions, instead all failures are listed in a summary at the end,
+/// after which the program exits with the code of the first failure.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// convert_batch(&inputs, Path::new("ascii"), "html", &config, None);
+/// ```
+fn convert_batch(
+    inputs: &[(String, PathBuf)],
+    output_dir: &Path,
+    format: &str,
+    config: &artem::config::Config,
+    frame_delay: Option<Duration>,
+) {
+    let mut outputs = HashSet::with_capacity(inputs.len());
+    let mut failures = Vec::new();
+    for (path, name) in inputs {
+        let output = output_dir.join(name).with_extension(format);
+        if !outputs.insert(output.clone()) {
+            log::warn!(
+                "Multiple inputs are written to {}, only the last one is kept",
+                output.display()
+            );
+        }
+
+        log::info!("Converting {} to {}", path, output.display());
+        match convert_to_file(path, &output, format, config, frame_delay) {
+            Ok(bytes) => log::info!("Written {} bytes to {}", bytes, output.display()),
+            Err(err) => {
+                log::error!("Failed to convert {}: {}", path, err.message);
+                failures.push((path, err));
+            }
+        }
+    }
+
+    println!(
+        "Converted {} of {} images to {}",
+        inputs.len() - failures.len(),
+        inputs.len(),
+        output_dir.display()
+    );
+    for (path, err) in &failures {
+        println!("Failed to convert {}: {}", path, err.message);
+    }
+    if let Some((_, err)) = failures.first() {
+        fatal_error(
+            &format!(
+                "Failed to convert {} of {} images",
+                failures.len(),
+                inputs.len()
+            ),
+            Some(err.code),
+        );
+    }
+}
+
+/// Convert a single image and write it to the output file.
+///
+/// The output is written to a temporary file next to the output file, which only replaces it once the conversion succeeded,
+/// so a failed image does not leave an incomplete file behind.
+/// Missing parent directories of the output file are created.
+///
+/// # Errors
+/// Returns an error if the image can not be converted or the output file can not be written.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let bytes = convert_to_file("image.png", Path::new("ascii/image.txt"), "txt", &config, None)?;
+/// ```
+fn convert_to_file(
+    path: &String,
+    output: &Path,
+    format: &str,
+    config: &artem::config::Config,
+    frame_delay: Option<Duration>,
+) -> Result<usize, InputError> {
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| {
+            InputError::new(
+                format!("Could not create directory {}: {err}", parent.display()),
+                73,
+            )
+        })?;
+    }
+
+    //the temporary file is hidden and in the same directory, so it can be renamed to the output file
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(output.file_name().unwrap_or_default());
+    temp_name.push(".tmp");
+    let temp_file = output.with_file_name(temp_name);
+    let file = File::create(&temp_file).map_err(|err| {
+        InputError::new(
+            format!("Could not create {}: {err}", temp_file.display()),
+            73,
+        )
+    })?;
+
+    let mut writer = OutputWriter::new(BufWriter::new(file), config.target);
+    let result = if format == "cast" {
+        export_cast(
+            std::slice::from_ref(&path),
+            config,
+            frame_delay,
+            &mut writer,
+        )
+    } else {
+        convert_image(path, config, &mut writer)
+    }
+    .and_then(|_| {
+        writer.flush().map_err(|err| {
+            InputError::new(
+                format!("Could not write to {}: {err}", temp_file.display()),
+                74,
+            )
+        })
+    });
+    let bytes_written = writer.bytes_written;
+    //an open file can not be renamed on windows
+    drop(writer);
+
+    let result = result.and_then(|_| {
+        std::fs::rename(&temp_file, output).map_err(|err| {
+            InputError::new(
+                format!("Could not write to {}: {err}", output.display()),
+                74,
+            )
+        })
+    });
+    if result.is_err() {
+        //the temporary file might already be removed or never written, so there is nothing to report
+        let _ = std::fs::remove_file(&temp_file);
+    }
+    result.map(|_| bytes_written)
+}
+
+/// Return all images in the directory and its subdirectories, sorted by their path.
+///
+/// Only files with the extension of a supported image format are returned.
+/// Symbolic links to directories are not followed, so they can not create endless loops.
+///
+/// # Errors
+/// Returns an error if a directory can not be read.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let images = directory_images(Path::new("assets/images"))?;
+/// ```
+fn directory_images(directory: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = std::fs::read_dir(directory)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<PathBuf>>>()?;
+    paths.sort();
+
+    let mut images = Vec::new();
+    for path in paths {
+        if std::fs::symlink_metadata(&path)?.is_dir() {
+            images.extend(directory_images(&path)?);
+        } else if path.is_file() && image::ImageFormat::from_path(&path).is_ok() {
+            images.push(path);
+        }
+    }
+    Ok(images)
+}
+
+/// Return the file name of the path, which is used as the name of its output.
 ///
 /// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(PathBuf::from("image.png"), file_name(Path::new("images/image.png")));
 /// ```
-/// let image = load_image("../examples/abraham_lincoln.jpg")
+fn file_name(path: &Path) -> PathBuf {
+    PathBuf::from(path.file_name().unwrap_or(path.as_os_str()))
+}
+
+/// Return the last segment of the url without its query, which is used as the name of its output.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(PathBuf::from("image.png"), url_file_name("https://example.com/image.png?size=large"));
+/// ```
+#[cfg(feature = "web_image")]
+fn url_file_name(url: &str) -> PathBuf {
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    match url.trim_end_matches('/').rsplit('/').next() {
+        Some(name) if !name.is_empty() => PathBuf::from(name),
+        _ => PathBuf::from("image"),
+    }
+}
+
+/// Convert all frames of each image and play them in the terminal.
+///
+/// The frames of an image are all converted before the playback starts, so the conversion does not slow it down.
+/// Like single images, the frames are rotated by the orientation stored in the EXIF metadata.
+/// The `frame_delay` overrides the delays of the frames, if it is set.
+/// Ctrl-C stops the conversion or the playback, the cursor is restored before the program exits.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// animate_images(&img_paths, &config, true, None);
+/// ```
+fn animate_images(
+    img_paths: &[&String],
+    config: &artem::config::Config,
+    repeat: bool,
+    frame_delay: Option<Duration>,
+) {
+    //the playback is only stopped, so the cursor can still be shown again
+    let stopped = Arc::new(AtomicBool::new(false));
+    let handler_stopped = Arc::clone(&stopped);
+    if let Err(err) = ctrlc::set_handler(move || handler_stopped.store(true, Ordering::Relaxed)) {
+        log::warn!("Failed to handle Ctrl-C, the cursor might not be restored: {err}");
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    for path in img_paths {
+        let bytes = read_image(path).unwrap_or_else(|err| err.exit());
+        let frames = match animation::decode_frames(&bytes) {
+            Ok(frames) => frames,
+            Err(err) => fatal_error(&err.to_string(), Some(66)),
+        };
+        log::debug!("Decoded {} frames", frames.len());
+        let config = oriented_config(config, exif_orientation(&mut std::io::Cursor::new(&bytes)));
+
+        let mut converted_frames = Vec::with_capacity(frames.len());
+        for (img, delay) in frames {
+            //the handler keeps Ctrl-C from exiting, so the conversion has to be stopped as well
+            if stopped.load(Ordering::Relaxed) {
+                log::info!("Stopped conversion");
+                return;
+            }
+            match artem::convert(img, &config) {
+                Ok(frame) => converted_frames.push((frame, frame_delay.unwrap_or(delay))),
+                Err(err) => fatal_error(&err.to_string(), Some(65)),
+            }
+        }
+
+        if animation::play(&converted_frames, repeat, &stopped, &mut stdout).is_err() {
+            fatal_error("Could not write to the terminal", Some(74));
+        }
+        if stopped.load(Ordering::Relaxed) {
+            log::info!("Stopped playback");
+            break;
+        }
+    }
+}
+
+/// Convert all frames of each image and write them as a single asciinema recording.
+///
+/// The size of the recording is the size of the first frame, including its border.
+/// The frames are not centered, since the recording is played back in its own size instead of the current terminal.
+/// Like single images, the frames are rotated by the orientation stored in the EXIF metadata.
+/// The `frame_delay` overrides the delays of the frames, if it is set.
+///
+/// # Errors
+/// Returns an error if an image can not be loaded, converted or written.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// export_cast(&img_paths, &config, None, &mut writer)?;
+/// ```
+fn export_cast(
+    img_paths: &[&String],
+    config: &artem::config::Config,
+    frame_delay: Option<Duration>,
+    writer: &mut impl Write,
+) -> Result<(), InputError> {
+    //the centering depends on the terminal size, which would not match the size of the recording
+    let config = artem::config::Config {
+        center_x: false,
+        center_y: false,
+        ..config.clone()
+    };
+    let mut converted_frames = Vec::new();
+    let mut size = None;
+    for path in img_paths {
+        let bytes = read_image(path)?;
+        let frames =
+            animation::decode_frames(&bytes).map_err(|err| InputError::new(err.to_string(), 66))?;
+        log::debug!("Decoded {} frames", frames.len());
+        let config = oriented_config(&config, exif_orientation(&mut std::io::Cursor::new(&bytes)));
+
+        for (img, delay) in frames {
+            let grid = artem::convert_to_grid(img, &config)
+                .map_err(|err| InputError::new(err.to_string(), 65))?;
+            //the border takes up two additional rows and columns
+            let border = if config.border { 2 } else { 0 };
+            size.get_or_insert((grid.columns() + border, grid.rows() + border));
+
+            let mut frame = Vec::new();
+            artem::write_grid(&grid, &config, &mut frame)
+                .map_err(|err| InputError::new(err.to_string(), 74))?;
+            //the output is only ever created from strings, so it is always valid utf-8
+            let frame = String::from_utf8(frame).expect("Output is not valid UTF-8");
+            converted_frames.push((frame, frame_delay.unwrap_or(delay)));
+        }
+    }
+
+    animation::write_cast(&converted_frames, size.unwrap_or_default(), writer)
+        .map_err(|_| InputError::new("Could not write to output file", 74))
+}
+
+/// Writer, which does not write the last line break of the output.
+///
+/// Since the output is written row by row, it is unknown which line break will be the last one.
+/// Therefore a line break at the end of a write is held back until more data is written.
+/// We cannot simply trim the output, as it may end up remove whitespace that is part of the image.
+/// Binary outputs, like png images, are written unchanged, since a trailing `0x0A` byte is part of their data.
+struct OutputWriter<W: Write> {
+    inner: W,
+    //only text outputs end with a line break, which should not be written
+    hold_line_break: bool,
+    pending_line_break: bool,
+    bytes_written: usize,
+}
+
+impl<W: Write> OutputWriter<W> {
+    fn new(inner: W, target: TargetType) -> Self {
+        Self {
+            inner,
+            hold_line_break: !matches!(target, TargetType::PngFile(..)),
+            pending_line_break: false,
+            bytes_written: 0,
+        }
+    }
+}
+
+impl<W: Write> Write for OutputWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if !self.hold_line_break {
+            self.inner.write_all(buf)?;
+            self.bytes_written += buf.len();
+            return Ok(buf.len());
+        }
+
+        //more data follows, so the previous line break was not the last one
+        if self.pending_line_break {
+            self.inner.write_all(b"\n")?;
+            self.bytes_written += 1;
+            self.pending_line_break = false;
+        }
+
+        let (data, line_break) = match buf.strip_suffix(b"\n") {
+            Some(data) => (data, true),
+            None => (buf, false),
+        };
+        self.inner.write_all(data)?;
+        self.bytes_written += data.len();
+        self.pending_line_break = line_break;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Return the bytes of the image from the specified path.
+///
+/// If the path is a url and the web_image feature is enabled, the image will be downloaded.
+/// If the path is `-`, the image is read from stdin.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let bytes = read_image("../examples/abraham_lincoln.jpg")?;
 /// ```
-fn load_image(path: &str) -> image::DynamicImage {
+fn read_image(path: &str) -> Result<Vec<u8>, InputError> {
     #[cfg(feature = "web_image")]
     if path.starts_with("http") {
-        log::info!("Started to download image from: {}", path);
-        let now = std::time::Instant::now();
-        let Ok(resp) = ureq::get(path).call() else {
-                    fatal_error(
-                        &format!("Failed to load image bytes from {}", path),
-                        Some(66),
-                    );
-                };
-
-        //get bytes of the images
-        let mut bytes: Vec<u8> = Vec::new();
-        resp.into_reader()
+        return download_image(path);
+    }
+
+    if path == STDIN_INPUT {
+        log::info!("Reading image from stdin");
+        let mut bytes = Vec::new();
+        std::io::stdin()
+            .lock()
             .read_to_end(&mut bytes)
-            .expect("Failed to read bytes");
-        log::info!("Downloading took {:3} ms", now.elapsed().as_millis());
+            .map_err(|err| {
+                InputError::new(format!("Failed to read image from stdin: {err}"), 66)
+            })?;
+        return Ok(bytes);
+    }
 
-        log::debug!("Opening downloaded image from memory");
-        return match image::load_from_memory(&bytes) {
-            Ok(img) => img,
-            Err(err) => fatal_error(&err.to_string(), Some(66)),
-        };
+    log::info!("Reading image");
+    std::fs::read(path).map_err(|err| InputError::new(err.to_string(), 66))
+}
+
+/// Download the image from the url and return its bytes.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let bytes = download_image("https://example.com/image.png")?;
+/// ```
+#[cfg(feature = "web_image")]
+fn download_image(url: &str) -> Result<Vec<u8>, InputError> {
+    log::info!("Started to download image from: {}", url);
+    let now = std::time::Instant::now();
+    let Ok(resp) = ureq::get(url).call() else {
+        return Err(InputError::new(
+            format!("Failed to load image bytes from {}", url),
+            66,
+        ));
+    };
+
+    //get bytes of the images
+    let mut bytes: Vec<u8> = Vec::new();
+    resp.into_reader().read_to_end(&mut bytes).map_err(|err| {
+        InputError::new(format!("Failed to read image bytes from {url}: {err}"), 66)
+    })?;
+    log::info!("Downloading took {:3} ms", now.elapsed().as_millis());
+    Ok(bytes)
+}
+
+/// Return the image from the specified path, with the orientation stored in its EXIF metadata.
+///
+/// Loads the image from the specified path.
+/// If the path is a url and the web_image feature is enabled,
+/// the image will be downloaded and opened from memory.
+/// If the path is `-`, the image is read from stdin and opened from memory.
+///
+/// # Examples
+/// ```
+/// let (image, orientation) = load_image("../examples/abraham_lincoln.jpg")?;
+/// ```
+fn load_image(path: &str) -> Result<(image::DynamicImage, Orientation), InputError> {
+    //downloaded images and stdin are only available as bytes, so the format is guessed from them
+    if path == STDIN_INPUT || (cfg!(feature = "web_image") && path.starts_with("http")) {
+        let bytes = read_image(path)?;
+        log::debug!("Opening image from memory");
+        let img =
+            image::load_from_memory(&bytes).map_err(|err| InputError::new(err.to_string(), 66))?;
+        return Ok((img, exif_orientation(&mut std::io::Cursor::new(&bytes))));
     }
 
     log::info!("Opening image");
-    match image::open(path) {
-        Ok(img) => img,
-        Err(err) => fatal_error(&err.to_string(), Some(66)),
+    let img = image::open(path).map_err(|err| InputError::new(err.to_string(), 66))?;
+    let orientation = match File::open(path) {
+        Ok(file) => exif_orientation(&mut std::io::BufReader::new(file)),
+        Err(_) => Orientation::default(),
+    };
+    Ok((img, orientation))
+}
+
+/// Return the orientation stored in the EXIF metadata of an image.
+///
+/// If the image has no EXIF metadata or no valid orientation, it is assumed to be upright.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let orientation = exif_orientation(&mut std::io::Cursor::new(&bytes));
+/// ```
+fn exif_orientation(reader: &mut (impl std::io::BufRead + std::io::Seek)) -> Orientation {
+    let exif = match exif::Reader::new().read_from_container(reader) {
+        Ok(exif) => exif,
+        Err(err) => {
+            log::debug!("No EXIF orientation: {err}");
+            return Orientation::default();
+        }
+    };
+    let orientation = exif
+        .get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+        .and_then(|field| field.value.get_uint(0))
+        .and_then(Orientation::from_exif)
+        .unwrap_or_default();
+    log::debug!("EXIF orientation: {orientation:?}");
+    orientation
+}
+
+/// Error of a single input, which either ends the program or is listed in the summary of a batch conversion.
+struct InputError {
+    message: String,
+    code: i32,
+}
+
+impl InputError {
+    fn new(message: impl Into<String>, code: i32) -> Self {
+        Self {
+            message: message.into(),
+            code,
+        }
+    }
+
+    /// Exit the program with the message and code of the error, see [`fatal_error`].
+    fn exit(self) -> ! {
+        fatal_error(&self.message, Some(self.code))
     }
 }
 
@@ -0,0 +1,57 @@
+//! Iteration, which is parallelized when the `parallel` feature is enabled.
+
+/// Returns the results of the function for all indices from `0` to `len`, in the order of their indices.
+///
+/// With the `parallel` feature, the indices are split between the threads of the global rayon thread pool,
+/// otherwise they are computed one after another. Since the order is always kept, both return the same result,
+/// as long as the function does not depend on the results for other indices.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let squares = map_indices(4, |index| index * index);
+/// assert_eq!(vec![0, 1, 4, 9], squares);
+/// ```
+#[cfg(feature = "parallel")]
+pub(crate) fn map_indices<T: Send>(
+    len: usize,
+    function: impl Fn(usize) -> T + Sync + Send,
+) -> Vec<T> {
+    use rayon::prelude::*;
+    (0..len).into_par_iter().map(function).collect()
+}
+
+/// Returns the results of the function for all indices from `0` to `len`, in the order of their indices.
+///
+/// Without the `parallel` feature, the indices are computed one after another.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let squares = map_indices(4, |index| index * index);
+/// assert_eq!(vec![0, 1, 4, 9], squares);
+/// ```
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map_indices<T: Send>(
+    len: usize,
+    function: impl Fn(usize) -> T + Sync + Send,
+) -> Vec<T> {
+    (0..len).map(function).collect()
+}
+
+#[cfg(test)]
+mod test_map_indices {
+    use super::*;
+
+    #[test]
+    fn order_is_kept() {
+        let squares = map_indices(1000, |index| index * index);
+        assert!(squares
+            .iter()
+            .enumerate()
+            .all(|(index, square)| index * index == *square));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(map_indices(0, |index| index).is_empty());
+    }
+}
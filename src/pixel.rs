@@ -1,8 +1,10 @@
-use image::Rgba;
+use image::{Rgb, Rgba};
 
-use crate::{config, target};
+use crate::grid::Cell;
 
-/// Convert a pixel block to a char (as a String) from the given density string.
+/// Convert a pixel block to a [`Cell`] with a char from the given density string.
+///
+/// The cell also contains the average color and luminosity of the block.
 ///
 /// # Panics
 ///
@@ -12,7 +14,6 @@ use crate::{config, target};
 ///
 /// ```compile_fail, compile will fail, this is an internal example
 /// use image::Rgba;
-/// use artem::config::TargetType;
 ///
 /// //example pixels, use them from the directly if possible
 /// let pixels = vec![
@@ -20,18 +21,11 @@ use crate::{config, target};
 ///     Rgba::<u8>::from([0, 0, 0, 255]),
 /// ];
 ///
-/// assert_eq!(".", correlating_char(&pixels, "#k. ", false, TargetType::default()));
+/// assert_eq!('.', correlating_cell(&pixels, "#k. ", false).character);
 /// ```
 ///
-/// To use color, use the `color` argument, if only the background should be colored, use the `on_background_color` arg instead.
-///
 /// The `invert` arg, inverts the mapping from pixel luminosity to density string.
-pub fn correlating_char(
-    block: &[Rgba<u8>],
-    density: &str,
-    invert: bool,
-    target: config::TargetType,
-) -> String {
+pub fn correlating_cell(block: &[Rgba<u8>], density: &str, invert: bool) -> Cell {
     assert!(!block.is_empty());
     assert!(!density.is_empty());
 
@@ -64,29 +58,11 @@ pub fn correlating_char(
         .nth(density_index as usize)
         .expect("Failed to get char");
 
-    //return the correctly formatted/colored string depending on the target
-    match target {
-        //if no color, use default case
-        config::TargetType::Shell(true, background_color)
-        | config::TargetType::AnsiFile(background_color) => {
-            target::ansi::colored_char(red, green, blue, density_char, background_color)
-        }
-        config::TargetType::HtmlFile(color, background_color) => {
-            if color {
-                target::html::colored_char(red, green, blue, density_char, background_color)
-            } else {
-                density_char.to_string()
-            }
-        }
-        //all other case, including a plain text file and shell without colors
-        _ => density_char.to_string(),
-    }
+    Cell::new(density_char, Rgb([red, green, blue]), luminosity)
 }
 
 #[cfg(test)]
 mod test_pixel_density {
-    use std::env;
-
     use super::*;
 
     #[test]
@@ -96,10 +72,7 @@ mod test_pixel_density {
             Rgba::<u8>::from([255, 255, 255, 255]),
             Rgba::<u8>::from([0, 0, 0, 255]),
         ];
-        assert_eq!(
-            " ",
-            correlating_char(&pixels, "# ", true, config::TargetType::Shell(false, false))
-        );
+        assert_eq!(' ', correlating_cell(&pixels, "# ", true).character);
     }
 
     #[test]
@@ -108,15 +81,7 @@ mod test_pixel_density {
             Rgba::<u8>::from([255, 255, 255, 255]),
             Rgba::<u8>::from([0, 0, 0, 255]),
         ];
-        assert_eq!(
-            "k",
-            correlating_char(
-                &pixels,
-                "#k. ",
-                false,
-                config::TargetType::Shell(false, false)
-            )
-        );
+        assert_eq!('k', correlating_cell(&pixels, "#k. ", false).character);
     }
 
     #[test]
@@ -126,189 +91,664 @@ mod test_pixel_density {
             Rgba::<u8>::from([255, 255, 255, 255]),
             Rgba::<u8>::from([0, 0, 0, 255]),
         ];
-        assert_eq!(
-            "#",
-            correlating_char(
-                &pixels,
-                "#k. ",
-                false,
-                config::TargetType::Shell(false, false)
-            )
-        );
+        assert_eq!('#', correlating_cell(&pixels, "#k. ", false).character);
     }
 
     #[test]
-    #[ignore = "Requires truecolor support"]
-    fn colored_char() {
-        //set needed env vars
-        env::set_var("COLORTERM", "truecolor");
-        //force color, this is not printed to the terminal anyways
-        env::set_var("CLICOLOR_FORCE", "1");
+    fn cell_has_average_color() {
+        let pixels = vec![
+            Rgba::<u8>::from([255, 0, 0, 255]),
+            Rgba::<u8>::from([0, 255, 0, 255]),
+        ];
+        let cell = correlating_cell(&pixels, "#k. ", false);
+        assert_eq!(Rgb([180, 180, 0]), cell.color);
+        assert_eq!(luminosity(180, 180, 0), cell.luminosity);
+    }
+}
 
-        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
-        assert_eq!(
-            "\u{1b}[38;2;0;0;255m \u{1b}[0m", //blue color
-            correlating_char(
-                &pixels,
-                "#k. ",
-                false,
-                config::TargetType::Shell(true, false)
-            )
-        );
+/// Returns the char of the density string, whose brightness level is the nearest to the given luminosity.
+///
+/// Unlike [`correlating_cell`], the chars are treated as evenly spaced levels, where the first and last char
+/// represent a luminosity of 0 and 255. Next to the char, the luminosity represented by it is returned,
+/// so the remaining error can be used for dithering.
+///
+/// # Panics
+///
+/// Panics if the density is empty.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(('k', 170f32), quantize_luminosity(180f32, "#k. ", false));
+/// ```
+pub(crate) fn quantize_luminosity(luminosity: f32, density: &str, invert: bool) -> (char, f32) {
+    assert!(!density.is_empty());
+
+    //use chars length to support unicode chars
+    let length = density.chars().count();
+    if length == 1 {
+        //a single char represents every luminosity
+        return (density.chars().next().unwrap(), luminosity);
     }
 
+    let step = 255f32 / (length - 1) as f32;
+    let level = (luminosity / step).round().clamp(0f32, length as f32 - 1.0) as usize;
+    //bright tiles use the first (densest) chars, unless the density is inverted
+    let density_index = if invert { level } else { length - 1 - level };
+
+    let density_char = density
+        .chars()
+        .nth(density_index)
+        .expect("Failed to get char");
+    (density_char, level as f32 * step)
+}
+
+#[cfg(test)]
+mod test_quantize_luminosity {
+    use super::*;
+
     #[test]
-    fn ansi_colored_char_shell() {
-        //set no color support
-        env::set_var("COLORTERM", "false");
-        //force color, this is not printed to the terminal anyways
-        env::set_var("CLICOLOR_FORCE", "1");
-        //just some random color
-        let pixels = vec![Rgba::<u8>::from([123, 42, 244, 255])];
-        assert_eq!(
-            "\u{1b}[35m.\u{1b}[0m",
-            correlating_char(
-                &pixels,
-                "#k. ",
-                false,
-                config::TargetType::Shell(true, false)
-            )
-        );
+    fn extremes() {
+        assert_eq!(('#', 255f32), quantize_luminosity(255f32, "#k. ", false));
+        assert_eq!((' ', 0f32), quantize_luminosity(0f32, "#k. ", false));
     }
 
     #[test]
-    fn ansi_colored_char_ansi() {
-        //set no color support
-        env::set_var("COLORTERM", "false");
-        //force color, this is not printed to the terminal anyways
-        env::set_var("CLICOLOR_FORCE", "1");
-        let pixels = vec![Rgba::<u8>::from([123, 42, 244, 255])];
-        assert_eq!(
-            "\u{1b}[35m.\u{1b}[0m",
-            correlating_char(&pixels, "#k. ", false, config::TargetType::AnsiFile(false))
-        );
+    fn nearest_level() {
+        assert_eq!(('k', 170f32), quantize_luminosity(180f32, "#k. ", false));
+        assert_eq!(('.', 85f32), quantize_luminosity(100f32, "#k. ", false));
+    }
+
+    #[test]
+    fn inverted() {
+        assert_eq!(('#', 0f32), quantize_luminosity(0f32, "#k. ", true));
+        assert_eq!(('.', 170f32), quantize_luminosity(180f32, "#k. ", true));
+    }
+
+    #[test]
+    fn out_of_range_is_clamped() {
+        assert_eq!(('#', 255f32), quantize_luminosity(300f32, "#k. ", false));
+        assert_eq!((' ', 0f32), quantize_luminosity(-40f32, "#k. ", false));
+    }
+
+    #[test]
+    fn single_char() {
+        assert_eq!(('#', 42f32), quantize_luminosity(42f32, "#", false));
+    }
+}
+
+/// Combine the cells of the top and bottom half of a tile to a single half block cell.
+///
+/// With colors, the upper half block (`▀`) is used, its foreground is the color of the top and its
+/// background the color of the bottom half. Otherwise each half is either filled or empty, depending
+/// on its luminosity, see [`quantize_half_blocks`].
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let cell = half_block_cell(&top, &bottom, true, false);
+/// assert_eq!('▀', cell.character);
+/// assert_eq!(Some(bottom.color), cell.background);
+/// ```
+pub(crate) fn half_block_cell(top: &Cell, bottom: &Cell, colored: bool, invert: bool) -> Cell {
+    let [top_red, top_green, top_blue] = top.color.0;
+    let [bottom_red, bottom_green, bottom_blue] = bottom.color.0;
+    //both halves have the same number of pixels, so the average of both is the average of the tile
+    let average = |top: u8, bottom: u8| ((top as u16 + bottom as u16) / 2) as u8;
+    let luminosity = (top.luminosity + bottom.luminosity) / 2f32;
+
+    if colored {
+        Cell {
+            character: '▀',
+            color: top.color,
+            luminosity,
+            background: Some(bottom.color),
+            transparent: false,
+        }
+    } else {
+        Cell {
+            character: quantize_half_blocks([top.luminosity, bottom.luminosity], invert).0,
+            color: Rgb([
+                average(top_red, bottom_red),
+                average(top_green, bottom_green),
+                average(top_blue, bottom_blue),
+            ]),
+            luminosity,
+            background: None,
+            transparent: false,
+        }
+    }
+}
+
+/// Returns the half block char for the luminosity of the top and bottom half of a cell.
+///
+/// Bright halves are filled, unless `invert` is set. Next to the char, the luminosity represented
+/// by each half (either 0 or 255) is returned, so the remaining error can be used for dithering.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(('▀', [255f32, 0f32]), quantize_half_blocks([200f32, 10f32], false));
+/// ```
+pub(crate) fn quantize_half_blocks(luminosity: [f32; 2], invert: bool) -> (char, [f32; 2]) {
+    let [(top, top_level), (bottom, bottom_level)] =
+        luminosity.map(|luminosity| quantize_dot(luminosity, invert));
+    let char = match (top, bottom) {
+        (true, true) => '█',
+        (true, false) => '▀',
+        (false, true) => '▄',
+        (false, false) => ' ',
+    };
+    (char, [top_level, bottom_level])
+}
+
+#[cfg(test)]
+mod test_half_blocks {
+    use super::*;
+
+    fn cell(color: [u8; 3]) -> Cell {
+        Cell::new('x', Rgb(color), luminosity(color[0], color[1], color[2]))
     }
 
     #[test]
-    #[ignore = "Requires truecolor support"]
-    fn colored_background_char_shell() {
-        //set needed env vars
-        env::set_var("COLORTERM", "truecolor");
-        //force color, this is not printed to the terminal anyways
-        env::set_var("CLICOLOR_FORCE", "1");
+    fn colored_uses_both_colors() {
+        let top = cell([154, 85, 54]);
+        let bottom = cell([0, 0, 255]);
+        let half_block = half_block_cell(&top, &bottom, true, false);
+        assert_eq!('▀', half_block.character);
+        assert_eq!(top.color, half_block.color);
+        assert_eq!(Some(bottom.color), half_block.background);
+    }
 
-        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
+    #[test]
+    fn uncolored_uses_average_color() {
+        let half_block = half_block_cell(&cell([255, 255, 255]), &cell([0, 0, 0]), false, false);
+        assert_eq!('▀', half_block.character);
+        assert_eq!(Rgb([127, 127, 127]), half_block.color);
+        assert_eq!(None, half_block.background);
+    }
+
+    #[test]
+    fn quantize_all_blocks() {
         assert_eq!(
-            "\u{1b}[48;2;0;0;255m \u{1b}[0m",
-            correlating_char(
-                &pixels,
-                "#k. ",
-                false,
-                config::TargetType::Shell(true, true)
-            )
+            ('█', [255f32, 255f32]),
+            quantize_half_blocks([200f32, 128f32], false)
+        );
+        assert_eq!(
+            ('▀', [255f32, 0f32]),
+            quantize_half_blocks([200f32, 10f32], false)
+        );
+        assert_eq!(
+            ('▄', [0f32, 255f32]),
+            quantize_half_blocks([10f32, 200f32], false)
+        );
+        assert_eq!(
+            (' ', [0f32, 0f32]),
+            quantize_half_blocks([10f32, 127f32], false)
         );
     }
 
     #[test]
-    #[ignore = "Requires truecolor support"]
-    fn colored_background_char_ansi() {
-        //set needed env vars
-        env::set_var("COLORTERM", "truecolor");
-        //force color, this is not printed to the terminal anyways
-        env::set_var("CLICOLOR_FORCE", "1");
-        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
+    fn quantize_inverted() {
+        assert_eq!(
+            (' ', [255f32, 255f32]),
+            quantize_half_blocks([200f32, 200f32], true)
+        );
         assert_eq!(
-            "\u{1b}[48;2;0;0;255m \u{1b}[0m",
-            correlating_char(&pixels, "#k. ", false, config::TargetType::AnsiFile(true))
+            ('▄', [255f32, 0f32]),
+            quantize_half_blocks([200f32, 10f32], true)
         );
     }
+}
+
+/// Returns if a dot, or another part of a block character, is set for the given luminosity.
+///
+/// Bright dots are set, since they are shown in the color of the text, unless `invert` is set.
+/// Next to it, the luminosity represented by the dot (either 0 or 255) is returned,
+/// so the remaining error can be used for dithering.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!((true, 255f32), quantize_dot(200f32, false));
+/// ```
+pub(crate) fn quantize_dot(luminosity: f32, invert: bool) -> (bool, f32) {
+    if luminosity < 127.5 {
+        (invert, 0f32)
+    } else {
+        (!invert, 255f32)
+    }
+}
+
+#[cfg(test)]
+mod test_quantize_dot {
+    use super::*;
 
     #[test]
-    fn target_file_returns_non_colored_string() {
-        //force color, this is not printed to the terminal anyways
-        env::set_var("COLORTERM", "truecolor");
-        env::set_var("CLICOLOR_FORCE", "1");
+    fn bright_dot_is_set() {
+        assert_eq!((true, 255f32), quantize_dot(200f32, false));
+        assert_eq!((false, 0f32), quantize_dot(127f32, false));
+    }
 
-        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
-        assert_eq!(
-            " ",
-            correlating_char(&pixels, "#k. ", false, config::TargetType::File)
-        );
+    #[test]
+    fn inverted() {
+        assert_eq!((false, 255f32), quantize_dot(200f32, true));
+        assert_eq!((true, 0f32), quantize_dot(-20f32, true));
     }
+}
+
+/// Convert a pixel block to a [`Cell`] with the given char and the average color and luminosity of the block.
+///
+/// # Panics
+///
+/// Panics if the given pixel block is empty.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!('⣿', average_cell(&pixels, '⣿').character);
+/// ```
+pub(crate) fn average_cell(block: &[Rgba<u8>], character: char) -> Cell {
+    assert!(!block.is_empty());
+
+    let (red, green, blue) = average_color(block);
+    Cell::new(
+        character,
+        Rgb([red, green, blue]),
+        luminosity(red, green, blue),
+    )
+}
+
+#[cfg(test)]
+mod test_average_cell {
+    use super::*;
 
     #[test]
-    fn white_has_no_tag() {
-        //force color, this is not printed to the terminal anyways
-        env::set_var("COLORTERM", "truecolor");
-        env::set_var("CLICOLOR_FORCE", "1");
+    fn cell_has_average_color() {
+        let pixels = vec![
+            Rgba::<u8>::from([255, 0, 0, 255]),
+            Rgba::<u8>::from([0, 255, 0, 255]),
+        ];
+        let cell = average_cell(&pixels, '⣿');
+        assert_eq!('⣿', cell.character);
+        assert_eq!(Rgb([180, 180, 0]), cell.color);
+        assert_eq!(luminosity(180, 180, 0), cell.luminosity);
+    }
+}
 
-        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
-        assert_eq!(
-            " ",
-            correlating_char(
-                &pixels,
-                "#k. ",
-                false,
-                config::TargetType::HtmlFile(true, false)
-            )
-        );
+/// Convert the colors of the sub-pixels of a tile to a [`Cell`] with the best split into two colors.
+///
+/// Every combination of sub-pixels is tried as the foreground, while the remaining ones are the background.
+/// The combination with the lowest difference between the sub-pixels and the average color of their part is used.
+/// The sub-pixels are given row by row and the `glyph` function returns the char that has the given sub-pixels set,
+/// where the first sub-pixel is the lowest bit.
+///
+/// # Panics
+///
+/// Panics if there are no or more than 8 sub-pixels.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// //left half red, right half blue
+/// let cell = two_color_cell(&[red.clone(), blue.clone(), red, blue], quadrant_char);
+/// assert_eq!('▌', cell.character);
+/// ```
+pub(crate) fn two_color_cell(sub_pixels: &[Vec<Rgba<u8>>], glyph: fn(u8) -> char) -> Cell {
+    assert!(!sub_pixels.is_empty() && sub_pixels.len() <= 8);
+
+    let colors: Vec<[f32; 3]> = sub_pixels
+        .iter()
+        .map(|block| {
+            let (red, green, blue) = average_color(block);
+            [red as f32, green as f32, blue as f32]
+        })
+        .collect();
+    //average color of the sub-pixels in the mask, or None if there are none
+    let mean = |mask: u16, foreground: bool| {
+        let mut sum = [0f32; 3];
+        let mut count = 0;
+        for (index, color) in colors.iter().enumerate() {
+            if (mask & (1 << index) != 0) == foreground {
+                for channel in 0..3 {
+                    sum[channel] += color[channel];
+                }
+                count += 1;
+            }
+        }
+        (count > 0).then(|| sum.map(|channel| channel / count as f32))
+    };
+
+    let full_mask = (1u16 << colors.len()) - 1;
+    let mut best = (f32::MAX, full_mask, [0f32; 3], [0f32; 3]);
+    //the first sub-pixel is always part of the foreground, since swapping both colors results in the same cell,
+    //starting with all sub-pixels set, so cells with a single color are shown as a full block
+    for mask in (1..=full_mask).rev().step_by(2) {
+        let foreground = mean(mask, true).expect("The first sub-pixel is always set");
+        let background = mean(mask, false).unwrap_or(foreground);
+        let error: f32 = colors
+            .iter()
+            .enumerate()
+            .map(|(index, color)| {
+                let target = if mask & (1 << index) != 0 {
+                    foreground
+                } else {
+                    background
+                };
+                (0..3)
+                    .map(|channel| (color[channel] - target[channel]).powi(2))
+                    .sum::<f32>()
+            })
+            .sum();
+        if error < best.0 {
+            best = (error, mask, foreground, background);
+        }
+    }
+
+    let (_, mask, foreground, background) = best;
+    let [red, green, blue] = foreground.map(|channel| channel.round() as u8);
+    let background = background.map(|channel| channel.round() as u8);
+    let [total_red, total_green, total_blue] =
+        mean(full_mask, true).expect("There is at least one sub-pixel");
+    Cell {
+        character: glyph(mask as u8),
+        color: Rgb([red, green, blue]),
+        luminosity: luminosity(
+            total_red.round() as u8,
+            total_green.round() as u8,
+            total_blue.round() as u8,
+        ),
+        background: Some(Rgb(background)),
+        transparent: false,
+    }
+}
+
+#[cfg(test)]
+mod test_two_color_cell {
+    use super::*;
+
+    fn block(color: [u8; 3]) -> Vec<Rgba<u8>> {
+        vec![Rgba::<u8>::from([color[0], color[1], color[2], 255]); 2]
     }
 
     #[test]
-    fn target_html_colored_string() {
-        //force color, this is not printed to the terminal anyways
-        env::set_var("COLORTERM", "truecolor");
-        env::set_var("CLICOLOR_FORCE", "1");
+    fn single_color_is_full_block() {
+        let cell = two_color_cell(&vec![block([154, 85, 54]); 4], quadrant_char);
+        assert_eq!('█', cell.character);
+        assert_eq!(Rgb([154, 85, 54]), cell.color);
+        assert_eq!(Some(Rgb([154, 85, 54])), cell.background);
+    }
 
-        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
-        assert_eq!(
-            "<span style=\"color: #0000FF\">.</span>",
-            correlating_char(
-                &pixels,
-                "#k:.",
-                false,
-                config::TargetType::HtmlFile(true, false)
-            )
+    #[test]
+    fn split_left_right() {
+        let red = block([255, 0, 0]);
+        let blue = block([0, 0, 255]);
+        let cell = two_color_cell(&[red.clone(), blue.clone(), red, blue], quadrant_char);
+        assert_eq!('▌', cell.character);
+        assert_eq!(Rgb([255, 0, 0]), cell.color);
+        assert_eq!(Some(Rgb([0, 0, 255])), cell.background);
+    }
+
+    #[test]
+    fn similar_colors_are_grouped() {
+        let dark = block([10, 10, 10]);
+        let light = block([250, 250, 250]);
+        //only the bottom right sextant is dark
+        let cell = two_color_cell(
+            &[
+                light.clone(),
+                light.clone(),
+                block([240, 240, 240]),
+                light.clone(),
+                light,
+                dark,
+            ],
+            sextant_char,
         );
+        assert_eq!(sextant_char(0b011111), cell.character);
+        assert_eq!(Rgb([10, 10, 10]), cell.background.unwrap());
     }
+}
+
+/// Bit of each dot of a braille pattern, the dots are numbered column by column, except for the last row.
+///
+/// See <https://en.wikipedia.org/wiki/Braille_Patterns> for the layout.
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Returns the braille char, which has the given 2x4 dots set.
+///
+/// The dots are given row by row, the first dot is the lowest bit.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// //first dot of the first row and last dot of the last row
+/// assert_eq!('⢁', braille_char(0b1000_0001));
+/// ```
+pub(crate) fn braille_char(dots: u8) -> char {
+    let mut pattern = 0;
+    for (row, row_dots) in BRAILLE_DOTS.iter().enumerate() {
+        for (column, bit) in row_dots.iter().enumerate() {
+            if dots & (1 << (row * 2 + column)) != 0 {
+                pattern |= bit;
+            }
+        }
+    }
+    //the braille patterns start at U+2800, which has no dots set
+    char::from_u32(0x2800 + pattern).expect("Invalid braille pattern")
+}
+
+/// Returns the dots of a braille char, in the same order as for [`braille_char`].
+///
+/// Chars that are not braille patterns return [`None`].
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(Some(0b1000_0001), braille_dots('⢁'));
+/// ```
+pub(crate) fn braille_dots(braille: char) -> Option<u8> {
+    let pattern = (braille as u32).checked_sub(0x2800)?;
+    if pattern > 0xFF {
+        return None;
+    }
+    let mut dots = 0;
+    for (row, row_dots) in BRAILLE_DOTS.iter().enumerate() {
+        for (column, bit) in row_dots.iter().enumerate() {
+            if pattern & bit != 0 {
+                dots |= 1 << (row * 2 + column);
+            }
+        }
+    }
+    Some(dots)
+}
+
+#[cfg(test)]
+mod test_braille_char {
+    use super::*;
 
     #[test]
-    fn target_html_background_string() {
-        //force color, this is not printed to the terminal anyways
-        env::set_var("COLORTERM", "truecolor");
-        env::set_var("CLICOLOR_FORCE", "1");
+    fn no_dots() {
+        assert_eq!('\u{2800}', braille_char(0));
+    }
 
-        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
-        assert_eq!(
-            "<span style=\"background-color: #0000FF\"> </span>",
-            correlating_char(
-                &pixels,
-                "#k. ",
-                false,
-                config::TargetType::HtmlFile(true, true)
-            )
-        );
+    #[test]
+    fn all_dots() {
+        assert_eq!('⣿', braille_char(0xFF));
     }
 
     #[test]
-    fn target_html_no_color() {
-        //force color, this is not printed to the terminal anyways
-        env::set_var("COLORTERM", "truecolor");
-        env::set_var("CLICOLOR_FORCE", "1");
+    fn dot_order() {
+        //first dot of the first column and last dot of the second column
+        assert_eq!('⢁', braille_char(0b1000_0001));
+        //last dot of the first column
+        assert_eq!('⡀', braille_char(0b0100_0000));
+    }
 
-        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
-        assert_eq!(
-            " ",
-            correlating_char(
-                &pixels,
-                "#k. ",
-                false,
-                config::TargetType::HtmlFile(false, false)
-            )
-        );
+    #[test]
+    fn dots_of_char() {
+        for dots in 0..=255 {
+            assert_eq!(Some(dots), braille_dots(braille_char(dots)));
+        }
+        assert_eq!(None, braille_dots('x'));
+        assert_eq!(None, braille_dots('\u{2900}'));
     }
 }
 
+/// Quadrant chars, indexed by the set quadrants, the top left quadrant is the lowest bit.
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// Returns the quadrant char, which has the given 2x2 quadrants set.
+///
+/// The quadrants are given row by row, the first quadrant is the lowest bit.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!('▚', quadrant_char(0b1001));
+/// ```
+pub(crate) fn quadrant_char(quadrants: u8) -> char {
+    QUADRANTS[(quadrants & 0b1111) as usize]
+}
+
+/// Returns the sextant char, which has the given 2x3 sextants set.
+///
+/// The sextants are given row by row, the first sextant is the lowest bit.
+/// The sextants of the Symbols for Legacy Computing block (U+1FB00 - U+1FB3B) do not contain the
+/// patterns, which already exist as block elements. These are replaced by the existing chars.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!('🬀', sextant_char(0b000001));
+/// assert_eq!('▌', sextant_char(0b010101));
+/// ```
+pub(crate) fn sextant_char(sextants: u8) -> char {
+    //patterns of the left and right column
+    const LEFT: u8 = 0b010101;
+    const RIGHT: u8 = 0b101010;
+    match sextants & 0b111111 {
+        0 => ' ',
+        LEFT => '▌',
+        RIGHT => '▐',
+        0b111111 => '█',
+        sextants => {
+            //the first sextant has no empty pattern and the columns are skipped
+            let skipped = 1 + u32::from(sextants > LEFT) + u32::from(sextants > RIGHT);
+            char::from_u32(0x1FB00 + sextants as u32 - skipped).expect("Invalid sextant")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_block_chars {
+    use super::*;
+
+    #[test]
+    fn quadrants() {
+        assert_eq!(' ', quadrant_char(0));
+        assert_eq!('▘', quadrant_char(0b0001));
+        assert_eq!('▚', quadrant_char(0b1001));
+        assert_eq!('▟', quadrant_char(0b1110));
+        assert_eq!('█', quadrant_char(0b1111));
+    }
+
+    #[test]
+    fn sextant_range() {
+        assert_eq!('\u{1FB00}', sextant_char(0b000001));
+        assert_eq!('\u{1FB3B}', sextant_char(0b111110));
+    }
+
+    #[test]
+    fn sextant_existing_chars() {
+        assert_eq!(' ', sextant_char(0));
+        assert_eq!('▌', sextant_char(0b010101));
+        assert_eq!('▐', sextant_char(0b101010));
+        assert_eq!('█', sextant_char(0b111111));
+    }
+
+    #[test]
+    fn sextants_around_columns() {
+        //the patterns before and after the skipped columns are next to each other
+        assert_eq!('\u{1FB13}', sextant_char(0b010100));
+        assert_eq!('\u{1FB14}', sextant_char(0b010110));
+        assert_eq!('\u{1FB27}', sextant_char(0b101001));
+        assert_eq!('\u{1FB28}', sextant_char(0b101011));
+    }
+
+    #[test]
+    fn all_sextants_are_unique() {
+        let chars: std::collections::HashSet<char> = (0..64).map(sextant_char).collect();
+        assert_eq!(64, chars.len());
+    }
+}
+
+/// Returns the quadrant char, that is the most similar to the given sextant char.
+///
+/// This can be used when the font does not support sextants. The top and bottom row of the sextants are used for
+/// the quadrants, the middle row is ignored, since half of it belongs to each row of quadrants.
+/// Chars that are not sextants return [`None`].
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(Some('▘'), sextant_to_quadrant('🬀'));
+/// assert_eq!(None, sextant_to_quadrant('x'));
+/// ```
+pub(crate) fn sextant_to_quadrant(sextant: char) -> Option<char> {
+    let index = (sextant as u32).checked_sub(0x1FB00)?;
+    if index > 0x3B {
+        return None;
+    }
+    //revert the skipped patterns of the sextant_char function
+    let mut sextants = index + 1;
+    if sextants >= 0b010101 {
+        sextants += 1;
+    }
+    if sextants >= 0b101010 {
+        sextants += 1;
+    }
+    let top = sextants & 0b11;
+    let bottom = (sextants >> 4) & 0b11;
+    Some(quadrant_char((top | (bottom << 2)) as u8))
+}
+
+#[cfg(test)]
+mod test_sextant_to_quadrant {
+    use super::*;
+
+    #[test]
+    fn sextants_and_quadrants() {
+        assert_eq!(Some('▘'), sextant_to_quadrant(sextant_char(0b000001)));
+        assert_eq!(Some('▟'), sextant_to_quadrant(sextant_char(0b111110)));
+        //the middle row alone is not visible as quadrants
+        assert_eq!(Some(' '), sextant_to_quadrant(sextant_char(0b001100)));
+        for sextants in 0..64 {
+            let char = sextant_char(sextants);
+            if ('\u{1FB00}'..='\u{1FB3B}').contains(&char) {
+                let quadrants = (sextants & 0b11) | ((sextants >> 4) << 2);
+                assert_eq!(Some(quadrant_char(quadrants)), sextant_to_quadrant(char));
+            }
+        }
+        assert_eq!(None, sextant_to_quadrant('x'));
+        assert_eq!(None, sextant_to_quadrant('\u{1FB3C}'));
+    }
+}
+
+/// Returns the luminosity of the average color of the pixel block.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!(255f32, average_luminosity(&[Rgba::<u8>::from([255, 255, 255, 255])]));
+/// ```
+pub(crate) fn average_luminosity(block: &[Rgba<u8>]) -> f32 {
+    let (red, green, blue) = average_color(block);
+    luminosity(red, green, blue)
+}
+
 ///Remap a value from one range to another.
 ///
 /// If the value is outside of the specified range, it will still be
@@ -343,33 +783,38 @@ mod test_map_range {
 
 /// Returns the average rbg color of multiple pixel.
 ///
-/// If the input block is empty, all pixels are seen and calculated as if there were black.
+/// Each pixel is weighted by its alpha value, so transparent pixels do not change the color of the visible ones.
+/// If the input block is empty or fully transparent, the color is black.
 ///
 /// # Examples
 ///
 /// ```compile_fail, compile will fail, this is an internal example
 /// let pixels: Vec<Rgba<u8>> = Vec::new();
-/// assert_eq!((0, 0, 0, 0.0), get_pixel_color_luminosity(&pixels));
+/// assert_eq!((0, 0, 0), average_color(&pixels));
 /// ```
 ///
 /// The formula for calculating the rbg colors is based an a minutephysics video <https://www.youtube.com/watch?v=LKnqECcg6Gw>
 fn average_color(block: &[Rgba<u8>]) -> (u8, u8, u8) {
-    let sum = block
+    let (sum, weight) = block
         .iter()
-        .map(|pixel| {
+        .fold(((0f32, 0f32, 0f32), 0f32), |(acc, weight), pixel| {
+            let alpha = pixel.0[3] as f32 / 255f32;
             (
-                pixel.0[0] as f32 * pixel.0[0] as f32,
-                pixel.0[1] as f32 * pixel.0[1] as f32,
-                pixel.0[2] as f32 * pixel.0[2] as f32,
+                (
+                    acc.0 + alpha * pixel.0[0] as f32 * pixel.0[0] as f32,
+                    acc.1 + alpha * pixel.0[1] as f32 * pixel.0[1] as f32,
+                    acc.2 + alpha * pixel.0[2] as f32 * pixel.0[2] as f32,
+                ),
+                weight + alpha,
             )
-        })
-        .fold((0f32, 0f32, 0f32), |acc, value| {
-            (acc.0 + value.0, acc.1 + value.1, acc.2 + value.2)
         });
+    if weight == 0f32 {
+        return (0, 0, 0);
+    }
     (
-        (sum.0 / block.len() as f32).sqrt() as u8,
-        (sum.1 / block.len() as f32).sqrt() as u8,
-        (sum.2 / block.len() as f32).sqrt() as u8,
+        (sum.0 / weight).sqrt() as u8,
+        (sum.1 / weight).sqrt() as u8,
+        (sum.2 / weight).sqrt() as u8,
     )
 }
 
@@ -397,6 +842,35 @@ mod test_avg_color {
         assert_eq!((0, 180, 180), average_color(&pixels));
     }
 
+    #[test]
+    fn transparent_pixels_are_ignored() {
+        let pixels = vec![
+            Rgba::<u8>::from([255, 0, 0, 255]),
+            Rgba::<u8>::from([0, 0, 0, 0]),
+            Rgba::<u8>::from([0, 255, 0, 0]),
+        ];
+
+        assert_eq!((255, 0, 0), average_color(&pixels));
+    }
+
+    #[test]
+    fn semi_transparent_pixels_are_weighted() {
+        let pixels = vec![
+            Rgba::<u8>::from([255, 0, 0, 255]),
+            Rgba::<u8>::from([0, 0, 255, 85]),
+        ];
+
+        //the red pixel has three times the weight of the blue one
+        assert_eq!((220, 0, 127), average_color(&pixels));
+    }
+
+    #[test]
+    fn fully_transparent_is_black() {
+        let pixels = vec![Rgba::<u8>::from([255, 255, 255, 0]); 4];
+
+        assert_eq!((0, 0, 0), average_color(&pixels));
+    }
+
     #[test]
     fn empty_input() {
         let pixels: Vec<Rgba<u8>> = Vec::new();
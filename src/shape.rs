@@ -0,0 +1,329 @@
+use image::{DynamicImage, GenericImageView};
+
+use crate::{
+    font::{self, GLYPH_SIZE},
+    pixel,
+};
+
+/// Number of columns of a shape, each covers two columns of a glyph.
+const SHAPE_COLUMNS: u32 = 4;
+/// Number of rows of a shape, each covers one row of a glyph.
+const SHAPE_ROWS: u32 = 8;
+/// Number of values of a shape.
+const SHAPE_SIZE: usize = (SHAPE_COLUMNS * SHAPE_ROWS) as usize;
+
+/// Minimum difference between the darkest and brightest area of a tile, for its contrast to be stretched.
+const MIN_CONTRAST: f32 = 0.25;
+
+/// Shape of a tile or glyph, the values range from 0 (empty/dark) to 1 (filled/bright) and are stored row by row.
+type Shape = [f32; SHAPE_SIZE];
+
+/// Matches tiles to the characters, whose shape is the most similar.
+///
+/// Each character is rasterized using the embedded bitmap font into a small grid, where each value is the part of
+/// the area covered by the glyph. The coverage is scaled, so the densest character covers the whole cell,
+/// otherwise bright tiles would be matched by their shape alone, instead of by their brightness as well.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let shapes = Shapes::new("/|\\_ ").unwrap();
+/// let character = shapes.matching_char(&tile_shape(&img, 0, 0, 8, 16, false));
+/// ```
+#[derive(Debug, Clone)]
+pub(crate) struct Shapes {
+    glyphs: Vec<(char, Shape)>,
+}
+
+impl Shapes {
+    /// Create the shapes of the given characters.
+    ///
+    /// Characters, which are not part of the embedded font, are ignored.
+    /// If none of the characters are part of it, [`None`] is returned.
+    pub(crate) fn new(characters: &str) -> Option<Self> {
+        let mut glyphs: Vec<(char, Shape)> = characters
+            .chars()
+            .filter_map(|char| Some((char, glyph_shape(font::glyph(char)?))))
+            .collect();
+
+        let densest = glyphs
+            .iter()
+            .map(|(_, shape)| shape.iter().sum::<f32>() / SHAPE_SIZE as f32)
+            .fold(0f32, f32::max);
+        if densest > 0f32 {
+            for (_, shape) in &mut glyphs {
+                for value in shape.iter_mut() {
+                    *value = (*value / densest).min(1f32);
+                }
+            }
+        }
+
+        (!glyphs.is_empty()).then_some(Self { glyphs })
+    }
+
+    /// Returns the character, whose shape has the lowest squared difference to the given shape.
+    ///
+    /// If multiple characters match equally well, the first one is used.
+    pub(crate) fn matching_char(&self, shape: &Shape) -> char {
+        let mut best = (f32::MAX, self.glyphs[0].0);
+        for (char, glyph) in &self.glyphs {
+            let error: f32 = glyph
+                .iter()
+                .zip(shape)
+                .map(|(glyph, tile)| (glyph - tile).powi(2))
+                .sum();
+            if error < best.0 {
+                best = (error, *char);
+            }
+        }
+        best.1
+    }
+}
+
+#[cfg(test)]
+mod test_shapes {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    /// Returns a shape with the given columns filled.
+    fn columns(filled: &[usize]) -> Shape {
+        let mut shape = [0f32; SHAPE_SIZE];
+        for row in 0..SHAPE_ROWS as usize {
+            for column in filled {
+                shape[row * SHAPE_COLUMNS as usize + column] = 1f32;
+            }
+        }
+        shape
+    }
+
+    #[test]
+    fn unknown_chars_are_ignored() {
+        assert!(Shapes::new("😀").is_none());
+        assert_eq!(1, Shapes::new("😀#").unwrap().glyphs.len());
+    }
+
+    #[test]
+    fn densest_char_is_scaled() {
+        let shapes = Shapes::new("█ ").unwrap();
+        assert_eq!([1f32; SHAPE_SIZE], shapes.glyphs[0].1);
+        assert_eq!([0f32; SHAPE_SIZE], shapes.glyphs[1].1);
+    }
+
+    #[test]
+    fn empty_and_full() {
+        let shapes = Shapes::new(" .:#").unwrap();
+        assert_eq!(' ', shapes.matching_char(&[0f32; SHAPE_SIZE]));
+        assert_eq!('#', shapes.matching_char(&[1f32; SHAPE_SIZE]));
+    }
+
+    #[test]
+    fn vertical_line() {
+        let shapes = Shapes::new("|-_/ ").unwrap();
+        assert_eq!('|', shapes.matching_char(&columns(&[1, 2])));
+    }
+
+    #[test]
+    fn horizontal_lines() {
+        let shapes = Shapes::new("|-_/ ").unwrap();
+        let mut shape = [0f32; SHAPE_SIZE];
+        //the underscore fills the last row, the hyphen the one in the middle
+        shape[7 * SHAPE_COLUMNS as usize..].fill(1f32);
+        assert_eq!('_', shapes.matching_char(&shape));
+        shape.fill(0f32);
+        shape[3 * SHAPE_COLUMNS as usize..4 * SHAPE_COLUMNS as usize].fill(1f32);
+        assert_eq!('-', shapes.matching_char(&shape));
+    }
+
+    #[test]
+    fn diagonal_lines() {
+        let shapes = Shapes::new("/\\|-_ ").unwrap();
+        //lines through a tile with the usual aspect ratio of 1:2
+        let line = |rising: bool| {
+            let img = RgbaImage::from_fn(8, 16, |x, y| {
+                let line_x = if rising { 7 - y / 2 } else { y / 2 };
+                if x.abs_diff(line_x) <= 1 {
+                    Rgba([255, 255, 255, 255])
+                } else {
+                    Rgba([0, 0, 0, 255])
+                }
+            });
+            tile_shape(&DynamicImage::ImageRgba8(img), 0, 0, 8, 16, false)
+        };
+        assert_eq!('/', shapes.matching_char(&line(true)));
+        assert_eq!('\\', shapes.matching_char(&line(false)));
+    }
+
+    #[test]
+    fn half_blocks() {
+        let shapes = Shapes::new("▌▐ ").unwrap();
+        assert_eq!('▌', shapes.matching_char(&columns(&[0, 1])));
+        assert_eq!('▐', shapes.matching_char(&columns(&[2, 3])));
+    }
+}
+
+/// Returns the shape of a glyph, which is the part of each area of the shape that is covered by the glyph.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!([1f32; SHAPE_SIZE], glyph_shape([0xFF; 8]));
+/// ```
+fn glyph_shape(glyph: [u8; 8]) -> Shape {
+    let glyph_columns = GLYPH_SIZE / SHAPE_COLUMNS;
+    let glyph_rows = GLYPH_SIZE / SHAPE_ROWS;
+    let mut shape = [0f32; SHAPE_SIZE];
+    for (index, value) in shape.iter_mut().enumerate() {
+        let column = index as u32 % SHAPE_COLUMNS;
+        let row = index as u32 / SHAPE_COLUMNS;
+        let mut covered = 0;
+        for y in row * glyph_rows..(row + 1) * glyph_rows {
+            for x in column * glyph_columns..(column + 1) * glyph_columns {
+                //the lowest bit is the leftmost pixel
+                if glyph[y as usize] & (1 << x) != 0 {
+                    covered += 1;
+                }
+            }
+        }
+        *value = covered as f32 / (glyph_columns * glyph_rows) as f32;
+    }
+    shape
+}
+
+#[cfg(test)]
+mod test_glyph_shape {
+    use super::*;
+
+    #[test]
+    fn full_and_empty() {
+        assert_eq!([1f32; SHAPE_SIZE], glyph_shape([0xFF; 8]));
+        assert_eq!([0f32; SHAPE_SIZE], glyph_shape([0; 8]));
+    }
+
+    #[test]
+    fn partially_covered() {
+        //only the leftmost pixel of the first row
+        let shape = glyph_shape([0b1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(0.5, shape[0]);
+        assert_eq!(0f32, shape[1..].iter().sum::<f32>());
+    }
+}
+
+/// Returns the shape of a tile, which is the average luminosity of each area of the shape.
+///
+/// The luminosity ranges from 0 to 1 and is inverted, if `invert` is set, so dark tiles match dense characters.
+/// If the tile is smaller than the shape, the nearest pixel is used for each area.
+/// Tiles with enough contrast are stretched to the full range, since thin lines only cover a small part of
+/// their areas and would be matched by sparse characters like `.` otherwise.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// let shape = tile_shape(&img, 0, 0, 8, 16, false);
+/// ```
+pub(crate) fn tile_shape(
+    img: &DynamicImage,
+    x: u32,
+    y: u32,
+    tile_width: u32,
+    tile_height: u32,
+    invert: bool,
+) -> Shape {
+    let mut shape = [0f32; SHAPE_SIZE];
+    for (index, value) in shape.iter_mut().enumerate() {
+        let column = index as u32 % SHAPE_COLUMNS;
+        let row = index as u32 / SHAPE_COLUMNS;
+        //each area has at least one pixel
+        let start_x = column * tile_width / SHAPE_COLUMNS;
+        let end_x = ((column + 1) * tile_width / SHAPE_COLUMNS).max(start_x + 1);
+        let start_y = row * tile_height / SHAPE_ROWS;
+        let end_y = ((row + 1) * tile_height / SHAPE_ROWS).max(start_y + 1);
+
+        let mut pixels = Vec::with_capacity(((end_x - start_x) * (end_y - start_y)) as usize);
+        for p_y in start_y..end_y {
+            for p_x in start_x..end_x {
+                pixels.push(img.get_pixel(x + p_x, y + p_y));
+            }
+        }
+        let luminosity = pixel::average_luminosity(&pixels) / 255f32;
+        *value = if invert {
+            1f32 - luminosity
+        } else {
+            luminosity
+        };
+    }
+    let min = shape.iter().copied().fold(1f32, f32::min);
+    let max = shape.iter().copied().fold(0f32, f32::max);
+    if max - min > MIN_CONTRAST {
+        for value in shape.iter_mut() {
+            *value = (*value - min) / (max - min);
+        }
+    }
+    shape
+}
+
+#[cfg(test)]
+mod test_tile_shape {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    #[test]
+    fn left_half_bright() {
+        let img = RgbaImage::from_fn(8, 16, |x, _| {
+            if x < 4 {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        });
+        let shape = tile_shape(&DynamicImage::ImageRgba8(img), 0, 0, 8, 16, false);
+        for row in shape.chunks(SHAPE_COLUMNS as usize) {
+            assert_eq!([1f32, 1f32, 0f32, 0f32], row);
+        }
+    }
+
+    #[test]
+    fn thin_line_is_stretched() {
+        let img = RgbaImage::from_fn(8, 16, |x, _| {
+            if x == 3 {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        });
+        let shape = tile_shape(&DynamicImage::ImageRgba8(img), 0, 0, 8, 16, false);
+        for row in shape.chunks(SHAPE_COLUMNS as usize) {
+            assert_eq!([0f32, 1f32, 0f32, 0f32], row);
+        }
+    }
+
+    #[test]
+    fn low_contrast_is_kept() {
+        let img = RgbaImage::from_fn(8, 16, |x, _| {
+            if x < 4 {
+                Rgba([40, 40, 40, 255])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        });
+        let shape = tile_shape(&DynamicImage::ImageRgba8(img), 0, 0, 8, 16, false);
+        assert!(shape[0] > 0f32 && shape[0] < MIN_CONTRAST);
+        assert_eq!(0f32, shape[3]);
+    }
+
+    #[test]
+    fn small_tile_uses_nearest_pixel() {
+        let img = RgbaImage::from_fn(1, 2, |_, y| {
+            if y == 0 {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        });
+        let shape = tile_shape(&DynamicImage::ImageRgba8(img), 0, 0, 1, 2, true);
+        //the top half is bright, which is inverted
+        assert_eq!(0f32, shape[..SHAPE_SIZE / 2].iter().sum::<f32>());
+        assert_eq!(
+            SHAPE_SIZE as f32 / 2f32,
+            shape[SHAPE_SIZE / 2..].iter().sum()
+        );
+    }
+}
@@ -0,0 +1,1011 @@
+use std::io::{self, Write};
+
+use colored::{Color, ColoredString, Colorize};
+use once_cell::sync::Lazy;
+
+use crate::{
+    color::Palette,
+    config::{ColorDepth, ColorMetric, Dithering},
+    dither::Ditherer,
+    grid::Cell,
+};
+
+use super::{CellColors, Renderer};
+
+/// Renderer for targets, who support Ansi formatted colors.
+///
+/// This includes the shell/terminal as well as `.ans`/`.ansi` files.
+/// Depending on the [`ColorDepth`], either truecolor or the nearest color of the 256 or 16 color ansi palette is used.
+/// A compact renderer only changes the color when it differs from the previous character, see [`AnsiRenderer::compact`].
+///
+/// # Examples
+/// ```
+/// use artem::{config::ConfigBuilder, target::ansi::AnsiRenderer};
+///
+/// let img = image::open("assets/images/standard_test_img.png").unwrap();
+/// let mut output = Vec::new();
+/// artem::convert_with_renderer(
+///     img,
+///     &ConfigBuilder::new().build().unwrap(),
+///     //color the background instead of the characters
+///     &mut AnsiRenderer::new(true),
+///     &mut output,
+/// )
+/// .unwrap();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct AnsiRenderer {
+    background_color: bool,
+    center_vertically: bool,
+    color_depth: ColorDepth,
+    color_metric: ColorMetric,
+    dither: Dithering,
+    //created for each image, since they contain the errors of the previous rows,
+    //the first one is used for the foreground and the second one for the background of two colored cells
+    ditherers: [Option<Ditherer<3>>; 2],
+    rows: u32,
+    compact: bool,
+    color_tolerance: u8,
+    //colors, which are currently set by the compacted output
+    current: Option<CellColors>,
+}
+
+impl AnsiRenderer {
+    /// Create a new [`AnsiRenderer`], which colors either the characters or the background.
+    pub fn new(background_color: bool) -> Self {
+        Self {
+            background_color,
+            ..Default::default()
+        }
+    }
+
+    /// Center the output vertically in the terminal, by adding line breaks above and below it.
+    pub fn center_vertically(mut self, center: bool) -> Self {
+        self.center_vertically = center;
+        self
+    }
+
+    /// Set the number of colors that are used, it defaults to the one supported by the terminal.
+    pub fn color_depth(mut self, color_depth: ColorDepth) -> Self {
+        self.color_depth = color_depth;
+        self
+    }
+
+    /// Set the metric used to find the most similar palette color, when not using truecolor.
+    pub fn color_metric(mut self, color_metric: ColorMetric) -> Self {
+        self.color_metric = color_metric;
+        self
+    }
+
+    /// Set the dithering method used when reducing the colors to 16 or 256 colors.
+    pub fn dither(mut self, dither: Dithering) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    /// Only write an escape sequence when the color changes and reset it once at the end of each row,
+    /// instead of coloring and resetting each character on its own.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Set how much each color channel may differ from the current color, before a compact renderer changes it.
+    pub fn color_tolerance(mut self, tolerance: u8) -> Self {
+        self.color_tolerance = tolerance;
+        self
+    }
+
+    /// Returns the color of the palette of the [`ColorDepth`], which is shown for the given color.
+    fn palette_color(&self, color: [u8; 3]) -> [u8; 3] {
+        let palette = match self.color_depth {
+            ColorDepth::TrueColor => return color,
+            ColorDepth::Ansi16 => &*VGA_PALETTE,
+            ColorDepth::Ansi256 => &*XTERM_PALETTE,
+        };
+        palette.color(palette.nearest(color, self.color_metric))
+    }
+
+    /// Returns the parameters of the escape sequence, which sets the given color of the palette.
+    fn color_code(&self, color: [u8; 3], background: bool) -> String {
+        //38 and 48 select the foreground or background color of the truecolor and 256 color palette
+        let layer = if background { 48 } else { 38 };
+        let [red, green, blue] = color;
+        match self.color_depth {
+            ColorDepth::TrueColor => format!("{layer};2;{red};{green};{blue}"),
+            ColorDepth::Ansi256 => {
+                format!(
+                    "{layer};5;{}",
+                    rgb_to_ansi256(red, green, blue, self.color_metric)
+                )
+            }
+            ColorDepth::Ansi16 => {
+                //the basic colors start at 30 and the bright ones at 90, their backgrounds are 10 higher
+                let index = VGA_PALETTE.nearest(color, self.color_metric) as u8;
+                let code = if index < 8 { 30 + index } else { 82 + index };
+                (code + if background { 10 } else { 0 }).to_string()
+            }
+        }
+    }
+
+    /// Write the cell, only changing the colors if they are not similar to the current ones.
+    fn compact_cell(&mut self, writer: &mut dyn Write, cell: &Cell) -> io::Result<()> {
+        //colored does not support writing only the escape sequences, but the environment should still be respected
+        if !colored::control::SHOULD_COLORIZE.should_colorize() {
+            return write!(writer, "{}", cell.character);
+        }
+
+        let color = self.dithered_color(cell.color.0, 0);
+        let color = self.palette_color(color);
+        let colors = match cell.background {
+            Some(background) => {
+                let background = self.dithered_color(background.0, 1);
+                (Some(color), Some(self.palette_color(background)))
+            }
+            //the 16 colors only color the background of two colored cells
+            None if self.background_color && self.color_depth != ColorDepth::Ansi16 => {
+                (None, Some(color))
+            }
+            None => (Some(color), None),
+        };
+
+        let background_set = self
+            .current
+            .is_some_and(|(_, background)| background.is_some());
+        //spaces do not show the color of the character, so they can keep the current one
+        let keep_current = if colors.1.is_none() && cell.character == ' ' && !background_set {
+            true
+        } else {
+            self.current
+                .is_some_and(|current| super::similar_colors(current, colors, self.color_tolerance))
+        };
+
+        if !keep_current {
+            //a color, which is no longer used, has to be reset first
+            if let Some((foreground, background)) = self.current {
+                if (foreground.is_some() && colors.0.is_none())
+                    || (background.is_some() && colors.1.is_none())
+                {
+                    writer.write_all(b"\x1b[0m")?;
+                }
+            }
+            let codes: Vec<String> = [(colors.0, false), (colors.1, true)]
+                .into_iter()
+                .filter_map(|(color, background)| {
+                    color.map(|color| self.color_code(color, background))
+                })
+                .collect();
+            write!(writer, "\x1b[{}m", codes.join(";"))?;
+            self.current = Some(colors);
+        }
+        write!(writer, "{}", cell.character)
+    }
+
+    /// Reset the colors of the compacted output, if any are set.
+    fn reset(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        if self.current.take().is_some() {
+            writer.write_all(b"\x1b[0m")?;
+        }
+        Ok(())
+    }
+
+    /// Returns the palette color the given color is reduced to when dithering.
+    ///
+    /// The `layer` selects the ditherer, 0 for the colors of the characters and 1 for the background colors
+    /// of two colored cells. If truecolor is used or dithering is disabled, the color is returned unchanged.
+    fn dithered_color(&mut self, color: [u8; 3], layer: usize) -> [u8; 3] {
+        let palette = match self.color_depth {
+            ColorDepth::TrueColor => return color,
+            ColorDepth::Ansi16 => &*VGA_PALETTE,
+            ColorDepth::Ansi256 => &*XTERM_PALETTE,
+        };
+
+        let metric = self.color_metric;
+        match self.ditherer(layer) {
+            Some(ditherer) => ditherer.dither(color.map(f32::from), |adjusted| {
+                let adjusted = adjusted.map(|channel| channel.round().clamp(0f32, 255f32) as u8);
+                let color = palette.color(palette.nearest(adjusted, metric));
+                (color, color.map(f32::from))
+            }),
+            None => color,
+        }
+    }
+
+    /// Returns the ditherer of the layer, which is created for the first cell of the image.
+    ///
+    /// If truecolor is used or dithering is disabled, there is no ditherer.
+    fn ditherer(&mut self, layer: usize) -> Option<&mut Ditherer<3>> {
+        let spread = match self.color_depth {
+            ColorDepth::TrueColor => return None,
+            //the 16 colors have about 3 levels per channel, the 256 colors 6
+            ColorDepth::Ansi16 => 85f32,
+            ColorDepth::Ansi256 => 51f32,
+        };
+        if self.dither == Dithering::None {
+            return None;
+        }
+
+        let dither = self.dither;
+        Some(self.ditherers[layer].get_or_insert_with(|| Ditherer::new(dither, spread)))
+    }
+}
+
+impl Renderer for AnsiRenderer {
+    fn header(&mut self, writer: &mut dyn Write, _columns: u32, rows: u32) -> io::Result<()> {
+        self.rows = rows;
+        self.ditherers = [None, None];
+        self.current = None;
+        if self.center_vertically {
+            log::trace!("Adding vertical top spacing");
+            writer.write_all(crate::spacing_vertical(rows).as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn cell(&mut self, writer: &mut dyn Write, cell: &Cell) -> io::Result<()> {
+        if self.compact {
+            return self.compact_cell(writer, cell);
+        }
+        let [red, green, blue] = self.dithered_color(cell.color.0, 0);
+        if let Some(background) = cell.background {
+            let background = self.dithered_color(background.0, 1);
+            return writer.write_all(
+                two_colored_char(
+                    [red, green, blue],
+                    background,
+                    cell.character,
+                    self.color_depth,
+                    self.color_metric,
+                )
+                .as_bytes(),
+            );
+        }
+        writer.write_all(
+            colored_char(
+                red,
+                green,
+                blue,
+                cell.character,
+                self.background_color,
+                self.color_depth,
+                self.color_metric,
+            )
+            .as_bytes(),
+        )
+    }
+
+    fn blank_cell(&mut self, writer: &mut dyn Write, cell: &Cell) -> io::Result<()> {
+        //the cell is still part of the row, so the following cells have to stay in their columns
+        for layer in 0..self.ditherers.len() {
+            if let Some(ditherer) = self.ditherer(layer) {
+                ditherer.skip();
+            }
+        }
+        self.text(writer, &cell.character.to_string())
+    }
+
+    fn text(&mut self, writer: &mut dyn Write, text: &str) -> io::Result<()> {
+        //the text is not part of the image, so it does not use its colors
+        self.reset(writer)?;
+        writer.write_all(text.as_bytes())
+    }
+
+    fn end_row(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        for ditherer in self.ditherers.iter_mut().flatten() {
+            ditherer.end_row();
+        }
+        self.reset(writer)?;
+        writer.write_all(b"\n")
+    }
+
+    fn footer(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        if self.center_vertically {
+            log::trace!("Adding vertical bottom spacing");
+            writer.write_all(crate::spacing_vertical(self.rows).as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_ansi_renderer {
+    use std::env;
+
+    use image::Rgb;
+
+    use super::*;
+
+    /// Render a single row of cells with the same color.
+    fn render_row(renderer: &mut AnsiRenderer, color: [u8; 3], columns: usize) -> String {
+        let mut output = Vec::new();
+        renderer.header(&mut output, columns as u32, 1).unwrap();
+        for _ in 0..columns {
+            renderer
+                .cell(&mut output, &Cell::new('x', Rgb(color), 0f32))
+                .unwrap();
+        }
+        renderer.end_row(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn no_dither_uses_single_color() {
+        env::set_var("CLICOLOR_FORCE", "1");
+        let mut renderer = AnsiRenderer::new(false)
+            .color_depth(ColorDepth::Ansi16)
+            .color_metric(ColorMetric::Euclidean);
+        let output = render_row(&mut renderer, [210, 210, 210], 8);
+        //the light gray is between white (170) and bright white (255), but nearer to white
+        assert_eq!(8, output.matches("\u{1b}[37m").count());
+    }
+
+    #[test]
+    fn dither_mixes_colors() {
+        env::set_var("CLICOLOR_FORCE", "1");
+        let mut renderer = AnsiRenderer::new(false)
+            .color_depth(ColorDepth::Ansi16)
+            .color_metric(ColorMetric::Euclidean)
+            .dither(Dithering::FloydSteinberg);
+        let output = render_row(&mut renderer, [210, 210, 210], 8);
+        let white = output.matches("\u{1b}[37m").count();
+        let bright_white = output.matches("\u{1b}[97m").count();
+        assert_eq!(8, white + bright_white);
+        assert!(white > 0 && bright_white > 0, "{output:?}");
+    }
+
+    #[test]
+    fn dither_skips_blank_cells() {
+        env::set_var("CLICOLOR_FORCE", "1");
+        let mut renderer = AnsiRenderer::new(false)
+            .color_depth(ColorDepth::Ansi16)
+            .color_metric(ColorMetric::Euclidean)
+            .dither(Dithering::FloydSteinberg);
+        let mut output = Vec::new();
+        renderer.header(&mut output, 4, 1).unwrap();
+        let light_gray = cell([210, 210, 210], None);
+        renderer.cell(&mut output, &light_gray).unwrap();
+        let blank = Cell {
+            character: ' ',
+            transparent: true,
+            ..light_gray
+        };
+        renderer.blank_cell(&mut output, &blank).unwrap();
+        renderer.cell(&mut output, &light_gray).unwrap();
+        renderer.cell(&mut output, &light_gray).unwrap();
+        renderer.end_row(&mut output).unwrap();
+        //the error of the first cell is diffused into the blank cell, so the third cell is white again
+        //and only the last one receives enough error to become bright white
+        assert_eq!(
+            "\u{1b}[37mx\u{1b}[0m \u{1b}[37mx\u{1b}[0m\u{1b}[97mx\u{1b}[0m\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn dither_is_ignored_for_truecolor() {
+        env::set_var("CLICOLOR_FORCE", "1");
+        let mut renderer = AnsiRenderer::new(false)
+            .color_depth(ColorDepth::TrueColor)
+            .dither(Dithering::Bayer);
+        let output = render_row(&mut renderer, [210, 210, 210], 4);
+        assert_eq!(4, output.matches("\u{1b}[38;2;210;210;210m").count());
+    }
+
+    #[test]
+    fn compact_row_has_single_color() {
+        env::set_var("CLICOLOR_FORCE", "1");
+        let mut renderer = AnsiRenderer::new(false)
+            .color_depth(ColorDepth::TrueColor)
+            .compact(true);
+        let output = render_row(&mut renderer, [210, 210, 210], 4);
+        assert_eq!("\u{1b}[38;2;210;210;210mxxxx\u{1b}[0m\n", output);
+    }
+
+    #[test]
+    fn compact_changes_color() {
+        env::set_var("CLICOLOR_FORCE", "1");
+        let mut renderer = AnsiRenderer::new(true)
+            .color_depth(ColorDepth::Ansi256)
+            .color_metric(ColorMetric::Euclidean)
+            .compact(true);
+        let mut output = Vec::new();
+        renderer.header(&mut output, 3, 1).unwrap();
+        for color in [[255, 0, 0], [250, 5, 5], [0, 0, 255]] {
+            renderer.cell(&mut output, &cell(color, None)).unwrap();
+        }
+        renderer.end_row(&mut output).unwrap();
+        //the first two colors are the same color of the palette
+        assert_eq!(
+            "\u{1b}[48;5;196mxx\u{1b}[48;5;21mx\u{1b}[0m\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn compact_tolerance() {
+        env::set_var("CLICOLOR_FORCE", "1");
+        let mut renderer = AnsiRenderer::new(false)
+            .color_depth(ColorDepth::TrueColor)
+            .compact(true)
+            .color_tolerance(4);
+        let mut output = Vec::new();
+        renderer.header(&mut output, 3, 1).unwrap();
+        for color in [[100, 100, 100], [104, 100, 96], [105, 100, 100]] {
+            renderer.cell(&mut output, &cell(color, None)).unwrap();
+        }
+        renderer.end_row(&mut output).unwrap();
+        //the tolerance is relative to the written color, so the last color differs too much
+        assert_eq!(
+            "\u{1b}[38;2;100;100;100mxx\u{1b}[38;2;105;100;100mx\u{1b}[0m\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn compact_resets_removed_background() {
+        env::set_var("CLICOLOR_FORCE", "1");
+        let mut renderer = AnsiRenderer::new(false)
+            .color_depth(ColorDepth::Ansi16)
+            .color_metric(ColorMetric::Euclidean)
+            .compact(true);
+        let mut output = Vec::new();
+        renderer.header(&mut output, 2, 1).unwrap();
+        renderer
+            .cell(&mut output, &cell([255, 0, 0], Some([0, 0, 170])))
+            .unwrap();
+        renderer
+            .cell(&mut output, &cell([255, 0, 0], None))
+            .unwrap();
+        renderer.text(&mut output, "|").unwrap();
+        assert_eq!(
+            "\u{1b}[91;44mx\u{1b}[0m\u{1b}[91mx\u{1b}[0m|",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    /// Returns a cell with the given colors.
+    fn cell(color: [u8; 3], background: Option<[u8; 3]>) -> Cell {
+        Cell {
+            character: 'x',
+            color: Rgb(color),
+            luminosity: 0f32,
+            background: background.map(Rgb),
+            transparent: false,
+        }
+    }
+}
+
+/// Returns an colored string with the given colors.
+///
+/// Depending on the [`ColorDepth`], the given char is returned as a colored string using either true colors,
+/// the 256 color palette or the 16 basic ansi colors.
+/// Background colors are only supported by true colors and the 256 color palette.
+/// The palette color is chosen using the given [`ColorMetric`].
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// println!("{}", colored_char(100, 100, 100, 'x', false, ColorDepth::TrueColor, ColorMetric::Ciede2000));
+/// ```
+pub(crate) fn colored_char(
+    red: u8,
+    green: u8,
+    blue: u8,
+    char: char,
+    background_color: bool,
+    color_depth: ColorDepth,
+    color_metric: ColorMetric,
+) -> String {
+    match color_depth {
+        //return true color string
+        ColorDepth::TrueColor if background_color => {
+            char.to_string().on_truecolor(red, green, blue).to_string()
+        }
+        ColorDepth::TrueColor => char.to_string().truecolor(red, green, blue).to_string(),
+        ColorDepth::Ansi256 => {
+            //colored does not support the 256 color palette, but the environment should still be respected
+            if !colored::control::SHOULD_COLORIZE.should_colorize() {
+                return char.to_string();
+            }
+            let color = rgb_to_ansi256(red, green, blue, color_metric);
+            //38 selects the foreground color, 48 the background color
+            let layer = if background_color { 48 } else { 38 };
+            format!("\x1b[{layer};5;{color}m{char}\x1b[0m")
+        }
+        //otherwise use basic (16 color) ansi color
+        ColorDepth::Ansi16 => {
+            rgb_to_ansi(&char.to_string(), red, green, blue, color_metric).to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_colored_string {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn rust_color_no_background() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "x".truecolor(154, 85, 54).to_string(),
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                false,
+                ColorDepth::TrueColor,
+                ColorMetric::Euclidean
+            )
+        );
+    }
+
+    #[test]
+    fn rust_color_with_background() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "x".on_truecolor(154, 85, 54).to_string(),
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                true,
+                ColorDepth::TrueColor,
+                ColorMetric::Euclidean
+            )
+        );
+    }
+
+    #[test]
+    fn rust_color_ansi256_no_background() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "\u{1b}[38;5;95mx\u{1b}[0m",
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                false,
+                ColorDepth::Ansi256,
+                ColorMetric::Euclidean
+            )
+        );
+    }
+
+    #[test]
+    fn rust_color_ansi256_with_background() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "\u{1b}[48;5;95mx\u{1b}[0m",
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                true,
+                ColorDepth::Ansi256,
+                ColorMetric::Euclidean
+            )
+        );
+    }
+
+    #[test]
+    fn rust_color_ansi_no_background() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "\u{1b}[33mx\u{1b}[0m",
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                false,
+                ColorDepth::Ansi16,
+                ColorMetric::Euclidean
+            )
+        );
+    }
+
+    #[test]
+    fn rust_color_ansi_with_background() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        //ansi does not support background, so it is the same as without
+        assert_eq!(
+            "\u{1b}[33mx\u{1b}[0m",
+            colored_char(
+                154,
+                85,
+                54,
+                'x',
+                true,
+                ColorDepth::Ansi16,
+                ColorMetric::Euclidean
+            )
+        );
+    }
+}
+
+/// Returns a string of the given char with both a foreground and a background color.
+///
+/// Unlike [`colored_char`], the background is also colored when using the 16 basic ansi colors,
+/// since the char is only shown correctly with both colors, for example for half blocks.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// println!("{}", two_colored_char([255, 0, 0], [0, 0, 255], '▀', ColorDepth::TrueColor, ColorMetric::Cie76));
+/// ```
+pub(crate) fn two_colored_char(
+    foreground: [u8; 3],
+    background: [u8; 3],
+    char: char,
+    color_depth: ColorDepth,
+    color_metric: ColorMetric,
+) -> String {
+    let [red, green, blue] = foreground;
+    let [background_red, background_green, background_blue] = background;
+    match color_depth {
+        ColorDepth::TrueColor => char
+            .to_string()
+            .truecolor(red, green, blue)
+            .on_truecolor(background_red, background_green, background_blue)
+            .to_string(),
+        ColorDepth::Ansi256 => {
+            //colored does not support the 256 color palette, but the environment should still be respected
+            if !colored::control::SHOULD_COLORIZE.should_colorize() {
+                return char.to_string();
+            }
+            format!(
+                "\x1b[38;5;{};48;5;{}m{char}\x1b[0m",
+                rgb_to_ansi256(red, green, blue, color_metric),
+                rgb_to_ansi256(
+                    background_red,
+                    background_green,
+                    background_blue,
+                    color_metric
+                )
+            )
+        }
+        ColorDepth::Ansi16 => char
+            .to_string()
+            .color(VGA_COLORS[VGA_PALETTE.nearest(foreground, color_metric)])
+            .on_color(VGA_COLORS[VGA_PALETTE.nearest(background, color_metric)])
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test_two_colored_string {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn truecolor() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "\u{1b}[48;2;0;0;255;38;2;154;85;54m▀\u{1b}[0m",
+            two_colored_char(
+                [154, 85, 54],
+                [0, 0, 255],
+                '▀',
+                ColorDepth::TrueColor,
+                ColorMetric::Euclidean
+            )
+        );
+    }
+
+    #[test]
+    fn ansi256() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "\u{1b}[38;5;95;48;5;21m▀\u{1b}[0m",
+            two_colored_char(
+                [154, 85, 54],
+                [0, 0, 255],
+                '▀',
+                ColorDepth::Ansi256,
+                ColorMetric::Euclidean
+            )
+        );
+    }
+
+    #[test]
+    fn ansi16_has_background() {
+        //ensure that colors will be used
+        env::set_var("CLICOLOR_FORCE", "1");
+        assert_eq!(
+            "▀".yellow().on_bright_blue().to_string(),
+            two_colored_char(
+                [154, 85, 54],
+                [0, 0, 255],
+                '▀',
+                ColorDepth::Ansi16,
+                ColorMetric::Euclidean
+            )
+        );
+    }
+}
+
+/// The 16 basic ansi colors, using the VGA colors as an example.
+///
+/// The colors are from <https://en.wikipedia.org/wiki/ANSI_escape_code#Colors>.
+static VGA_PALETTE: Lazy<Palette> = Lazy::new(|| {
+    Palette::new(&[
+        [0, 0, 0],       //black
+        [170, 0, 0],     //red
+        [0, 170, 0],     //green
+        [170, 85, 0],    //yellow
+        [0, 0, 170],     //blue
+        [170, 0, 170],   //magenta
+        [0, 170, 170],   //cyan
+        [170, 170, 170], //white
+        [128, 128, 128], //bright black/gray
+        [255, 0, 0],     //bright red
+        [0, 255, 0],     //bright green
+        [255, 255, 0],   //bright yellow
+        [0, 0, 255],     //bright blue
+        [255, 0, 255],   //bright magenta
+        [0, 255, 255],   //bright cyan
+        [255, 255, 255], //bright white
+    ])
+});
+
+/// The ansi colors in the same order as the [`VGA_PALETTE`].
+const VGA_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
+];
+
+///Converts the given input string to an ansi colored string
+///
+/// It tries to match the ANSI-Color as closely as possible by calculating the distance between all
+/// 16 colors and the given input color from `r`, `b` and `b` using the [`ColorMetric`], then returning the nearest.
+/// It will not be 100% accurate, since every terminal has slightly different
+/// ANSI-Colors. It used the VGA-Colors as ANSI-Color.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// //convert black to ansi black color
+/// assert_eq!("input".black(), rgb_to_ansi("input", 0, 0, 0, ColorMetric::Ciede2000));
+/// ```
+fn rgb_to_ansi(input: &str, r: u8, g: u8, b: u8, metric: ColorMetric) -> ColoredString {
+    //find nearest color and convert string to matching color
+    input.color(VGA_COLORS[VGA_PALETTE.nearest([r, g, b], metric)])
+}
+
+#[cfg(test)]
+mod test_convert_rgb_ansi {
+    use super::*;
+
+    #[test]
+    fn convert_vga_normal_values() {
+        //convert black to ansi black color
+        assert_eq!(
+            "input".black(),
+            rgb_to_ansi("input", 0, 0, 0, ColorMetric::Euclidean)
+        );
+        //convert red to ansi red color
+        assert_eq!(
+            "input".red(),
+            rgb_to_ansi("input", 170, 0, 0, ColorMetric::Euclidean)
+        );
+        //convert green to ansi green color
+        assert_eq!(
+            "input".green(),
+            rgb_to_ansi("input", 0, 170, 0, ColorMetric::Euclidean)
+        );
+        //convert yellow to ansi yellow color
+        assert_eq!(
+            "input".yellow(),
+            rgb_to_ansi("input", 170, 85, 0, ColorMetric::Euclidean)
+        );
+        //convert blue to ansi blue color
+        assert_eq!(
+            "input".blue(),
+            rgb_to_ansi("input", 0, 0, 170, ColorMetric::Euclidean)
+        );
+        //convert magenta to ansi magenta color
+        assert_eq!(
+            "input".magenta(),
+            rgb_to_ansi("input", 170, 0, 170, ColorMetric::Euclidean)
+        );
+        //convert cyan to ansi cyan color
+        assert_eq!(
+            "input".cyan(),
+            rgb_to_ansi("input", 0, 170, 170, ColorMetric::Euclidean)
+        );
+        //convert white to ansi white color
+        assert_eq!(
+            "input".white(),
+            rgb_to_ansi("input", 170, 170, 170, ColorMetric::Euclidean)
+        );
+    }
+
+    #[test]
+    fn convert_vga_bright_values() {
+        //convert bright black to ansi bright black color
+        assert_eq!(
+            "input".bright_black(),
+            rgb_to_ansi("input", 128, 128, 128, ColorMetric::Euclidean)
+        );
+        //convert bright red to ansi bright red color
+        assert_eq!(
+            "input".bright_red(),
+            rgb_to_ansi("input", 255, 0, 0, ColorMetric::Euclidean)
+        );
+        //convert bright green to ansi bright green color
+        assert_eq!(
+            "input".bright_green(),
+            rgb_to_ansi("input", 0, 255, 0, ColorMetric::Euclidean)
+        );
+        //convert bright yellow to ansi bright yellow color
+        assert_eq!(
+            "input".bright_yellow(),
+            rgb_to_ansi("input", 255, 255, 0, ColorMetric::Euclidean)
+        );
+        //convert bright blue to ansi bright blue color
+        assert_eq!(
+            "input".bright_blue(),
+            rgb_to_ansi("input", 0, 0, 255, ColorMetric::Euclidean)
+        );
+        //convert bright magenta to ansi bright magenta color
+        assert_eq!(
+            "input".bright_magenta(),
+            rgb_to_ansi("input", 255, 0, 255, ColorMetric::Euclidean)
+        );
+        //convert bright cyan to ansi bright cyan color
+        assert_eq!(
+            "input".bright_cyan(),
+            rgb_to_ansi("input", 0, 255, 255, ColorMetric::Euclidean)
+        );
+        //convert bright white to ansi bright white color
+        assert_eq!(
+            "input".bright_white(),
+            rgb_to_ansi("input", 255, 255, 255, ColorMetric::Euclidean)
+        );
+    }
+
+    #[test]
+    fn rgb_violet_perceptual() {
+        //violet is closer to blue than to magenta, which only the perceptual metrics notice
+        assert_eq!(
+            "input".magenta(),
+            rgb_to_ansi("input", 123, 42, 244, ColorMetric::Euclidean)
+        );
+        assert_eq!(
+            "input".bright_blue(),
+            rgb_to_ansi("input", 123, 42, 244, ColorMetric::Ciede2000)
+        );
+    }
+
+    #[test]
+    fn rust_color_all_metrics() {
+        //the rust color is mapped to the brown vga color (170, 85, 0) by all metrics
+        for metric in [
+            ColorMetric::Euclidean,
+            ColorMetric::Redmean,
+            ColorMetric::Cie76,
+            ColorMetric::Ciede2000,
+        ] {
+            assert_eq!("input".yellow(), rgb_to_ansi("input", 154, 85, 54, metric));
+        }
+    }
+
+    #[test]
+    fn rgb_blue() {
+        //convert a blue rgb tone to ansi blue
+        assert_eq!(
+            "input".blue(),
+            rgb_to_ansi("input", 0, 0, 88, ColorMetric::Euclidean)
+        );
+    }
+}
+
+/// The 6x6x6 color cube and the gray ramp of the 256 color (xterm) palette, starting at index 16.
+///
+/// The basic colors (0-15) are not included, since they differ between terminals.
+static XTERM_PALETTE: Lazy<Palette> = Lazy::new(|| {
+    //intensity of the 6 steps of each channel in the color cube
+    const CUBE_STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    let mut colors = Vec::with_capacity(240);
+    for red in CUBE_STEPS {
+        for green in CUBE_STEPS {
+            for blue in CUBE_STEPS {
+                colors.push([red, green, blue]);
+            }
+        }
+    }
+    //gray ramp goes from 8 to 238 in steps of 10
+    colors.extend((0..24).map(|step| [8 + step * 10; 3]));
+    Palette::new(&colors)
+});
+
+/// Returns the index of the nearest color in the 256 color (xterm) palette.
+///
+/// The palette consists of the 16 basic colors (0-15), a 6x6x6 color cube (16-231)
+/// and a ramp of 24 gray tones (232-255). Since the basic colors differ between terminals,
+/// only the color cube and the gray ramp are used. The nearest color is chosen using the [`ColorMetric`].
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// //pure red is part of the color cube
+/// assert_eq!(196, rgb_to_ansi256(255, 0, 0, ColorMetric::Ciede2000));
+/// ```
+fn rgb_to_ansi256(red: u8, green: u8, blue: u8, metric: ColorMetric) -> u8 {
+    16 + XTERM_PALETTE.nearest([red, green, blue], metric) as u8
+}
+
+#[cfg(test)]
+mod test_convert_rgb_ansi256 {
+    use super::*;
+
+    #[test]
+    fn cube_corners() {
+        assert_eq!(16, rgb_to_ansi256(0, 0, 0, ColorMetric::Euclidean));
+        assert_eq!(196, rgb_to_ansi256(255, 0, 0, ColorMetric::Euclidean));
+        assert_eq!(46, rgb_to_ansi256(0, 255, 0, ColorMetric::Euclidean));
+        assert_eq!(21, rgb_to_ansi256(0, 0, 255, ColorMetric::Euclidean));
+        assert_eq!(231, rgb_to_ansi256(255, 255, 255, ColorMetric::Euclidean));
+    }
+
+    #[test]
+    fn cube_steps() {
+        //exact color of the cube (95, 135, 175)
+        assert_eq!(67, rgb_to_ansi256(95, 135, 175, ColorMetric::Euclidean));
+        //values between two steps round to the nearest one
+        assert_eq!(67, rgb_to_ansi256(100, 140, 170, ColorMetric::Euclidean));
+    }
+
+    #[test]
+    fn gray_ramp() {
+        assert_eq!(232, rgb_to_ansi256(8, 8, 8, ColorMetric::Euclidean));
+        assert_eq!(244, rgb_to_ansi256(128, 128, 128, ColorMetric::Euclidean));
+        assert_eq!(255, rgb_to_ansi256(238, 238, 238, ColorMetric::Euclidean));
+    }
+
+    #[test]
+    fn rust_color() {
+        //(154, 85, 54) is nearest to (135, 95, 95)
+        assert_eq!(95, rgb_to_ansi256(154, 85, 54, ColorMetric::Euclidean));
+    }
+
+    #[test]
+    fn rust_color_perceptual() {
+        //the euclidean distance results in a grayish pink (135, 95, 95),
+        //the perceptual metrics keep the orange/brown hue of the color
+        assert_eq!(131, rgb_to_ansi256(154, 85, 54, ColorMetric::Cie76));
+        assert_eq!(130, rgb_to_ansi256(154, 85, 54, ColorMetric::Ciede2000));
+    }
+
+    #[test]
+    fn gray_with_tint_perceptual() {
+        //euclidean reduces the greenish gray to a neutral gray (118, 118, 118)
+        assert_eq!(243, rgb_to_ansi256(120, 130, 110, ColorMetric::Euclidean));
+        assert_eq!(101, rgb_to_ansi256(120, 130, 110, ColorMetric::Ciede2000));
+    }
+}
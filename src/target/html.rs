@@ -0,0 +1,402 @@
+use std::io::{self, Write};
+
+use crate::grid::Cell;
+
+use super::{CellColors, Renderer};
+
+/// Renderer for html files, which colors the characters using `<span>` elements.
+///
+/// A compact renderer merges adjacent characters with the same color into a single `<span>`, see [`HtmlRenderer::compact`].
+///
+/// # Examples
+/// ```
+/// use artem::{config::ConfigBuilder, target::html::HtmlRenderer};
+///
+/// let img = image::open("assets/images/standard_test_img.png").unwrap();
+/// let mut output = Vec::new();
+/// artem::convert_with_renderer(
+///     img,
+///     &ConfigBuilder::new().build().unwrap(),
+///     &mut HtmlRenderer::new(false).document(true),
+///     &mut output,
+/// )
+/// .unwrap();
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HtmlRenderer {
+    background_color: bool,
+    document: bool,
+    compact: bool,
+    color_tolerance: u8,
+    //colors of the currently open span of the compacted output
+    current: Option<CellColors>,
+}
+
+impl HtmlRenderer {
+    /// Create a new [`HtmlRenderer`], which colors either the characters or the background.
+    ///
+    /// A background colored output will be wrapped in a complete html document, see [`HtmlRenderer::document`].
+    pub fn new(background_color: bool) -> Self {
+        Self {
+            background_color,
+            document: background_color,
+            ..Default::default()
+        }
+    }
+
+    /// Wrap the output in a complete html document, instead of only returning the colored characters.
+    pub fn document(mut self, document: bool) -> Self {
+        self.document = document;
+        self
+    }
+
+    /// Merge adjacent characters with the same color into a single `<span>`, which is closed at the end of each row.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Set how much each color channel may differ from the color of the current `<span>`, before a compact renderer starts a new one.
+    pub fn color_tolerance(mut self, tolerance: u8) -> Self {
+        self.color_tolerance = tolerance;
+        self
+    }
+
+    /// Write the cell into the current `<span>`, if its colors are similar, otherwise a new one is started.
+    fn compact_cell(&mut self, writer: &mut dyn Write, cell: &Cell) -> io::Result<()> {
+        let colors = match cell.background {
+            Some(background) => (Some(cell.color.0), Some(background.0)),
+            None if self.background_color => (None, Some(cell.color.0)),
+            //white spaces don't have a visible foreground color, so they only need to be outside of background colors
+            None if cell.character.is_whitespace() => {
+                if self
+                    .current
+                    .is_some_and(|(_, background)| background.is_some())
+                {
+                    self.close_span(writer)?;
+                }
+                return write!(writer, "{}", cell.character);
+            }
+            None => (Some(cell.color.0), None),
+        };
+
+        if !self
+            .current
+            .is_some_and(|current| super::similar_colors(current, colors, self.color_tolerance))
+        {
+            self.close_span(writer)?;
+            let style = match colors {
+                (Some([red, green, blue]), Some([background_red, background_green, background_blue])) => format!(
+                    "color: #{red:02X}{green:02X}{blue:02X}; \
+                    background-color: #{background_red:02X}{background_green:02X}{background_blue:02X}"
+                ),
+                (None, Some([red, green, blue])) => {
+                    format!("background-color: #{red:02X}{green:02X}{blue:02X}")
+                }
+                (Some([red, green, blue]), None) => format!("color: #{red:02X}{green:02X}{blue:02X}"),
+                (None, None) => String::new(),
+            };
+            write!(writer, "<span style=\"{style}\">")?;
+            self.current = Some(colors);
+        }
+        write!(writer, "{}", cell.character)
+    }
+
+    /// Close the current `<span>` of the compacted output, if one is open.
+    fn close_span(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        if self.current.take().is_some() {
+            writer.write_all(b"</span>")?;
+        }
+        Ok(())
+    }
+}
+
+impl Renderer for HtmlRenderer {
+    fn header(&mut self, writer: &mut dyn Write, _columns: u32, _rows: u32) -> io::Result<()> {
+        self.current = None;
+        if self.document {
+            log::trace!("Adding html top part");
+            writer.write_all(html_top().as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn cell(&mut self, writer: &mut dyn Write, cell: &Cell) -> io::Result<()> {
+        if self.compact {
+            return self.compact_cell(writer, cell);
+        }
+        if let Some(background) = cell.background {
+            return writer.write_all(
+                two_colored_char(cell.color.0, background.0, cell.character).as_bytes(),
+            );
+        }
+        let [red, green, blue] = cell.color.0;
+        writer.write_all(
+            colored_char(red, green, blue, cell.character, self.background_color).as_bytes(),
+        )
+    }
+
+    fn text(&mut self, writer: &mut dyn Write, text: &str) -> io::Result<()> {
+        //the text is not part of the image, so it does not use its colors
+        self.close_span(writer)?;
+        writer.write_all(text.as_bytes())
+    }
+
+    fn end_row(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        self.close_span(writer)?;
+        writer.write_all(b"\n")
+    }
+
+    fn footer(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        self.close_span(writer)?;
+        if self.document {
+            log::trace!("Adding html bottom part");
+            writer.write_all(html_bottom().as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_html_renderer {
+    use image::Rgb;
+
+    use super::*;
+
+    /// Render a single row of cells with the given characters and colors.
+    fn render_row(renderer: &mut HtmlRenderer, cells: &[(char, [u8; 3])]) -> String {
+        let mut output = Vec::new();
+        renderer.header(&mut output, cells.len() as u32, 1).unwrap();
+        for (character, color) in cells {
+            renderer
+                .cell(&mut output, &Cell::new(*character, Rgb(*color), 0f32))
+                .unwrap();
+        }
+        renderer.end_row(&mut output).unwrap();
+        renderer.footer(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn each_char_has_span() {
+        let output = render_row(&mut HtmlRenderer::new(false), &[('x', [0, 0, 0]); 2]);
+        assert_eq!(
+            "<span style=\"color: #000000\">x</span><span style=\"color: #000000\">x</span>\n",
+            output
+        );
+    }
+
+    #[test]
+    fn compact_merges_spans() {
+        let output = render_row(
+            &mut HtmlRenderer::new(false).compact(true),
+            &[
+                ('x', [0, 0, 0]),
+                (' ', [9, 9, 9]),
+                ('y', [0, 0, 0]),
+                ('z', [255, 0, 0]),
+            ],
+        );
+        assert_eq!(
+            "<span style=\"color: #000000\">x y</span><span style=\"color: #FF0000\">z</span>\n",
+            output
+        );
+    }
+
+    #[test]
+    fn compact_background_tolerance() {
+        let output = render_row(
+            &mut HtmlRenderer::new(true).compact(true).color_tolerance(2),
+            &[(' ', [10, 10, 10]), ('x', [12, 8, 10]), ('y', [13, 10, 10])],
+        );
+        assert!(output.contains(
+            "<span style=\"background-color: #0A0A0A\"> x</span><span style=\"background-color: #0D0A0A\">y</span>\n"
+        ));
+    }
+}
+
+///Returns the top part of the output html file.
+///
+/// This contains the html elements needed for a correct html file.
+/// The title will be set to `Artem Ascii Image`.
+/// It will also have the pre tag for correct spacing/line breaking
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// use artem::target::html;
+///
+/// let string = String::new();
+/// string.push_str(&html_top())
+/// ```
+pub(crate) fn html_top() -> String {
+    r#"<!DOCTYPE html>
+    <html lang="en">
+    
+    <head>
+        <meta charset="UTF-8">
+        <meta http-equiv="X-UA-Compatible" content="IE=edge">
+        <meta name="viewport" content="width=device-width, initial-scale=1.0">
+        <title>Artem Ascii Image</title>
+    </head>
+    
+    <body>
+        <pre>"#
+        .to_string()
+}
+
+#[cfg(test)]
+mod test_push_html_top {
+    use super::*;
+    #[test]
+    fn push_top_html_returns_correct_string() {
+        assert_eq!(
+            r#"<!DOCTYPE html>
+    <html lang="en">
+    
+    <head>
+        <meta charset="UTF-8">
+        <meta http-equiv="X-UA-Compatible" content="IE=edge">
+        <meta name="viewport" content="width=device-width, initial-scale=1.0">
+        <title>Artem Ascii Image</title>
+    </head>
+    
+    <body>
+        <pre>"#,
+            html_top()
+        )
+    }
+}
+
+///Returns the bottom part of the output html file.
+///
+/// The matching closing tags fro [`html_top`]. It will close
+/// the pres, body and html tag.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// use artem::target::html;
+///
+/// let string = String::new();
+/// string.push_str(&html_top())
+/// string.push_str(&html_bottom())
+/// ```
+pub(crate) fn html_bottom() -> String {
+    "\n</pre></body></html>".to_string()
+}
+
+#[cfg(test)]
+mod test_push_html_bottom {
+    use super::*;
+
+    #[test]
+    fn push_bottom_html_returns_correct_string() {
+        assert_eq!("\n</pre></body></html>", html_bottom())
+    }
+}
+
+/// Returns an html string representation of the given char with optional background color support.
+///
+/// Creates an <span> element with style attribute, which sets the (background) color to the
+/// given rgb inputs.
+/// Technically the span can have more than a single char, but the complexity needed for a system to group
+/// characters with the same color would be unnecessary and out of scope.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// println!("{}", get_html(100, 100, 100, 'x', false));
+/// ```
+pub(crate) fn colored_char(
+    red: u8,
+    green: u8,
+    blue: u8,
+    char: char,
+    background_color: bool,
+) -> String {
+    if background_color {
+        format!(
+            "<span style=\"background-color: #{:02X?}{:02X?}{:02X?}\">{}</span>",
+            red, green, blue, char
+        )
+    } else if char.is_whitespace() {
+        //white spaces don't have a visible foreground color,
+        //it saves space when not  having an entire useless span tag
+        String::from(char)
+    } else {
+        format!(
+            "<span style=\"color: #{:02X?}{:02X?}{:02X?}\">{}</span>",
+            red, green, blue, char
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_html_string {
+    use super::*;
+
+    #[test]
+    fn whitespace_no_tag() {
+        assert_eq!(" ", colored_char(0, 0, 0, ' ', false))
+    }
+
+    #[test]
+    fn black_no_background() {
+        assert_eq!(
+            "<span style=\"color: #000000\">x</span>",
+            colored_char(0, 0, 0, 'x', false)
+        )
+    }
+
+    #[test]
+    fn black_with_background() {
+        assert_eq!(
+            "<span style=\"background-color: #000000\">x</span>",
+            colored_char(0, 0, 0, 'x', true)
+        )
+    }
+
+    #[test]
+    fn rust_color_no_background() {
+        assert_eq!(
+            "<span style=\"color: #9A5536\">x</span>",
+            colored_char(154, 85, 54, 'x', false)
+        )
+    }
+
+    #[test]
+    fn rust_color_with_background() {
+        assert_eq!(
+            "<span style=\"background-color: #9A5536\">x</span>",
+            colored_char(154, 85, 54, 'x', true)
+        )
+    }
+}
+
+/// Returns an html string representation of the given char with both a color and a background color.
+///
+/// This is used for cells with two colors, like half blocks, independent of the background color option.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// println!("{}", two_colored_char([255, 0, 0], [0, 0, 255], '▀'));
+/// ```
+pub(crate) fn two_colored_char(foreground: [u8; 3], background: [u8; 3], char: char) -> String {
+    let [red, green, blue] = foreground;
+    let [background_red, background_green, background_blue] = background;
+    format!(
+        "<span style=\"color: #{red:02X}{green:02X}{blue:02X}; \
+        background-color: #{background_red:02X}{background_green:02X}{background_blue:02X}\">{char}</span>"
+    )
+}
+
+#[cfg(test)]
+mod test_html_two_colored_string {
+    use super::*;
+
+    #[test]
+    fn half_block() {
+        assert_eq!(
+            "<span style=\"color: #9A5536; background-color: #0000FF\">▀</span>",
+            two_colored_char([154, 85, 54], [0, 0, 255], '▀')
+        )
+    }
+}
@@ -0,0 +1,428 @@
+//!This module contains the output targets/renderers for the converted image.
+//!These include the shell/terminal, plain text files and text files, who support colored output.
+//!
+//!Every target implements the [`Renderer`] trait, which is used to write the converted [`Grid`](crate::grid::Grid).
+//!Custom output formats can be created by implementing the [`Renderer`] trait and passing it to
+//![`crate::convert_with_renderer`].
+
+use std::{
+    io::{self, Write},
+    num::NonZeroU32,
+};
+
+use crate::{
+    config::{Config, TargetType},
+    grid::Cell,
+};
+
+/// Contains methods for dealing with html files.
+/// These can add starting and closing tags.
+pub mod html;
+
+/// Contains methods for converting characters to targets, who support
+/// Ansi formatted colors. This includes the shell/terminal as well as `.ans`/`.ansi`
+/// files.
+pub mod ansi;
+
+/// Contains the renderer for targets without any color support, like plain text files.
+pub mod plain;
+
+/// Contains the renderer for scalable vector graphics (svg) files.
+pub mod svg;
+
+/// Contains the renderer for png images, which rasterizes the characters using a bitmap font.
+pub mod png;
+
+/// Output target for the converted image.
+///
+/// A renderer is called for every part of the output in order: first the [`Renderer::header`],
+/// then each row, consisting of multiple [`Renderer::cell`] calls and a final [`Renderer::end_row`],
+/// and lastly the [`Renderer::footer`].
+/// Decorations like the border or spacing for centering the image are passed to [`Renderer::text`].
+///
+/// # Examples
+/// ```
+/// use std::io::{self, Write};
+///
+/// use artem::{config::ConfigBuilder, grid::Cell, target::Renderer};
+///
+/// /// Writes the luminosity of each cell as a number.
+/// struct LuminosityRenderer;
+///
+/// impl Renderer for LuminosityRenderer {
+///     fn cell(&mut self, writer: &mut dyn Write, cell: &Cell) -> io::Result<()> {
+///         write!(writer, "{:4}", cell.luminosity.round())
+///     }
+/// }
+///
+/// let img = image::open("assets/images/standard_test_img.png").unwrap();
+/// let mut output = Vec::new();
+/// artem::convert_with_renderer(
+///     img,
+///     &ConfigBuilder::new().build().unwrap(),
+///     &mut LuminosityRenderer,
+///     &mut output,
+/// )
+/// .unwrap();
+/// ```
+pub trait Renderer {
+    /// Write the start of the output, before any rows.
+    ///
+    /// The `columns` and `rows` are the size of the whole output, including the border.
+    /// By default nothing is written.
+    fn header(&mut self, _writer: &mut dyn Write, _columns: u32, _rows: u32) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Write a single converted cell.
+    fn cell(&mut self, writer: &mut dyn Write, cell: &Cell) -> io::Result<()>;
+
+    /// Write a transparent cell, which does not use any of its colors.
+    ///
+    /// By default the character is written as [`Renderer::text`].
+    fn blank_cell(&mut self, writer: &mut dyn Write, cell: &Cell) -> io::Result<()> {
+        self.text(writer, &cell.character.to_string())
+    }
+
+    /// Write text that is not part of the image, like the border.
+    ///
+    /// By default the text is written unchanged.
+    fn text(&mut self, writer: &mut dyn Write, text: &str) -> io::Result<()> {
+        writer.write_all(text.as_bytes())
+    }
+
+    /// Write the end of a row.
+    ///
+    /// By default a line break is written.
+    fn end_row(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all(b"\n")
+    }
+
+    /// Write the end of the output, after all rows.
+    ///
+    /// By default nothing is written.
+    fn footer(&mut self, _writer: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Returns whether the output can be centered in the terminal, when it is enabled in the [`Config`].
+    ///
+    /// Outputs with their own size, like svg documents, return false, since the spacing is not part of their size.
+    /// By default the output can be centered.
+    fn centered(&self) -> bool {
+        true
+    }
+}
+
+/// Foreground and background color of a cell, either of them can be unset.
+pub(crate) type CellColors = (Option<[u8; 3]>, Option<[u8; 3]>);
+
+/// Returns whether the foreground and background colors are similar, so a compacted output can keep the current colors.
+///
+/// Each channel can differ by at most the `tolerance`. An unset color is only similar to another unset color.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert!(similar_colors((Some([100, 0, 0]), None), (Some([104, 0, 0]), None), 4));
+/// ```
+pub(crate) fn similar_colors(current: CellColors, colors: CellColors, tolerance: u8) -> bool {
+    let similar = |current: Option<[u8; 3]>, color: Option<[u8; 3]>| match (current, color) {
+        (Some(current), Some(color)) => current
+            .iter()
+            .zip(color)
+            .all(|(current, channel)| current.abs_diff(channel) <= tolerance),
+        (current, color) => current.is_none() && color.is_none(),
+    };
+    similar(current.0, colors.0) && similar(current.1, colors.1)
+}
+
+#[cfg(test)]
+mod test_similar_colors {
+    use super::*;
+
+    #[test]
+    fn same_colors() {
+        let colors = (Some([1, 2, 3]), Some([4, 5, 6]));
+        assert!(similar_colors(colors, colors, 0));
+    }
+
+    #[test]
+    fn within_tolerance() {
+        assert!(similar_colors(
+            (Some([100, 100, 100]), None),
+            (Some([104, 96, 100]), None),
+            4
+        ));
+        assert!(!similar_colors(
+            (Some([100, 100, 100]), None),
+            (Some([105, 100, 100]), None),
+            4
+        ));
+    }
+
+    #[test]
+    fn unset_colors_differ() {
+        assert!(!similar_colors(
+            (Some([0, 0, 0]), None),
+            (Some([0, 0, 0]), Some([0, 0, 0])),
+            255
+        ));
+        assert!(similar_colors((None, None), (None, None), 0));
+    }
+}
+
+/// Returns the built-in [`Renderer`] for the [`TargetType`] of the config.
+///
+/// # Examples
+///
+/// ```compile_fail, compile will fail, this is an internal example
+/// let mut renderer = target::renderer(&config);
+/// renderer.cell(&mut writer, &cell)?;
+/// ```
+pub(crate) fn renderer(config: &Config) -> Box<dyn Renderer> {
+    match config.target {
+        TargetType::Shell(true, background_color) => Box::new(
+            //the vertical spacing is only added when the background is colored as well
+            ansi::AnsiRenderer::new(background_color)
+                .center_vertically(config.center_y && background_color)
+                .color_depth(config.color_depth)
+                .color_metric(config.color_metric)
+                .dither(config.dither)
+                .compact(config.compact)
+                .color_tolerance(config.color_tolerance),
+        ),
+        TargetType::AnsiFile(background_color) => Box::new(
+            ansi::AnsiRenderer::new(background_color)
+                .color_depth(config.color_depth)
+                .color_metric(config.color_metric)
+                .dither(config.dither)
+                .compact(config.compact)
+                .color_tolerance(config.color_tolerance),
+        ),
+        TargetType::HtmlFile(true, background_color) => Box::new(
+            html::HtmlRenderer::new(background_color)
+                .compact(config.compact)
+                .color_tolerance(config.color_tolerance),
+        ),
+        TargetType::SvgFile(color, background_color) => Box::new(
+            svg::SvgRenderer::new(color, background_color).font_family(config.font_family.clone()),
+        ),
+        TargetType::PngFile(color, background_color) => {
+            let renderer = png::PngRenderer::new(color, background_color);
+            //a zero cell size is rejected when the config is validated, so the default size can be kept
+            match (
+                NonZeroU32::new(config.cell_size.0),
+                NonZeroU32::new(config.cell_size.1),
+            ) {
+                (Some(width), Some(height)) => Box::new(renderer.cell_size(width, height)),
+                _ => Box::new(renderer),
+            }
+        }
+        //all other case, including a plain text file and shell without colors
+        _ => Box::new(plain::PlainRenderer),
+    }
+}
+
+#[cfg(test)]
+mod test_renderer {
+    use std::env;
+
+    use image::Rgba;
+
+    use super::*;
+    use crate::{config::ColorDepth, pixel::correlating_cell};
+
+    /// Render a single cell with the built-in renderer of the target.
+    fn cell_string(cell: &Cell, target: TargetType, color_depth: ColorDepth) -> String {
+        let mut output = Vec::new();
+        renderer(&Config {
+            target,
+            color_depth,
+            ..Default::default()
+        })
+        .cell(&mut output, cell)
+        .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn colored_char() {
+        //force color, this is not printed to the terminal anyways
+        env::set_var("CLICOLOR_FORCE", "1");
+
+        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
+        assert_eq!(
+            "\u{1b}[38;2;0;0;255m \u{1b}[0m", //blue color
+            cell_string(
+                &correlating_cell(&pixels, "#k. ", false),
+                TargetType::Shell(true, false),
+                ColorDepth::TrueColor
+            )
+        );
+    }
+
+    #[test]
+    fn ansi_colored_char_shell() {
+        //force color, this is not printed to the terminal anyways
+        env::set_var("CLICOLOR_FORCE", "1");
+        //just some random color
+        let pixels = vec![Rgba::<u8>::from([123, 42, 244, 255])];
+        //violet is mapped to bright blue by the default perceptual color metric
+        assert_eq!(
+            "\u{1b}[94m.\u{1b}[0m",
+            cell_string(
+                &correlating_cell(&pixels, "#k. ", false),
+                TargetType::Shell(true, false),
+                ColorDepth::Ansi16
+            )
+        );
+    }
+
+    #[test]
+    fn ansi_colored_char_ansi() {
+        //force color, this is not printed to the terminal anyways
+        env::set_var("CLICOLOR_FORCE", "1");
+        let pixels = vec![Rgba::<u8>::from([123, 42, 244, 255])];
+        //violet is mapped to bright blue by the default perceptual color metric
+        assert_eq!(
+            "\u{1b}[94m.\u{1b}[0m",
+            cell_string(
+                &correlating_cell(&pixels, "#k. ", false),
+                TargetType::AnsiFile(false),
+                ColorDepth::Ansi16
+            )
+        );
+    }
+
+    #[test]
+    fn colored_background_char_shell() {
+        //force color, this is not printed to the terminal anyways
+        env::set_var("CLICOLOR_FORCE", "1");
+
+        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
+        assert_eq!(
+            "\u{1b}[48;2;0;0;255m \u{1b}[0m",
+            cell_string(
+                &correlating_cell(&pixels, "#k. ", false),
+                TargetType::Shell(true, true),
+                ColorDepth::TrueColor
+            )
+        );
+    }
+
+    #[test]
+    fn colored_background_char_ansi() {
+        //force color, this is not printed to the terminal anyways
+        env::set_var("CLICOLOR_FORCE", "1");
+        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
+        assert_eq!(
+            "\u{1b}[48;2;0;0;255m \u{1b}[0m",
+            cell_string(
+                &correlating_cell(&pixels, "#k. ", false),
+                TargetType::AnsiFile(true),
+                ColorDepth::TrueColor
+            )
+        );
+    }
+
+    #[test]
+    fn target_file_returns_non_colored_string() {
+        //force color, this is not printed to the terminal anyways
+        env::set_var("CLICOLOR_FORCE", "1");
+
+        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
+        assert_eq!(
+            " ",
+            cell_string(
+                &correlating_cell(&pixels, "#k. ", false),
+                TargetType::File,
+                ColorDepth::TrueColor
+            )
+        );
+    }
+
+    #[test]
+    fn white_has_no_tag() {
+        //force color, this is not printed to the terminal anyways
+        env::set_var("CLICOLOR_FORCE", "1");
+
+        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
+        assert_eq!(
+            " ",
+            cell_string(
+                &correlating_cell(&pixels, "#k. ", false),
+                TargetType::HtmlFile(true, false),
+                ColorDepth::TrueColor
+            )
+        );
+    }
+
+    #[test]
+    fn target_html_colored_string() {
+        //force color, this is not printed to the terminal anyways
+        env::set_var("CLICOLOR_FORCE", "1");
+
+        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
+        assert_eq!(
+            "<span style=\"color: #0000FF\">.</span>",
+            cell_string(
+                &correlating_cell(&pixels, "#k:.", false),
+                TargetType::HtmlFile(true, false),
+                ColorDepth::TrueColor
+            )
+        );
+    }
+
+    #[test]
+    fn target_html_background_string() {
+        //force color, this is not printed to the terminal anyways
+        env::set_var("CLICOLOR_FORCE", "1");
+
+        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
+        assert_eq!(
+            "<span style=\"background-color: #0000FF\"> </span>",
+            cell_string(
+                &correlating_cell(&pixels, "#k. ", false),
+                TargetType::HtmlFile(true, true),
+                ColorDepth::TrueColor
+            )
+        );
+    }
+
+    #[test]
+    fn target_html_no_color() {
+        //force color, this is not printed to the terminal anyways
+        env::set_var("CLICOLOR_FORCE", "1");
+
+        let pixels = vec![Rgba::<u8>::from([0, 0, 255, 255])];
+        assert_eq!(
+            " ",
+            cell_string(
+                &correlating_cell(&pixels, "#k. ", false),
+                TargetType::HtmlFile(false, false),
+                ColorDepth::TrueColor
+            )
+        );
+    }
+
+    #[test]
+    fn zero_cell_size_does_not_panic() {
+        //the invalid size is only reported when the config is validated
+        let mut output = Vec::new();
+        let mut renderer = renderer(&Config {
+            target: TargetType::PngFile(true, false),
+            cell_size: (0, 16),
+            ..Default::default()
+        });
+        renderer.header(&mut output, 1, 1).unwrap();
+        renderer.footer(&mut output).unwrap();
+        assert!(image::load_from_memory(&output).is_ok());
+    }
+
+    #[test]
+    fn default_end_row_is_line_break() {
+        let mut output = Vec::new();
+        plain::PlainRenderer.end_row(&mut output).unwrap();
+        assert_eq!(b"\n", output.as_slice());
+    }
+}
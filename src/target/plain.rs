@@ -0,0 +1,48 @@
+use std::io::{self, Write};
+
+use crate::grid::Cell;
+
+use super::Renderer;
+
+/// Renderer for plain text without any colors.
+///
+/// It is used for plain text files, as well as the shell when colors are disabled.
+///
+/// # Examples
+/// ```
+/// use artem::{config::ConfigBuilder, target::plain::PlainRenderer};
+///
+/// let img = image::open("assets/images/standard_test_img.png").unwrap();
+/// let mut output = Vec::new();
+/// artem::convert_with_renderer(
+///     img,
+///     &ConfigBuilder::new().build().unwrap(),
+///     &mut PlainRenderer,
+///     &mut output,
+/// )
+/// .unwrap();
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PlainRenderer;
+
+impl Renderer for PlainRenderer {
+    fn cell(&mut self, writer: &mut dyn Write, cell: &Cell) -> io::Result<()> {
+        write!(writer, "{}", cell.character)
+    }
+}
+
+#[cfg(test)]
+mod test_plain_renderer {
+    use image::Rgb;
+
+    use super::*;
+
+    #[test]
+    fn writes_only_char() {
+        let mut output = Vec::new();
+        PlainRenderer
+            .cell(&mut output, &Cell::new('x', Rgb([154, 85, 54]), 97.32))
+            .unwrap();
+        assert_eq!("x", String::from_utf8(output).unwrap());
+    }
+}
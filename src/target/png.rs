@@ -0,0 +1,284 @@
+use std::{
+    io::{self, Write},
+    num::NonZeroU32,
+};
+
+use image::{codecs::png::PngEncoder, ImageEncoder, Rgb, RgbImage};
+
+use crate::{
+    font::{self, GLYPH_SIZE},
+    grid::Cell,
+};
+
+use super::Renderer;
+
+/// Color of the canvas, which is visible behind characters without a background color.
+const CANVAS_COLOR: Rgb<u8> = Rgb([0, 0, 0]);
+/// Color of characters without any color, like the border.
+const TEXT_COLOR: Rgb<u8> = Rgb([255, 255, 255]);
+
+/// Renderer for png images.
+///
+/// The characters are rasterized using an embedded 8x8 bitmap font, which is scaled to the size of a cell.
+/// As no system fonts are used, the resulting image is the same on every system.
+/// Characters which are not part of the font are drawn as a `?`.
+///
+/// The image is kept in memory and only encoded in the [`Renderer::footer`].
+///
+/// # Examples
+/// ```
+/// use std::num::NonZeroU32;
+///
+/// use artem::{config::ConfigBuilder, target::png::PngRenderer};
+///
+/// let img = image::open("assets/images/standard_test_img.png").unwrap();
+/// let mut output = Vec::new();
+/// artem::convert_with_renderer(
+///     img,
+///     &ConfigBuilder::new().build().unwrap(),
+///     &mut PngRenderer::new(true, false)
+///         .cell_size(NonZeroU32::new(8).unwrap(), NonZeroU32::new(16).unwrap()),
+///     &mut output,
+/// )
+/// .unwrap();
+/// assert!(image::load_from_memory(&output).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PngRenderer {
+    color: bool,
+    background_color: bool,
+    cell_width: u32,
+    cell_height: u32,
+    row: u32,
+    column: u32,
+    image: RgbImage,
+}
+
+impl PngRenderer {
+    /// Create a new [`PngRenderer`], which optionally colors the characters or the background.
+    pub fn new(color: bool, background_color: bool) -> Self {
+        Self {
+            color,
+            background_color,
+            cell_width: 8,
+            cell_height: 16,
+            row: 0,
+            column: 0,
+            image: RgbImage::new(0, 0),
+        }
+    }
+
+    /// Set the size of a single character in pixels, it defaults to 8x16.
+    pub fn cell_size(mut self, width: NonZeroU32, height: NonZeroU32) -> Self {
+        self.cell_width = width.get();
+        self.cell_height = height.get();
+        self
+    }
+
+    /// Draw a character at the current position and advance to the next column.
+    ///
+    /// Characters outside of the image, for example of a text that is longer than the row, are ignored.
+    /// The output is never centered, so the spacing does not push the characters out of the image.
+    fn draw_char(&mut self, char: char, foreground: Rgb<u8>, background: Rgb<u8>) {
+        let column = self.column;
+        self.column += 1;
+
+        let x_offset = column * self.cell_width;
+        let y_offset = self.row * self.cell_height;
+        if x_offset >= self.image.width() || y_offset >= self.image.height() {
+            return;
+        }
+
+        let glyph = glyph(char);
+        for y in 0..self.cell_height {
+            //scale the glyph to the cell size, using the nearest pixel
+            let bits = glyph[(y * GLYPH_SIZE / self.cell_height) as usize];
+            for x in 0..self.cell_width {
+                //the lowest bit is the leftmost pixel
+                let set = bits & (1 << (x * GLYPH_SIZE / self.cell_width)) != 0;
+                self.image.put_pixel(
+                    x_offset + x,
+                    y_offset + y,
+                    if set { foreground } else { background },
+                );
+            }
+        }
+    }
+}
+
+impl Default for PngRenderer {
+    fn default() -> Self {
+        Self::new(true, false)
+    }
+}
+
+impl Renderer for PngRenderer {
+    fn header(&mut self, _writer: &mut dyn Write, columns: u32, rows: u32) -> io::Result<()> {
+        self.image = RgbImage::from_pixel(
+            columns * self.cell_width,
+            rows * self.cell_height,
+            CANVAS_COLOR,
+        );
+        self.row = 0;
+        self.column = 0;
+        Ok(())
+    }
+
+    fn cell(&mut self, _writer: &mut dyn Write, cell: &Cell) -> io::Result<()> {
+        if let Some(background) = cell.background {
+            self.draw_char(cell.character, cell.color, background);
+        } else if self.background_color {
+            //use a dark or light character, depending on which one is better visible on the background
+            let foreground = if cell.luminosity > 127f32 {
+                CANVAS_COLOR
+            } else {
+                TEXT_COLOR
+            };
+            self.draw_char(cell.character, foreground, cell.color);
+        } else if self.color {
+            self.draw_char(cell.character, cell.color, CANVAS_COLOR);
+        } else {
+            self.draw_char(cell.character, TEXT_COLOR, CANVAS_COLOR);
+        }
+        Ok(())
+    }
+
+    fn text(&mut self, _writer: &mut dyn Write, text: &str) -> io::Result<()> {
+        for char in text.chars() {
+            self.draw_char(char, TEXT_COLOR, CANVAS_COLOR);
+        }
+        Ok(())
+    }
+
+    fn end_row(&mut self, _writer: &mut dyn Write) -> io::Result<()> {
+        self.row += 1;
+        self.column = 0;
+        Ok(())
+    }
+
+    fn footer(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        log::trace!("Encoding png image");
+        PngEncoder::new(writer)
+            .write_image(
+                &self.image,
+                self.image.width(),
+                self.image.height(),
+                image::ColorType::Rgb8,
+            )
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+
+    fn centered(&self) -> bool {
+        //the image is only as wide as the output, so the spacing would clip its right side
+        false
+    }
+}
+
+/// Returns the bitmap of a character from the embedded font, see [`font::glyph`].
+///
+/// Unknown characters are replaced by a `?`.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!([0; 8], glyph(' '));
+/// ```
+fn glyph(char: char) -> [u8; 8] {
+    font::glyph(char)
+        .or_else(|| font::glyph('?'))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test_glyph {
+    use super::*;
+
+    #[test]
+    fn unknown_char_is_question_mark() {
+        assert_eq!(glyph('?'), glyph('😀'));
+    }
+}
+
+#[cfg(test)]
+mod test_png_renderer {
+    use super::*;
+
+    fn cell(character: char, color: [u8; 3], luminosity: f32) -> Cell {
+        Cell::new(character, Rgb(color), luminosity)
+    }
+
+    /// Render a single row of cells and decode the resulting png.
+    fn render(mut renderer: PngRenderer, cells: &[Cell]) -> RgbImage {
+        let mut output = Vec::new();
+        renderer.header(&mut output, cells.len() as u32, 1).unwrap();
+        for cell in cells {
+            renderer.cell(&mut output, cell).unwrap();
+        }
+        renderer.end_row(&mut output).unwrap();
+        renderer.footer(&mut output).unwrap();
+        image::load_from_memory(&output).unwrap().to_rgb8()
+    }
+
+    #[test]
+    fn is_not_centered() {
+        assert!(!PngRenderer::new(true, false).centered());
+    }
+
+    #[test]
+    fn image_size_uses_cell_size() {
+        let image = render(
+            PngRenderer::new(true, false)
+                .cell_size(NonZeroU32::new(5).unwrap(), NonZeroU32::new(7).unwrap()),
+            &[cell('#', [255, 0, 0], 54f32); 3],
+        );
+        assert_eq!((15, 7), image.dimensions());
+    }
+
+    #[test]
+    fn colored_foreground() {
+        let image = render(
+            PngRenderer::new(true, false),
+            &[cell('█', [154, 85, 54], 97f32)],
+        );
+        //the full block is set in every pixel
+        assert!(image.pixels().all(|pixel| *pixel == Rgb([154, 85, 54])));
+    }
+
+    #[test]
+    fn colored_background() {
+        let image = render(
+            PngRenderer::new(true, true),
+            &[cell(' ', [154, 85, 54], 97f32)],
+        );
+        assert!(image.pixels().all(|pixel| *pixel == Rgb([154, 85, 54])));
+    }
+
+    #[test]
+    fn half_block_uses_both_colors() {
+        let mut half_block = cell('▀', [154, 85, 54], 97f32);
+        half_block.background = Some(Rgb([0, 0, 255]));
+        let image = render(PngRenderer::new(true, false), &[half_block]);
+        //the upper half block fills exactly the top half of the glyph
+        assert_eq!(Rgb([154, 85, 54]), *image.get_pixel(0, 0));
+        assert_eq!(Rgb([0, 0, 255]), *image.get_pixel(0, 15));
+    }
+
+    #[test]
+    fn no_color() {
+        let image = render(
+            PngRenderer::new(false, false),
+            &[cell('█', [154, 85, 54], 97f32)],
+        );
+        assert!(image.pixels().all(|pixel| *pixel == TEXT_COLOR));
+    }
+
+    #[test]
+    fn text_outside_image_is_ignored() {
+        let mut output = Vec::new();
+        let mut renderer = PngRenderer::new(false, false);
+        renderer.header(&mut output, 1, 1).unwrap();
+        renderer.text(&mut output, "  ██").unwrap();
+        renderer.footer(&mut output).unwrap();
+        let image = image::load_from_memory(&output).unwrap().to_rgb8();
+        assert!(image.pixels().all(|pixel| *pixel == CANVAS_COLOR));
+    }
+}
@@ -0,0 +1,295 @@
+use std::io::{self, Write};
+
+use crate::grid::Cell;
+
+use super::Renderer;
+
+/// Width of a single character in the svg document.
+const CELL_WIDTH: u32 = 10;
+/// Height of a single character/row in the svg document.
+const CELL_HEIGHT: u32 = 20;
+/// Font size of the characters, it is a bit smaller than the cell, so characters do not overlap.
+const FONT_SIZE: u32 = 16;
+
+/// Renderer for scalable vector graphics (svg) files.
+///
+/// Each row is written as a `<text>` element, with one `<tspan>` per character. Every character
+/// is placed at its own position, so the output is aligned even when the font is not monospaced.
+/// When using background colors, each cell additionally gets a colored `<rect>` behind it.
+///
+/// # Examples
+/// ```
+/// use artem::{config::ConfigBuilder, target::svg::SvgRenderer};
+///
+/// let img = image::open("assets/images/standard_test_img.png").unwrap();
+/// let mut output = Vec::new();
+/// artem::convert_with_renderer(
+///     img,
+///     &ConfigBuilder::new().build().unwrap(),
+///     &mut SvgRenderer::new(true, false).font_family("DejaVu Sans Mono".to_string()),
+///     &mut output,
+/// )
+/// .unwrap();
+/// assert!(String::from_utf8(output).unwrap().starts_with("<svg"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SvgRenderer {
+    color: bool,
+    background_color: bool,
+    font_family: String,
+    row: u32,
+    column: u32,
+    //the background rects have to be written before the text, so a row is buffered until it ends
+    backgrounds: String,
+    text: String,
+}
+
+impl SvgRenderer {
+    /// Create a new [`SvgRenderer`], which optionally colors the characters or the background.
+    pub fn new(color: bool, background_color: bool) -> Self {
+        Self {
+            color,
+            background_color,
+            font_family: "monospace".to_string(),
+            row: 0,
+            column: 0,
+            backgrounds: String::new(),
+            text: String::new(),
+        }
+    }
+
+    /// Set the font family used for the characters, it defaults to `monospace`.
+    pub fn font_family(mut self, font_family: String) -> Self {
+        self.font_family = font_family;
+        self
+    }
+
+    /// Add a character to the current row, optionally with a fill and background color.
+    fn push_char(&mut self, char: char, fill: Option<&str>, background: Option<&str>) {
+        let x = self.column * CELL_WIDTH;
+        self.column += 1;
+
+        if let Some(background) = background {
+            self.backgrounds.push_str(&format!(
+                "<rect x=\"{x}\" y=\"{}\" width=\"{CELL_WIDTH}\" height=\"{CELL_HEIGHT}\" fill=\"{background}\"/>",
+                self.row * CELL_HEIGHT
+            ));
+        }
+
+        //white spaces are not visible, so they don't need to be part of the text
+        if char.is_whitespace() {
+            return;
+        }
+
+        self.text.push_str(&format!("<tspan x=\"{x}\""));
+        if let Some(fill) = fill {
+            self.text.push_str(&format!(" fill=\"{fill}\""));
+        }
+        self.text.push('>');
+        self.text.push_str(&escape(&char.to_string()));
+        self.text.push_str("</tspan>");
+    }
+
+    /// Write the buffered row and start a new one.
+    fn flush_row(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all(self.backgrounds.as_bytes())?;
+        if !self.text.is_empty() {
+            //place the baseline near the bottom of the cell, leaving space for descenders
+            write!(
+                writer,
+                "<text y=\"{}\">{}</text>",
+                self.row * CELL_HEIGHT + FONT_SIZE,
+                self.text
+            )?;
+        }
+        writer.write_all(b"\n")?;
+        self.backgrounds.clear();
+        self.text.clear();
+        self.column = 0;
+        self.row += 1;
+        Ok(())
+    }
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        Self::new(true, false)
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn header(&mut self, writer: &mut dyn Write, columns: u32, rows: u32) -> io::Result<()> {
+        let width = columns * CELL_WIDTH;
+        let height = rows * CELL_HEIGHT;
+        writeln!(
+            writer,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" \
+            font-family=\"{}\" font-size=\"{FONT_SIZE}\" xml:space=\"preserve\">",
+            escape(&self.font_family)
+        )
+    }
+
+    fn cell(&mut self, _writer: &mut dyn Write, cell: &Cell) -> io::Result<()> {
+        let [red, green, blue] = cell.color.0;
+        let color = format!("#{red:02X}{green:02X}{blue:02X}");
+        if let Some(background) = cell.background {
+            let [red, green, blue] = background.0;
+            let background = format!("#{red:02X}{green:02X}{blue:02X}");
+            self.push_char(cell.character, Some(&color), Some(&background));
+        } else if self.background_color {
+            self.push_char(cell.character, None, Some(&color));
+        } else if self.color {
+            self.push_char(cell.character, Some(&color), None);
+        } else {
+            self.push_char(cell.character, None, None);
+        }
+        Ok(())
+    }
+
+    fn text(&mut self, _writer: &mut dyn Write, text: &str) -> io::Result<()> {
+        for char in text.chars() {
+            self.push_char(char, None, None);
+        }
+        Ok(())
+    }
+
+    fn end_row(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        self.flush_row(writer)
+    }
+
+    fn footer(&mut self, writer: &mut dyn Write) -> io::Result<()> {
+        //the last row (for example the bottom border) might not have been ended
+        if self.column != 0 {
+            self.flush_row(writer)?;
+        }
+        writer.write_all(b"</svg>\n")
+    }
+
+    fn centered(&self) -> bool {
+        //the spacing would push the characters outside of the view box
+        false
+    }
+}
+
+/// Escape the characters that can not be used as text or attribute values in xml.
+///
+/// # Examples
+/// ```compile_fail, compile will fail, this is an internal example
+/// assert_eq!("&lt;&amp;&gt;", escape("<&>"));
+/// ```
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test_escape {
+    use super::*;
+
+    #[test]
+    fn escapes_xml_chars() {
+        assert_eq!("&lt;a&gt; &amp; &quot;&apos;", escape("<a> & \"'"));
+    }
+
+    #[test]
+    fn keeps_other_chars() {
+        assert_eq!("MWNX ║═", escape("MWNX ║═"));
+    }
+}
+
+#[cfg(test)]
+mod test_svg_renderer {
+    use image::Rgb;
+
+    use super::*;
+
+    fn cell(character: char) -> Cell {
+        Cell::new(character, Rgb([154, 85, 54]), 97.32)
+    }
+
+    fn render(mut renderer: SvgRenderer, cells: &[Cell]) -> String {
+        let mut output = Vec::new();
+        renderer.header(&mut output, cells.len() as u32, 1).unwrap();
+        for cell in cells {
+            renderer.cell(&mut output, cell).unwrap();
+        }
+        renderer.end_row(&mut output).unwrap();
+        renderer.footer(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn is_not_centered() {
+        assert!(!SvgRenderer::new(true, false).centered());
+    }
+
+    #[test]
+    fn header_has_size() {
+        assert!(render(SvgRenderer::new(true, false), &[cell('x'), cell('y')]).starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"20\" viewBox=\"0 0 20 20\" \
+            font-family=\"monospace\" font-size=\"16\" xml:space=\"preserve\">\n"
+        ));
+    }
+
+    #[test]
+    fn colored_chars() {
+        assert!(render(SvgRenderer::new(true, false), &[cell('x'), cell(' '), cell('<')])
+            .ends_with(
+                "<text y=\"16\"><tspan x=\"0\" fill=\"#9A5536\">x</tspan><tspan x=\"20\" fill=\"#9A5536\">&lt;</tspan></text>\n</svg>\n"
+            ));
+    }
+
+    #[test]
+    fn two_colored_cell() {
+        let mut half_block = cell('▀');
+        half_block.background = Some(Rgb([0, 0, 255]));
+        //the background of the cell is used, even if background colors are disabled
+        assert!(render(SvgRenderer::new(true, false), &[half_block]).ends_with(
+            "<rect x=\"0\" y=\"0\" width=\"10\" height=\"20\" fill=\"#0000FF\"/><text y=\"16\"><tspan x=\"0\" fill=\"#9A5536\">▀</tspan></text>\n</svg>\n"
+        ));
+    }
+
+    #[test]
+    fn background_rects() {
+        assert!(render(SvgRenderer::new(true, true), &[cell('x')]).ends_with(
+            "<rect x=\"0\" y=\"0\" width=\"10\" height=\"20\" fill=\"#9A5536\"/><text y=\"16\"><tspan x=\"0\">x</tspan></text>\n</svg>\n"
+        ));
+    }
+
+    #[test]
+    fn no_color() {
+        assert!(render(SvgRenderer::new(false, false), &[cell('x')])
+            .ends_with("<text y=\"16\"><tspan x=\"0\">x</tspan></text>\n</svg>\n"));
+    }
+
+    #[test]
+    fn custom_font_family() {
+        assert!(render(
+            SvgRenderer::new(true, false).font_family("\"Fira Code\"".to_string()),
+            &[cell('x')]
+        )
+        .contains("font-family=\"&quot;Fira Code&quot;\""));
+    }
+
+    #[test]
+    fn unfinished_row_is_written_in_footer() {
+        let mut output = Vec::new();
+        let mut renderer = SvgRenderer::new(false, false);
+        renderer.text(&mut output, "╚═╝").unwrap();
+        renderer.footer(&mut output).unwrap();
+        assert_eq!(
+            "<text y=\"16\"><tspan x=\"0\">╚</tspan><tspan x=\"10\">═</tspan><tspan x=\"20\">╝</tspan></text>\n</svg>\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
@@ -0,0 +1,142 @@
+pub mod brightness {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_none() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--brightness");
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: a value is required for '--brightness <brightness>' but none was supplied",
+        ));
+    }
+
+    #[test]
+    fn negative_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-c",
+            "# ",
+            "--brightness",
+            "-1",
+            "--no-color",
+        ]);
+        //the whole image is black
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_match("^[ \n]+$").unwrap());
+    }
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-c",
+            "# ",
+            "--brightness",
+            "1",
+            "--no-color",
+        ]);
+        //the whole image is white
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_match("^[#\n]+$").unwrap());
+    }
+}
+
+pub mod gamma {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_invalid_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--gamma", "0"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "[ERROR] The adjustment Gamma(0.0) has an invalid value",
+        ));
+    }
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--gamma", "1"]);
+        //a gamma of 1 does not change the image
+        let desired_output =
+            std::fs::read_to_string("assets/standard_test_img/standard_test_img.txt").unwrap();
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(desired_output));
+    }
+}
+
+pub mod saturation {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_invalid_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--saturation", "-1"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: unexpected argument '-1' found",
+        ));
+    }
+}
+
+pub mod order {
+    use assert_cmd::prelude::*;
+    use std::process::Command;
+
+    fn convert(args: &[&str]) -> String {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--no-color"])
+            .args(args);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn adjustments_are_applied_in_order() {
+        //brightening first clips the bright parts, which can not be restored by darkening afterwards
+        assert_ne!(
+            convert(&["--brightness", "0.5", "--brightness", "-0.5"]),
+            convert(&["--brightness", "-0.5", "--brightness", "0.5"])
+        );
+        assert_eq!(
+            convert(&["--brightness", "0.5", "--brightness", "-0.5"]),
+            convert(&[
+                "--brightness",
+                "0.5",
+                "--contrast",
+                "1",
+                "--brightness",
+                "-0.5"
+            ])
+        );
+    }
+
+    #[test]
+    fn flags_are_ordered() {
+        assert_ne!(
+            convert(&["--auto-levels", "--brightness", "0.3"]),
+            convert(&["--brightness", "0.3", "--auto-levels"])
+        );
+    }
+
+    #[test]
+    fn sharpen_and_equalize_change_output() {
+        let plain = convert(&[]);
+        assert_ne!(plain, convert(&["--sharpen", "2"]));
+        assert_ne!(plain, convert(&["--equalize"]));
+        assert_ne!(plain, convert(&["--contrast", "2", "--hue", "90"]));
+    }
+}
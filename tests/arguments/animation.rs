@@ -0,0 +1,98 @@
+pub mod animate {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_conflict_output() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/animation.gif")
+            .args(["--animate", "-o", "/tmp/animation.txt"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the argument '--animate' cannot be used with '--output <output-file>'",
+        ));
+    }
+
+    #[test]
+    fn frames_are_played_in_place() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/animation.gif").args([
+            "--animate",
+            "--no-color",
+            "-c",
+            "# ",
+            "-s",
+            "5",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //the cursor moves back up before the second and third frame
+        assert_eq!(2, output.matches("\x1b[5A").count());
+        assert!(output.starts_with("\x1b[?25l#"));
+        assert!(output.ends_with("\x1b[?25h"));
+    }
+
+    #[test]
+    fn still_image_is_single_frame() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--animate", "--no-color", "-s", "5"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\x1b[5A").not());
+    }
+
+    #[test]
+    fn exif_orientation_is_corrected() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/exif_orientation.png").args([
+            "--animate",
+            "-c",
+            "# ",
+            "--no-color",
+            "-s",
+            "10",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //the frame is rotated like a still image, with the white half on the right
+        let frame = output
+            .trim_start_matches("\x1b[?25l")
+            .trim_end_matches("\x1b[?25h");
+        for line in frame.lines() {
+            assert!(line.starts_with(' '));
+            assert!(line.ends_with('#'));
+        }
+    }
+}
+
+pub mod loop_animation {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_requires_animate() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/animation.gif").arg("--loop");
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the following required arguments were not provided:\n  --animate",
+        ));
+    }
+}
+
+pub mod fps {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/animation.gif")
+            .args(["--animate", "--fps", "0"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: invalid value '0' for '--fps <fps>'",
+        ));
+    }
+}
@@ -74,4 +74,50 @@ pub mod characters {
             ));
         }
     }
+
+    #[test]
+    fn arg_calibrate_sort() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-c", "#@:. ", "--no-color"]);
+        let sorted = cmd.assert().success().get_output().stdout.clone();
+
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-c",
+            " .@:#",
+            "--calibrate",
+            "sort",
+            "--no-color",
+        ]);
+        //the shuffled characters are sorted from dark to light
+        cmd.assert().success().stdout(predicate::eq(sorted));
+    }
+
+    #[test]
+    fn arg_calibrate_linear() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-c",
+            " .,@",
+            "--calibrate",
+            "linear",
+            "--no-color",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //the period and comma are nearly as light as each other, so only one of them is used
+        assert!(output.contains('@'));
+        assert!(!(output.contains('.') && output.contains(',')));
+    }
+
+    #[test]
+    fn arg_calibrate_invalid_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--calibrate", "random"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: invalid value 'random' for '--calibrate <calibrate>'",
+        ));
+    }
 }
@@ -102,3 +102,277 @@ pub mod background_color {
             .stdout(predicate::str::starts_with(load_correct_file()));
     }
 }
+
+pub mod color_depth {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_none() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--color-depth");
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: a value is required for '--color-depth <color-depth>' but none was supplied",
+        ));
+    }
+
+    #[test]
+    fn arg_invalid_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-depth", "8"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: invalid value '8' for '--color-depth <color-depth>'",
+        ));
+    }
+
+    #[test]
+    fn arg_conflict_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-depth", "256", "--no-color"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the argument '--color-depth <color-depth>' cannot be used with '--no-color'",
+        ));
+    }
+
+    #[test]
+    fn ansi256_colors() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.env("CLICOLOR_FORCE", "1")
+            .arg("assets/images/standard_test_img.png")
+            .args(["--color-depth", "256"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with("\u{1b}[38;5;"));
+    }
+
+    #[test]
+    fn ansi256_background_colors() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.env("CLICOLOR_FORCE", "1")
+            .arg("assets/images/standard_test_img.png")
+            .args(["--color-depth", "256", "--background"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with("\u{1b}[48;5;"));
+    }
+
+    #[test]
+    fn detected_from_term() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.env("CLICOLOR_FORCE", "1")
+            .env_remove("COLORTERM")
+            .env("TERM", "xterm-256color")
+            .arg("assets/images/standard_test_img.png");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with("\u{1b}[38;5;"));
+    }
+}
+
+pub mod color_metric {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_invalid_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-metric", "cmyk"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: invalid value 'cmyk' for '--color-metric <color-metric>'",
+        ));
+    }
+
+    #[test]
+    fn arg_conflict_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--color-metric",
+            "redmean",
+            "--no-color",
+        ]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the argument '--color-metric <color-metric>' cannot be used with '--no-color'",
+        ));
+    }
+
+    #[test]
+    fn metrics_change_colors() {
+        let output = |metric: &str| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.env("CLICOLOR_FORCE", "1")
+                .arg("examples/abraham_lincoln.jpg")
+                .args(["--color-depth", "256", "--color-metric", metric]);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        assert_ne!(output("euclidean"), output("ciede2000"));
+    }
+}
+
+pub mod blank_transparent {
+    use assert_cmd::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn transparent_tiles_have_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        //the left half of the image is fully transparent
+        cmd.env("CLICOLOR_FORCE", "1")
+            .env("COLORTERM", "truecolor")
+            .arg("assets/images/transparent.png")
+            .args(["-s", "10", "--blank-transparent"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        for line in output.lines() {
+            assert!(line.starts_with("          \x1b[38;2;"));
+        }
+    }
+
+    #[test]
+    fn transparent_tiles_are_colored_without_arg() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.env("CLICOLOR_FORCE", "1")
+            .env("COLORTERM", "truecolor")
+            .arg("assets/images/transparent.png")
+            .args(["-s", "10"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        for line in output.lines() {
+            assert!(line.starts_with("\x1b[38;2;0;0;0m "));
+        }
+    }
+}
+
+pub mod background_fill {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/transparent.png")
+            .args(["--background-fill", "white"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: invalid value 'white' for '--background-fill <COLOR>'",
+        ));
+    }
+
+    #[test]
+    fn transparency_is_filled() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        //the transparent half becomes as bright as the opaque one, the semi-transparent quarter is darker
+        cmd.arg("assets/images/transparent.png").args([
+            "-c",
+            "#. ",
+            "--no-color",
+            "-s",
+            "10",
+            "--background-fill",
+            "#fff",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_match("^(#{20}\n)+$").unwrap());
+    }
+
+    #[test]
+    fn semi_transparency_is_blended() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/transparent.png").args([
+            "-c",
+            "#. ",
+            "--no-color",
+            "-s",
+            "10",
+            "--background-fill",
+            "#000000",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(Some("          ##########"), output.lines().next());
+        assert_eq!(Some("          .........."), output.lines().last());
+    }
+}
+
+pub mod compact {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    /// Returns the colored terminal output of the example image with the given arguments.
+    fn output(args: &[&str]) -> Vec<u8> {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.env("CLICOLOR_FORCE", "1")
+            .env("COLORTERM", "truecolor")
+            .arg("examples/abraham_lincoln.jpg")
+            .args(args);
+        cmd.assert().success().get_output().stdout.clone()
+    }
+
+    #[test]
+    fn arg_conflict_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--compact", "--no-color"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the argument '--compact' cannot be used with '--no-color'",
+        ));
+    }
+
+    #[test]
+    fn output_is_smaller() {
+        let compact = output(&["--compact"]);
+        assert!(compact.len() < output(&[]).len());
+        //colored rows are reset once at their end, rows of only spaces are not colored at all
+        for line in String::from_utf8(compact).unwrap().lines() {
+            assert!(line.matches("\x1b[0m").count() <= 1);
+            assert!(line.ends_with("\x1b[0m") || !line.contains('\x1b'));
+        }
+    }
+
+    #[test]
+    fn tolerance_requires_compact() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--color-tolerance", "8"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the following required arguments were not provided:\n  --compact",
+        ));
+    }
+
+    #[test]
+    fn tolerance_is_smaller() {
+        assert!(
+            output(&["--compact", "--color-tolerance", "32"]).len() < output(&["--compact"]).len()
+        );
+    }
+
+    #[test]
+    fn html_spans_are_merged() {
+        let dir = std::env::temp_dir().join("artem_compact_html");
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = |name: &str| {
+            let path = dir.join(name);
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["-o", path.to_str().unwrap()]);
+            if name.starts_with("compact") {
+                cmd.arg("--compact");
+            }
+            cmd.assert().success();
+            std::fs::read_to_string(path).unwrap()
+        };
+        let compact = file("compact.html");
+        assert!(compact.matches("<span").count() < file("default.html").matches("<span").count());
+        assert_eq!(
+            compact.matches("<span").count(),
+            compact.matches("</span>").count()
+        );
+    }
+}
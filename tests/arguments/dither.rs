@@ -0,0 +1,60 @@
+pub mod dither {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_none() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--dither");
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: a value is required for '--dither <dither>' but none was supplied",
+        ));
+    }
+
+    #[test]
+    fn arg_invalid_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--dither", "random"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: invalid value 'random' for '--dither <dither>'",
+        ));
+    }
+
+    #[test]
+    fn arg_none_is_unchanged() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-c", " .:#", "--dither", "none"]);
+        //only check first line
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "::::::::::..................................:#########################::::::::::\n",
+        ));
+    }
+
+    #[test]
+    fn arg_bayer() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-c", " .:#", "--dither", "bayer"]);
+        //only check first two lines, the pattern of the gradient alternates between them
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "::::::::::..................:.:.:.:.:.:.:.:.::#:#:#:#:#:#:#:#:########::::::::::\n\
+            ::.:::.::: ... ..............:...:...:.:.:.::::::::::#:#:#:#:#:#:#:#:#.:::.:::.:\n",
+        ));
+    }
+
+    #[test]
+    fn arg_error_diffusion() {
+        for method in ["floyd-steinberg", "atkinson"] {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["-c", " .:#", "--dither", method]);
+            cmd.assert().success().stdout(predicate::str::starts_with(
+                "::::::::::..................................:#########################::::::::::\n",
+            ).not());
+        }
+    }
+}
@@ -60,7 +60,7 @@ pub mod input {
         cmd.arg("--help");
         cmd.assert().success().stdout(predicate::str::contains(
             //only test beginning, since different formatting would break the rest
-            "Paths to the target image. The original image is NOT altered.",
+            "Paths to the target image. Use - to read the image from stdin.",
         ));
     }
 
@@ -175,3 +175,70 @@ pub mod url_input {
         ));
     }
 }
+
+pub mod stdin_input {
+    //the command of assert_cmd can write to the stdin of the process
+    use assert_cmd::Command;
+    use predicates::prelude::*;
+
+    use crate::common::load_correct_file;
+
+    #[test]
+    fn correct_input() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.arg("-")
+            .write_stdin(std::fs::read("assets/images/standard_test_img.png").unwrap());
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(load_correct_file()));
+    }
+
+    #[test]
+    fn stdin_and_file_input() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        let ascii_img = load_correct_file();
+        cmd.args(["-", "assets/images/standard_test_img.png"])
+            .write_stdin(std::fs::read("assets/images/standard_test_img.png").unwrap());
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(format!(
+                "{ascii_img}\n{ascii_img}"
+            )));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.arg("-").write_stdin("not an image");
+        cmd.assert()
+            .failure()
+            .code(66)
+            .stderr(predicate::str::contains(
+                "The image format could not be determined",
+            ));
+    }
+
+    #[test]
+    fn multiple_stdin_input() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.args(["-", "-"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "Stdin can only be used as input once",
+        ));
+    }
+
+    #[test]
+    fn animated_input() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+
+        cmd.args(["-", "--animate", "--no-color", "-s", "5"])
+            .write_stdin(std::fs::read("assets/images/animation.gif").unwrap());
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("\x1b[5A"));
+    }
+}
@@ -1,9 +1,13 @@
 ///! Tests for the different arguments.
 ///! Some of the them are bundled into the same file, since they are similar.
 ///! For example all color arguments.
+pub mod adjust;
+pub mod animation;
 pub mod characters;
 pub mod color;
+pub mod dither;
 pub mod input;
+pub mod mode;
 pub mod output;
 pub mod scale;
 pub mod size;
@@ -0,0 +1,157 @@
+pub mod mode {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_invalid_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mode", "blocks"]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: invalid value 'blocks' for '--mode <mode>'",
+        ));
+    }
+
+    #[test]
+    fn arg_half_block_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mode", "half-block", "--no-color"]);
+        //only check first line, the gray bars at the top are split into filled and empty blocks
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "          ██████████████████████████████████                                    \n",
+        ));
+    }
+
+    #[test]
+    fn arg_half_block_truecolor() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mode", "half-block", "--color-depth", "truecolor"])
+            .env("CLICOLOR_FORCE", "1");
+        //the top pixel is the foreground, the bottom pixel the background
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "\u{1b}[48;2;103;103;103;38;2;103;103;103m▀\u{1b}[0m",
+        ));
+    }
+
+    #[test]
+    fn arg_half_block_rows() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mode", "half-block", "--no-color"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //every row has the same number of columns as in the ascii mode
+        assert!(output
+            .lines()
+            .all(|line| line.chars().count() == 80 && line.chars().all(|c| " ▀▄█".contains(c))));
+    }
+
+    #[test]
+    fn arg_braille_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mode", "braille", "--no-color"]);
+        //only check first line, the gray bars at the top either have all or no dots set
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀\n",
+        ));
+    }
+
+    #[test]
+    fn arg_braille_dither() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--mode",
+            "braille",
+            "--no-color",
+            "--dither",
+            "bayer",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //the gray bars are dithered into patterns of single dots
+        let first_line = output.lines().next().unwrap();
+        assert!(first_line.chars().any(|c| c != '⠀' && c != '⣿'));
+        assert!(output
+            .lines()
+            .all(|line| line.chars().all(|c| ('\u{2800}'..='\u{28FF}').contains(&c))));
+    }
+
+    #[test]
+    fn arg_quadrant_truecolor() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--mode", "quadrant", "--color-depth", "truecolor"])
+            .env("CLICOLOR_FORCE", "1");
+        //the gray bar has a single color, so the whole cell is the foreground
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "\u{1b}[48;2;103;103;103;38;2;103;103;103m█\u{1b}[0m",
+        ));
+    }
+
+    #[test]
+    fn arg_sextant_no_color() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--mode",
+            "sextant",
+            "--no-color",
+            "--dither",
+            "atkinson",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //the gradient is dithered into sextants
+        assert!(output
+            .chars()
+            .any(|c| ('\u{1FB00}'..='\u{1FB3B}').contains(&c)));
+        assert!(output.lines().all(|line| line.chars().count() == 80));
+    }
+
+    #[test]
+    fn arg_shape_outline() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--mode",
+            "shape",
+            "--outline",
+            "--no-color",
+            "--characters",
+            "/\\|_-. ",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //the edges of the bars are drawn as lines
+        assert!(output.lines().next().unwrap().contains('|'));
+        assert!(output.lines().any(|line| line.chars().all(|c| c == '_')));
+    }
+}
+
+pub mod sextant_fallback {
+    use assert_cmd::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_replaces_sextants() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--mode",
+            "sextant",
+            "--sextant-fallback",
+            "--no-color",
+            "--dither",
+            "atkinson",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //the gradient is dithered into quadrants instead of sextants
+        assert!(!output
+            .chars()
+            .any(|c| ('\u{1FB00}'..='\u{1FB3B}').contains(&c)));
+        assert!(output.chars().any(|c| ('▖'..='▟').contains(&c)));
+        assert!(output.lines().all(|line| line.chars().count() == 80));
+    }
+}
@@ -42,6 +42,227 @@ pub mod output_file {
         fs::remove_file("/tmp/ascii.html").unwrap();
     }
 
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn file_is_svg() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-o", "/tmp/ascii.svg"]);
+        //only check first line
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "Written 65733 bytes to /tmp/ascii.svg",
+        ));
+        let file_output = fs::read_to_string("/tmp/ascii.svg").unwrap();
+        //delete output file
+        fs::remove_file("/tmp/ascii.svg").unwrap();
+        assert!(file_output
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800\" height=\"520\""));
+        assert!(file_output.ends_with("</svg>"));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn svg_is_not_centered() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_centered.svg",
+            "--centerX",
+        ]);
+        cmd.assert().success();
+        let file_output = fs::read_to_string("/tmp/ascii_centered.svg").unwrap();
+        //delete output file
+        fs::remove_file("/tmp/ascii_centered.svg").unwrap();
+        //the characters start at the left edge of the view box, without any spacing in front of them
+        assert!(file_output
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800\" height=\"520\""));
+        assert!(file_output
+            .lines()
+            .nth(1)
+            .unwrap()
+            .contains("<tspan x=\"0\""));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn svg_font_family() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_font.svg",
+            "--font-family",
+            "Fira Code",
+        ]);
+        cmd.assert().success();
+        let file_output = fs::read_to_string("/tmp/ascii_font.svg").unwrap();
+        //delete output file
+        fs::remove_file("/tmp/ascii_font.svg").unwrap();
+        assert!(file_output.contains("font-family=\"Fira Code\""));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn file_is_png() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-o", "/tmp/ascii.png"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with("Written "));
+        let img = image::open("/tmp/ascii.png").unwrap();
+        //delete output file
+        fs::remove_file("/tmp/ascii.png").unwrap();
+        //80 columns and 26 rows with the default 8x16 cells
+        assert_eq!((640, 416), (img.width(), img.height()));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn png_is_not_centered() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_centered.png",
+            "--centerX",
+            "--centerY",
+        ]);
+        cmd.assert().success();
+        let img = image::open("/tmp/ascii_centered.png").unwrap();
+        //delete output file
+        fs::remove_file("/tmp/ascii_centered.png").unwrap();
+        //the image has the size of the output, the same as without centering
+        assert_eq!((640, 416), (img.width(), img.height()));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn png_is_written_unchanged() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["-o", "/tmp/ascii_unchanged.png"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let bytes = fs::read("/tmp/ascii_unchanged.png").unwrap();
+        //delete output file
+        fs::remove_file("/tmp/ascii_unchanged.png").unwrap();
+        //binary outputs keep all of their bytes, including a trailing line break
+        assert_eq!(
+            format!(
+                "Written {} bytes to /tmp/ascii_unchanged.png\n",
+                bytes.len()
+            ),
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn png_cell_size_border() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_cell_size.png",
+            "--cell-size",
+            "4x8",
+            "--border",
+        ]);
+        cmd.assert().success();
+        let img = image::open("/tmp/ascii_cell_size.png").unwrap();
+        //delete output file
+        fs::remove_file("/tmp/ascii_cell_size.png").unwrap();
+        //the border is part of the 80 columns, but adds two additional rows
+        assert_eq!((80 * 4, 28 * 8), (img.width(), img.height()));
+    }
+
+    #[test]
+    fn png_cell_size_invalid() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "-o",
+            "/tmp/ascii_invalid.png",
+            "--cell-size",
+            "8",
+        ]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: invalid value '8' for '--cell-size <WIDTHxHEIGHT>'",
+        ));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn file_is_cast() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/animation.gif").args([
+            "-o",
+            "/tmp/ascii.cast",
+            "-s",
+            "20",
+            "--border",
+        ]);
+        cmd.assert().success();
+        let file_output = fs::read_to_string("/tmp/ascii.cast").unwrap();
+        //delete output file
+        fs::remove_file("/tmp/ascii.cast").unwrap();
+        let lines: Vec<&str> = file_output.lines().collect();
+        //header, one event for each of the three frames and the end of the last frame
+        assert_eq!(5, lines.len());
+        assert_eq!(r#"{"version": 2, "width": 20, "height": 7}"#, lines[0]);
+        assert!(lines[1].starts_with(r#"[0.000000, "o", "\u001b[H╔"#));
+        assert!(lines[2].starts_with(r#"[0.100000, "o", "#));
+        assert_eq!(r#"[0.300000, "o", ""]"#, lines[4]);
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn cast_fps() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/animation.gif")
+            .args(["-o", "/tmp/ascii_fps.cast", "--fps", "4"]);
+        cmd.assert().success();
+        let file_output = fs::read_to_string("/tmp/ascii_fps.cast").unwrap();
+        //delete output file
+        fs::remove_file("/tmp/ascii_fps.cast").unwrap();
+        assert!(file_output
+            .lines()
+            .nth(2)
+            .unwrap()
+            .starts_with("[0.250000,"));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn cast_exif_orientation() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/exif_orientation.png").args([
+            "-o",
+            "/tmp/ascii_exif.cast",
+            "-c",
+            "# ",
+            "--no-color",
+            "-s",
+            "10",
+        ]);
+        cmd.assert().success();
+        let file_output = fs::read_to_string("/tmp/ascii_exif.cast").unwrap();
+        //delete output file
+        fs::remove_file("/tmp/ascii_exif.cast").unwrap();
+        //the frame is rotated like a still image, with the white half on the right
+        assert!(file_output
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with(r#"[0.000000, "o", "\u001b[H          ##########\r\n"#));
+    }
+
     #[test]
     //windows does not like this test, it can not create the file
     #[cfg(not(target_os = "windows"))]
@@ -58,6 +279,200 @@ pub mod output_file {
     }
 }
 
+pub mod output_dir {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::{fs, path::Path, process::Command};
+
+    #[test]
+    fn arg_conflicts_output() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--output-dir",
+            "/tmp/artem_conflict",
+            "-o",
+            "/tmp/test.txt",
+        ]);
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: the argument '--output-dir <DIR>' cannot be used with '--output <output-file>'",
+        ));
+    }
+
+    #[test]
+    fn format_requires_output_dir() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--format", "html"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "the following required arguments were not provided",
+        ));
+    }
+
+    #[test]
+    fn directory_requires_output_dir() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images");
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "assets/images is a directory, directories can only be converted with --output-dir",
+        ));
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn file_keeps_name() {
+        let dir = "/tmp/artem_output_dir_file";
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--output-dir",
+            dir,
+            "--format",
+            "html",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(format!(
+                "Converted 1 of 1 images to {dir}"
+            )));
+        let output = fs::read_to_string(Path::new(dir).join("standard_test_img.html")).unwrap();
+        assert!(output.starts_with("<span style=\"color: #"));
+        //delete output directory
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn directory_include() {
+        let dir = "/tmp/artem_output_dir_include";
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images")
+            .args(["--output-dir", dir, "--include", "*.png", "-s", "20"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(format!(
+                "Converted 3 of 3 images to {dir}"
+            )));
+        let mut outputs: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        outputs.sort();
+        assert_eq!(
+            vec![
+                "exif_orientation.txt",
+                "standard_test_img.txt",
+                "transparent.txt"
+            ],
+            outputs
+        );
+        //delete output directory
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn directory_exclude() {
+        let dir = "/tmp/artem_output_dir_exclude";
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images").args([
+            "--output-dir",
+            dir,
+            "--include",
+            "*.png",
+            "--exclude",
+            "*_*",
+            "-s",
+            "20",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::starts_with(format!(
+                "Converted 1 of 1 images to {dir}"
+            )));
+        assert!(Path::new(dir).join("transparent.txt").is_file());
+        //delete output directory
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn directory_keeps_subdirectories() {
+        let input = "/tmp/artem_output_dir_nested";
+        let dir = "/tmp/artem_output_dir_nested_out";
+        fs::create_dir_all(Path::new(input).join("sub")).unwrap();
+        fs::copy(
+            "assets/images/animation.gif",
+            Path::new(input).join("sub/animation.gif"),
+        )
+        .unwrap();
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg(input)
+            .args(["--output-dir", dir, "--format", "cast", "-s", "20"]);
+        cmd.assert().success();
+        let output = fs::read_to_string(Path::new(dir).join("sub/animation.cast")).unwrap();
+        //header and three frames with the event at the end
+        assert_eq!(5, output.lines().count());
+        //delete input and output directory
+        fs::remove_dir_all(input).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    //windows does not like this test, it can not create the file
+    #[cfg(not(target_os = "windows"))]
+    fn failed_images_are_summarized() {
+        let input = "/tmp/artem_output_dir_failed";
+        let dir = "/tmp/artem_output_dir_failed_out";
+        fs::create_dir_all(input).unwrap();
+        fs::write(Path::new(input).join("broken.png"), "not an image").unwrap();
+        fs::copy(
+            "assets/images/standard_test_img.png",
+            Path::new(input).join("image.png"),
+        )
+        .unwrap();
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg(input).args(["--output-dir", dir]);
+        cmd.assert()
+            .failure()
+            .code(66)
+            .stdout(predicate::str::starts_with(format!(
+                "Converted 1 of 2 images to {dir}\nFailed to convert {input}/broken.png"
+            )));
+        //the failed image does not leave an output behind
+        assert!(!Path::new(dir).join("broken.txt").exists());
+        assert!(!Path::new(dir).join(".broken.txt.tmp").exists());
+        assert!(Path::new(dir).join("image.txt").is_file());
+        //delete input and output directory
+        fs::remove_dir_all(input).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
+
+pub mod glob_input {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn pattern_is_expanded() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_*.png").args(["-s", "20"]);
+        cmd.assert().success();
+    }
+
+    #[test]
+    fn pattern_without_matches() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/*.missing");
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "No files match assets/images/*.missing",
+        ));
+    }
+}
+
 pub mod verbosity {
     use assert_cmd::prelude::*;
     use predicates::prelude::*;
@@ -136,6 +136,316 @@ pub mod hysteresis {
     }
 }
 
+pub mod edge_characters {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn outline_is_required() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--edge-characters");
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::starts_with(
+                "error: the following required arguments were not provided:",
+            ))
+            .stderr(predicate::str::contains("--outline"));
+    }
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--outline",
+            "--edge-characters",
+            "--no-color",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //the vertical edges of the bars and the horizontal edges below them are drawn as lines
+        assert!(output.lines().next().unwrap().contains('|'));
+        assert!(output.lines().any(|line| line.starts_with("---------")));
+    }
+
+    #[test]
+    fn flipped_edges_are_mirrored() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--outline",
+            "--edge-characters",
+            "--no-color",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--outline",
+            "--edge-characters",
+            "--no-color",
+            "--flipX",
+        ]);
+        let flipped = cmd.assert().success().get_output().stdout.clone();
+        let flipped = String::from_utf8(flipped).unwrap();
+
+        //the diagonal edges are mirrored as well
+        let mirrored: String = output
+            .lines()
+            .flat_map(|line| {
+                line.chars()
+                    .rev()
+                    .map(|c| match c {
+                        '/' => '\\',
+                        '\\' => '/',
+                        c => c,
+                    })
+                    .chain(std::iter::once('\n'))
+            })
+            .collect();
+        assert_eq!(mirrored, flipped);
+    }
+}
+
+pub mod outline_sigma {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn outline_is_required() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--outline-sigma", "2"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::starts_with(
+                "error: the following required arguments were not provided:",
+            ))
+            .stderr(predicate::str::contains("--outline"));
+    }
+
+    #[test]
+    fn arg_invalid_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--outline", "--outline-sigma", "0"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "[ERROR] The outline sigma 0 is not a positive, finite number",
+        ));
+    }
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--outline",
+            "--outline-sigma",
+            "1.4",
+        ]);
+        //the gaussian blur keeps more details than the default 3x3 blur, only check first line
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "         oo       .        ,       .       ;x       .        ,       oo         ",
+        ));
+    }
+}
+
+pub mod outline_thresholds {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn hysteresis_is_required() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--outline", "--outline-low", "0.2"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::starts_with(
+                "error: the following required arguments were not provided:",
+            ))
+            .stderr(predicate::str::contains("--hysteresis"));
+    }
+
+    #[test]
+    fn low_above_high_fails() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png").args([
+            "--outline",
+            "--hysteresis",
+            "--outline-low",
+            "0.6",
+        ]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "[ERROR] The outline thresholds 0.6 and 0.5 have to be between 0 and 1, with the lower one first",
+        ));
+    }
+
+    #[test]
+    fn higher_thresholds_remove_edges() {
+        let count_edges = |args: &[&str]| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/standard_test_img.png")
+                .args(["--outline", "--hysteresis"])
+                .args(args);
+            let output = cmd.assert().success().get_output().stdout.clone();
+            String::from_utf8(output)
+                .unwrap()
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .count()
+        };
+        assert!(count_edges(&["--outline-low", "0.8", "--outline-high", "0.9"]) < count_edges(&[]));
+    }
+}
+
+pub mod non_max_suppression {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn outline_is_required() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--non-max-suppression");
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::starts_with(
+                "error: the following required arguments were not provided:",
+            ))
+            .stderr(predicate::str::contains("--outline"));
+    }
+
+    #[test]
+    fn arg_is_correct() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--outline", "--non-max-suppression"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        let outline =
+            std::fs::read_to_string("assets/standard_test_img/standard_test_img_outline.txt")
+                .unwrap();
+        //the thinner edges result in less dense characters
+        assert_ne!(outline, output);
+    }
+}
+
+pub mod exif_orientation {
+    use assert_cmd::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn orientation_is_corrected() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        //the top half of the image is white, but it has to be rotated by 90 degrees clockwise
+        cmd.arg("assets/images/exif_orientation.png")
+            .args(["-c", "# ", "--no-color", "-s", "10"]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        for line in output.lines() {
+            assert!(line.starts_with(' '));
+            assert!(line.ends_with('#'));
+        }
+    }
+}
+
+pub mod rotate {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_is_none() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .arg("--rotate");
+        cmd.assert().failure().stderr(predicate::str::starts_with(
+            "error: a value is required for '--rotate <DEGREES>' but none was supplied",
+        ));
+    }
+
+    #[test]
+    fn exif_and_rotation_are_combined() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/exif_orientation.png").args([
+            "-c",
+            "# ",
+            "--no-color",
+            "-s",
+            "10",
+            "--rotate",
+            "-90",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output).unwrap();
+        //rotating back results in the stored image, with the white half on top
+        assert!(output.lines().next().unwrap().chars().all(|c| c == '#'));
+        assert!(output.lines().last().unwrap().chars().all(|c| c == ' '));
+    }
+
+    #[test]
+    fn arbitrary_angle() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--rotate", "30", "--no-color"]);
+        cmd.assert().success();
+    }
+}
+
+pub mod crop {
+    use assert_cmd::prelude::*;
+    use predicates::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn arg_invalid_value() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--crop", "10,10,50%,50%"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "either all or none of the values must be percentages",
+        ));
+    }
+
+    #[test]
+    fn outside_of_image() {
+        let mut cmd = Command::cargo_bin("artem").unwrap();
+        cmd.arg("assets/images/standard_test_img.png")
+            .args(["--crop", "100000,0,10,10"]);
+        cmd.assert().failure().stderr(predicate::str::contains(
+            "does not contain any part of the image",
+        ));
+    }
+
+    #[test]
+    fn pixels_and_percentage_are_equal() {
+        let convert = |crop: &str| {
+            let mut cmd = Command::cargo_bin("artem").unwrap();
+            cmd.arg("assets/images/exif_orientation.png").args([
+                "-c",
+                "# ",
+                "--no-color",
+                "-s",
+                "10",
+                "--crop",
+                crop,
+            ]);
+            cmd.assert().success().get_output().stdout.clone()
+        };
+        //the crop is applied to the upright image, which is 20x40 pixels
+        assert_eq!(convert("0,0,10,40"), convert("0%,0%,50%,100%"));
+        //only the black half of the image is left
+        assert!(String::from_utf8(convert("0,0,10,40"))
+            .unwrap()
+            .chars()
+            .all(|c| c == ' ' || c == '\n'));
+    }
+}
+
 pub mod border {
     use assert_cmd::prelude::*;
     use predicates::prelude::*;